@@ -1,11 +1,27 @@
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::collections::VecDeque;
 use std::sync::mpsc::Sender;
 use std::time::{Duration, Instant};
 
 use crate::config::TokenSpec;
 use crate::core::Context;
-use crate::domain::abi::AbiRegistry;
+use crate::domain::abi::{AbiLayout, AbiRegistry, FunctionSignature, NestedCall};
+use crate::domain::alerts::{AlertEvent, AlertRule, AlertState};
+use crate::domain::beacon::BeaconStatus;
+use crate::domain::delegation::Authorization;
+use crate::domain::finality::FinalityHeads;
+use crate::domain::metrics::{MetricsWindow, SeriesView};
+use crate::domain::nodes::NodeComparison;
+use crate::domain::optimism::{DepositInfo, L1Fee, L1Origin};
+use crate::domain::budget::{BudgetStatus, Pressure};
+use crate::domain::quorum::QuorumReport;
+use crate::domain::rpc_stats::{sort_rows, RpcStatRow, RpcStatsSort};
+use crate::domain::transfers::TransferSummary;
+use crate::infrastructure::abi::nested::{
+    function_from_signature, nested_calls_for_args, SelectorLookup,
+};
+use crate::infrastructure::runtime;
 use crate::AbiScanRequest;
 use crate::store::LabelStore;
 
@@ -191,6 +207,9 @@ pub struct BlockInfo {
     pub gas_used: u64,
     pub base_fee: u64,
     pub miner: String,
+    pub blob_count: u32,
+    /// Wei; None before Cancun
+    pub blob_base_fee: Option<u128>,
 }
 
 impl BlockInfo {
@@ -205,6 +224,8 @@ impl BlockInfo {
             gas_used,
             base_fee,
             miner,
+            blob_count: (number % 4) as u32,
+            blob_base_fee: Some(1 + (number % 3) as u128),
         }
     }
 }
@@ -222,6 +243,8 @@ pub struct DecodedArg {
     pub name: String,
     pub kind: String,
     pub value: String,
+    /// Calls wrapped in this argument (multicall, Safe, router commands)
+    pub nested: Vec<NestedCall>,
 }
 
 /// Token balance result
@@ -248,6 +271,15 @@ pub struct TxInfo {
     pub decoded_args: Option<Vec<DecodedArg>>,
     pub decode_error: Option<String>,
     pub block_number: u64,
+    pub blob_hashes: Vec<String>,
+    pub max_fee_per_blob_gas: Option<u128>,
+    /// Wei paid for blob gas
+    pub blob_fee: Option<u128>,
+    pub authorizations: Vec<Authorization>,
+    pub deposit: Option<DepositInfo>,
+    /// Wei paid for L2 execution
+    pub execution_fee: Option<u128>,
+    pub l1_fee: Option<L1Fee>,
 }
 
 impl TxInfo {
@@ -285,6 +317,13 @@ impl TxInfo {
             decoded_args: None,
             decode_error: None,
             block_number,
+            blob_hashes: Vec::new(),
+            max_fee_per_blob_gas: None,
+            blob_fee: None,
+            authorizations: Vec::new(),
+            deposit: None,
+            execution_fee: None,
+            l1_fee: None,
         }
     }
 }
@@ -294,6 +333,8 @@ impl TxInfo {
 pub enum AddressKind {
     Eoa,
     Contract,
+    /// EOA running a delegate's code under EIP-7702
+    Delegated,
 }
 
 #[derive(Debug, Clone)]
@@ -381,13 +422,105 @@ pub struct TraceFrame {
     pub note: String,
     pub collapsed: bool,
     pub input: Option<String>,
+    pub output: Option<String>,
     pub selector: Option<String>,
     pub method: Option<String>,
     pub signature: Option<String>,
     pub decoded_args: Option<Vec<DecodedArg>>,
+    pub decoded_output: Option<Vec<DecodedArg>>,
     pub decode_error: Option<String>,
 }
 
+impl From<runtime::BlockInfo> for BlockInfo {
+    fn from(block: runtime::BlockInfo) -> Self {
+        Self {
+            number: block.number,
+            tx_count: block.tx_count,
+            gas_used: block.gas_used,
+            base_fee: block.base_fee,
+            miner: block.miner,
+            blob_count: block.blob_count,
+            blob_base_fee: block.blob_base_fee,
+        }
+    }
+}
+
+impl From<runtime::TxInfo> for TxInfo {
+    fn from(tx: runtime::TxInfo) -> Self {
+        Self {
+            hash: tx.hash,
+            from: tx.from,
+            to: tx.to,
+            value: tx.value,
+            gas_used: tx.gas_used,
+            status: match tx.status {
+                runtime::TxStatus::Success => TxStatus::Success,
+                runtime::TxStatus::Revert => TxStatus::Revert,
+                runtime::TxStatus::Unknown => TxStatus::Unknown,
+            },
+            input: tx.input,
+            selector: tx.selector,
+            method: tx.method,
+            signature: tx.signature,
+            decoded_args: tx.decoded_args.map(|args| {
+                args.into_iter()
+                    .map(|a| DecodedArg {
+                        name: a.name,
+                        kind: a.kind,
+                        value: a.value,
+                        nested: Vec::new(),
+                    })
+                    .collect()
+            }),
+            decode_error: tx.decode_error,
+            block_number: tx.block_number,
+            blob_hashes: tx.blob_hashes,
+            max_fee_per_blob_gas: tx.max_fee_per_blob_gas,
+            blob_fee: tx.blob_fee,
+            authorizations: tx.authorizations,
+            deposit: tx.deposit,
+            execution_fee: tx.execution_fee,
+            l1_fee: tx.l1_fee,
+        }
+    }
+}
+
+impl From<runtime::TraceFrame> for TraceFrame {
+    fn from(f: runtime::TraceFrame) -> Self {
+        Self {
+            depth: f.depth,
+            call: f.call,
+            from: f.from,
+            to: f.to,
+            value: f.value,
+            gas_used: f.gas_used,
+            status: match f.status {
+                runtime::CallStatus::Ok => CallStatus::Ok,
+                runtime::CallStatus::Revert => CallStatus::Revert,
+            },
+            note: f.note,
+            collapsed: f.collapsed,
+            input: f.input,
+            output: f.output,
+            selector: f.selector,
+            method: f.method,
+            signature: f.signature,
+            decoded_args: f.decoded_args.map(|args| {
+                args.into_iter()
+                    .map(|a| DecodedArg {
+                        name: a.name,
+                        kind: a.kind,
+                        value: a.value,
+                        nested: Vec::new(),
+                    })
+                    .collect()
+            }),
+            decoded_output: None,
+            decode_error: f.decode_error,
+        }
+    }
+}
+
 impl TraceFrame {
     fn mock(depth: usize, seed: u64) -> Self {
         let call = match seed % 4 {
@@ -423,10 +556,12 @@ impl TraceFrame {
             note: note.to_string(),
             collapsed: false,
             input: Some(input),
+            output: None,
             selector: Some(selector),
             method: None,
             signature: None,
             decoded_args: None,
+            decoded_output: None,
             decode_error: None,
         }
     }
@@ -445,6 +580,15 @@ pub struct StatusMessage {
     pub since: Instant,
 }
 
+/// One line of the Ops event log
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// Unix seconds
+    pub at: i64,
+    pub level: StatusLevel,
+    pub message: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct PendingChord {
     pub key: char,
@@ -454,7 +598,7 @@ pub struct PendingChord {
 #[derive(Debug, Clone)]
 pub struct StorageRequest {
     pub address: String,
-    pub slot: String,
+    pub slots: Vec<String>,
 }
 
 /// Sections in the Explorer tab sidebar
@@ -576,34 +720,46 @@ impl Default for ToolkitState {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OpsSection {
     Health,
+    Nodes,
+    Beacon,
+    L1Origin,
     Peers,
     Mempool,
     Logs,
     Metrics,
     Alerts,
     RpcStats,
+    Quorum,
 }
 
 impl OpsSection {
-    pub const ALL: [OpsSection; 7] = [
+    pub const ALL: [OpsSection; 11] = [
         OpsSection::Health,
+        OpsSection::Nodes,
+        OpsSection::Beacon,
+        OpsSection::L1Origin,
         OpsSection::Peers,
         OpsSection::Mempool,
         OpsSection::Logs,
         OpsSection::Metrics,
         OpsSection::Alerts,
         OpsSection::RpcStats,
+        OpsSection::Quorum,
     ];
 
     pub fn title(&self) -> &'static str {
         match self {
             OpsSection::Health => "health",
+            OpsSection::Nodes => "nodes",
+            OpsSection::Beacon => "beacon",
+            OpsSection::L1Origin => "l1-origin",
             OpsSection::Peers => "peers",
             OpsSection::Mempool => "mempool",
             OpsSection::Logs => "logs",
             OpsSection::Metrics => "metrics",
             OpsSection::Alerts => "alerts",
             OpsSection::RpcStats => "rpc-stats",
+            OpsSection::Quorum => "quorum",
         }
     }
 }
@@ -635,8 +791,13 @@ pub struct App {
     pub abi_registry: Option<AbiRegistry>,
     pub abi_reload_sender: Option<Sender<AbiScanRequest>>,
     pub abi_scan_roots: Vec<std::path::PathBuf>,
+    pub abi_layouts: Vec<AbiLayout>,
     pub tokens: Vec<TokenSpec>,
+    /// Tokens found through Transfer logs rather than the config
+    pub discovered_tokens: Vec<TokenSpec>,
     pub labels: BTreeMap<String, String>,
+    /// Labels taken from deployment artifacts, rebuilt on every ABI scan
+    pub auto_labels: BTreeMap<String, String>,
     pub label_store: Option<LabelStore>,
     pub blocks: Vec<BlockInfo>,
     pub txs: Vec<TxInfo>,
@@ -650,6 +811,39 @@ pub struct App {
     pub selected_trace: usize,
     pub pinned_blocks: BTreeSet<u64>,
     pub watched_addresses: BTreeSet<String>,
+    /// Watch list last sent to the worker for activity alerts
+    watched_synced: Option<BTreeSet<String>>,
+    pub alert_rules: Vec<AlertRule>,
+    /// Rules currently firing, keyed by rule name
+    pub active_alerts: BTreeMap<String, AlertEvent>,
+    /// Fired/resolved transitions, newest last
+    pub alert_history: VecDeque<AlertEvent>,
+    /// Connections, failovers and errors, newest last
+    pub event_log: VecDeque<LogEntry>,
+    pub metrics_window: MetricsWindow,
+    /// Latest chart data from the worker for `metrics_window`
+    pub metrics: Vec<SeriesView>,
+    pub pending_metrics_window: Option<MetricsWindow>,
+    /// Latest side-by-side round across all endpoints, one comparison per chain
+    pub node_comparison: Option<Vec<NodeComparison>>,
+    /// Whether the worker was last told to poll every endpoint
+    comparison_synced: bool,
+    /// Endpoints answering quorum reads; empty while quorum reads are off
+    pub quorum_endpoints: Vec<String>,
+    pub pending_quorum: Option<bool>,
+    /// Recent quorum reads, newest last
+    pub quorum_reports: VecDeque<QuorumReport>,
+    /// Call counts and latency percentiles per endpoint and method
+    pub rpc_stats: Vec<RpcStatRow>,
+    pub rpc_stats_sort: RpcStatsSort,
+    /// Request budget of the connected endpoint, when it has limits or was throttled
+    pub rpc_budget: Option<BudgetStatus>,
+    /// Consensus client of the connected endpoint, when it has a beacon_url
+    pub beacon: Option<BeaconStatus>,
+    /// L1 origin on OP Stack chains
+    pub l1_origin: Option<L1Origin>,
+    /// Safe and finalized heads of the connected endpoint
+    pub finality: FinalityHeads,
     pub paused: bool,
     pub follow_blocks: bool,
     pub follow_txs: bool,
@@ -661,6 +855,8 @@ pub struct App {
     pub rpc_endpoints: Vec<RpcEndpointOption>,
     pub rpc_endpoint_index: usize,
     pub node_kind: String,
+    /// Chain id reported by the connected node
+    pub chain_id: Option<u64>,
     pub last_rtt_ms: Option<u64>,
     pub peer_count: u32,
     pub sync_progress: f64,
@@ -668,6 +864,9 @@ pub struct App {
     pub pending_chord: Option<PendingChord>,
     pub pending_endpoint_switch: Option<usize>,
     pub pending_trace_request: Option<String>,
+    /// Tx whose asset movements were last requested (avoids re-fetching)
+    pub transfers_requested_for: Option<String>,
+    pub transfer_summary: Option<TransferSummary>,
     pub pending_refresh_request: bool,
     pub pending_balance_request: Option<String>,
     pub pending_storage_request: Option<StorageRequest>,
@@ -675,6 +874,10 @@ pub struct App {
     pub storage_cache: BTreeMap<(String, String), String>,
     /// Cache of resolved function signatures: selector -> (name, full_signature)
     pub signature_cache: BTreeMap<String, (String, String)>,
+    /// EIP-7702 delegates by normalized EOA address
+    pub delegations: BTreeMap<String, String>,
+    /// Functions of ABIs fetched from Sourcify by contract address, kept across local ABI reloads
+    resolved_functions: BTreeMap<String, Vec<FunctionSignature>>,
     pub prompt_context: Option<String>,
     pub settings_open: bool,
     pub help_open: bool,
@@ -702,8 +905,11 @@ impl App {
             abi_registry: None,
             abi_reload_sender: None,
             abi_scan_roots: Vec::new(),
+            abi_layouts: AbiLayout::ALL.to_vec(),
             tokens: Vec::new(),
+            discovered_tokens: Vec::new(),
             labels: BTreeMap::new(),
+            auto_labels: BTreeMap::new(),
             label_store: None,
             blocks: Vec::new(),
             txs: Vec::new(),
@@ -717,6 +923,25 @@ impl App {
             selected_trace: 0,
             pinned_blocks: BTreeSet::new(),
             watched_addresses: BTreeSet::new(),
+            watched_synced: None,
+            alert_rules: Vec::new(),
+            active_alerts: BTreeMap::new(),
+            alert_history: VecDeque::new(),
+            event_log: VecDeque::new(),
+            metrics_window: MetricsWindow::default(),
+            metrics: Vec::new(),
+            pending_metrics_window: None,
+            node_comparison: None,
+            comparison_synced: false,
+            quorum_endpoints: Vec::new(),
+            pending_quorum: None,
+            quorum_reports: VecDeque::new(),
+            rpc_stats: Vec::new(),
+            rpc_stats_sort: RpcStatsSort::default(),
+            rpc_budget: None,
+            beacon: None,
+            l1_origin: None,
+            finality: FinalityHeads::default(),
             paused: false,
             follow_blocks: true,
             follow_txs: true,
@@ -728,6 +953,7 @@ impl App {
             rpc_endpoints: Vec::new(),
             rpc_endpoint_index: 0,
             node_kind: "unknown".to_string(),
+            chain_id: None,
             last_rtt_ms: None,
             peer_count: 0,
             sync_progress: 0.0,
@@ -735,12 +961,16 @@ impl App {
             pending_chord: None,
             pending_endpoint_switch: None,
             pending_trace_request: None,
+            transfers_requested_for: None,
+            transfer_summary: None,
             pending_refresh_request: false,
             pending_balance_request: None,
             pending_storage_request: None,
             token_balances: BTreeMap::new(),
             storage_cache: BTreeMap::new(),
             signature_cache: BTreeMap::new(),
+            delegations: BTreeMap::new(),
+            resolved_functions: BTreeMap::new(),
             prompt_context: None,
             settings_open: false,
             help_open: false,
@@ -1158,12 +1388,15 @@ impl App {
                     self.exit_prompt();
                     return;
                 };
-                let Some(slot) = normalize_storage_slot(input) else {
-                    self.set_status("Invalid slot (use 0x.. or decimal)", StatusLevel::Warn);
+                let Some(slots) = parse_storage_slots(input) else {
+                    self.set_status(
+                        format!("Invalid slots (0x.. or decimal, lists and a..b ranges, max {MAX_STORAGE_BATCH})"),
+                        StatusLevel::Warn,
+                    );
                     self.exit_prompt();
                     return;
                 };
-                self.request_storage_at(address, slot);
+                self.request_storage_at(address, slots);
                 self.exit_prompt();
             }
             PromptKind::Label => {
@@ -1176,14 +1409,15 @@ impl App {
                 let label = input.trim();
                 if label.is_empty() {
                     self.labels.remove(&normalized);
+                    let fallback = self.auto_labels.get(&normalized).cloned();
                     for item in &mut self.addresses {
                         if item.address.eq_ignore_ascii_case(&address) {
-                            item.label = None;
+                            item.label = fallback.clone();
                         }
                     }
                     for item in &mut self.contracts {
                         if item.address.eq_ignore_ascii_case(&address) {
-                            item.label = None;
+                            item.label = fallback.clone();
                         }
                     }
                     if let Some(store) = self.label_store.as_ref() {
@@ -1535,10 +1769,12 @@ impl App {
             .to_string(),
             collapsed: false,
             input: Some(tx.input.clone()),
+            output: None,
             selector: Some(tx.selector.clone()),
             method: Some(tx.method.clone()),
             signature: tx.signature.clone(),
             decoded_args: tx.decoded_args.clone(),
+            decoded_output: None,
             decode_error: tx.decode_error.clone(),
         });
 
@@ -1619,13 +1855,18 @@ impl App {
         {
             Some(idx) => idx,
             None => {
-                let label = self.labels.get(&normalize_hex_address(address)).cloned();
+                let label = self.label_for(address).cloned();
+                let kind = if self.delegate_of(address).is_some() {
+                    AddressKind::Delegated
+                } else {
+                    AddressKind::Eoa
+                };
                 self.addresses.push(AddressInfo {
                     address: address.to_string(),
                     label,
                     balance: 0.0,
                     delta: 0.0,
-                    kind: AddressKind::Eoa,
+                    kind,
                 });
                 self.addresses.len().saturating_sub(1)
             }
@@ -1831,7 +2072,12 @@ impl App {
 
             // Toolkit commands - Phase 3
             Command::Encode(args) => crate::modules::toolkit::encode::encode(args.clone()),
-            Command::Decode(args) => crate::modules::toolkit::decode::decode(args.clone(), &self.signature_cache),
+            Command::Decode(args) => {
+                let lookup = selector_lookup(self.abi_registry.as_ref(), &self.signature_cache);
+                self.toolkit_state.output =
+                    crate::modules::toolkit::decode::decode_tree(args.as_deref(), &lookup).join("\n");
+                crate::modules::toolkit::decode::decode(args.clone(), &self.signature_cache)
+            }
 
             // Toolkit commands - Phase 3 (all implemented)
             Command::Slot(args) => crate::modules::toolkit::slot::slot(args.clone()),
@@ -1854,19 +2100,40 @@ impl App {
             Command::RpcStats => crate::modules::ops::rpc_stats::rpc_stats_simple(
                 self.last_rtt_ms,
                 &self.rpc_endpoint,
+                &self.rpc_stats,
             ),
             Command::Mempool => crate::modules::ops::mempool::mempool_unavailable(),
+            Command::Quorum(arg) => {
+                let enable = match arg.as_deref().map(str::to_lowercase).as_deref() {
+                    None => self.quorum_endpoints.is_empty(),
+                    Some("on") => true,
+                    Some("off") => false,
+                    Some(_) => {
+                        return Action::Notify("Usage: quorum [on|off]".to_string(), NotifyLevel::Warn)
+                    }
+                };
+                if enable && self.rpc_endpoints.len() < 2 {
+                    return Action::Notify(
+                        "Quorum reads need at least two configured endpoints".to_string(),
+                        NotifyLevel::Warn,
+                    );
+                }
+                self.pending_quorum = Some(enable);
+                Action::Notify(
+                    format!("Quorum reads {}...", if enable { "starting" } else { "stopping" }),
+                    NotifyLevel::Info,
+                )
+            }
 
             // Ops commands - Phase 5
             Command::Logs => crate::modules::ops::logs::logs(None),
-            Command::Metrics => crate::modules::ops::metrics::metrics_unavailable(),
+            Command::Metrics => {
+                crate::modules::ops::metrics::metrics_summary(&self.metrics, self.metrics_window)
+            }
             Command::Alerts => {
-                // Use current app state for alert checking
-                crate::modules::ops::alerts::alerts(
-                    self.peer_count,
-                    self.last_rtt_ms,
-                    self.sync_progress < 1.0,
-                )
+                // Rules are evaluated by the worker; report what's firing now
+                let active: Vec<&AlertEvent> = self.active_alerts.values().collect();
+                crate::modules::ops::alerts::alerts(&active, self.alert_rules.len())
             }
 
             // Workflow commands - implemented
@@ -1949,6 +2216,50 @@ impl App {
         self.pending_trace_request.take()
     }
 
+    /// Request the asset movement summary once per tx opened in the detail view
+    pub fn take_transfers_request(&mut self) -> Option<String> {
+        if self.data_mode != DataMode::Rpc || self.current_view() != View::TxDetail {
+            return None;
+        }
+        let hash = self.selected_tx()?.hash.clone();
+        if self
+            .transfers_requested_for
+            .as_ref()
+            .is_some_and(|requested| requested.eq_ignore_ascii_case(&hash))
+        {
+            return None;
+        }
+        self.transfers_requested_for = Some(hash.clone());
+        Some(hash)
+    }
+
+    /// The watch list, whenever it differs from what the worker last saw
+    pub fn take_watchlist_update(&mut self) -> Option<Vec<String>> {
+        if self.watched_synced.as_ref() == Some(&self.watched_addresses) {
+            return None;
+        }
+        self.watched_synced = Some(self.watched_addresses.clone());
+        Some(self.watched_addresses.iter().cloned().collect())
+    }
+
+    pub fn take_metrics_window_request(&mut self) -> Option<MetricsWindow> {
+        self.pending_metrics_window.take()
+    }
+
+    /// Start comparison polling when the Nodes section opens, stop it when it closes
+    pub fn take_node_comparison_update(&mut self) -> Option<bool> {
+        let wanted = self.current_tab == Tab::Ops && self.ops_section == OpsSection::Nodes;
+        if wanted == self.comparison_synced {
+            return None;
+        }
+        self.comparison_synced = wanted;
+        Some(wanted)
+    }
+
+    pub fn take_quorum_request(&mut self) -> Option<bool> {
+        self.pending_quorum.take()
+    }
+
     pub fn take_refresh_request(&mut self) -> bool {
         if self.pending_refresh_request {
             self.pending_refresh_request = false;
@@ -1966,17 +2277,52 @@ impl App {
         self.pending_storage_request.take()
     }
 
-    pub fn apply_abi_registry(&mut self, registry: AbiRegistry) {
+    pub fn apply_abi_registry(&mut self, mut registry: AbiRegistry) {
         let count = registry.len();
         let scan_ms = registry.scan_ms;
+        // Local ABIs win over ones fetched from Sourcify
+        for function in self.resolved_functions.values().flatten() {
+            registry.insert(function.clone());
+        }
+        self.apply_deployment_labels(&registry);
         self.abi_registry = Some(registry);
+        // Re-decorate everything already ingested with the new ABIs
         self.decode_all_txs();
+        self.decorate_trace_with_abi();
         self.set_status(
             format!("ABI loaded: {count} selectors ({scan_ms}ms)"),
             StatusLevel::Info,
         );
     }
 
+    /// Auto-label addresses found in deployment artifacts. User labels always win
+    /// and auto-labels are never written to the label store.
+    fn apply_deployment_labels(&mut self, registry: &AbiRegistry) {
+        self.auto_labels = registry
+            .deployments
+            .iter()
+            .map(|deployment| (normalize_hex_address(&deployment.address), deployment.name.clone()))
+            .collect();
+        for item in &mut self.addresses {
+            let address = normalize_hex_address(&item.address);
+            if !self.labels.contains_key(&address) {
+                item.label = self.auto_labels.get(&address).cloned();
+            }
+        }
+        for item in &mut self.contracts {
+            let address = normalize_hex_address(&item.address);
+            if !self.labels.contains_key(&address) {
+                item.label = self.auto_labels.get(&address).cloned();
+            }
+        }
+    }
+
+    /// User label for an address, falling back to its deployment name
+    pub fn label_for(&self, address: &str) -> Option<&String> {
+        let address = normalize_hex_address(address);
+        self.labels.get(&address).or_else(|| self.auto_labels.get(&address))
+    }
+
     pub fn request_abi_reload(&mut self) {
         let Some(sender) = self.abi_reload_sender.as_ref() else {
             self.set_status("ABI reload unavailable", StatusLevel::Warn);
@@ -1988,6 +2334,7 @@ impl App {
         }
         match sender.send(AbiScanRequest {
             roots: self.abi_scan_roots.clone(),
+            layouts: self.abi_layouts.clone(),
         }) {
             Ok(()) => self.set_status("Scanning ABI…", StatusLevel::Info),
             Err(err) => self.set_status(format!("ABI reload failed: {err}"), StatusLevel::Warn),
@@ -2024,22 +2371,32 @@ impl App {
         self.set_status("Fetching asset snapshot…", StatusLevel::Info);
     }
 
-    pub fn request_storage_at(&mut self, address: String, slot: String) {
+    pub fn request_storage_at(&mut self, address: String, slots: Vec<String>) {
         if self.data_mode != DataMode::Rpc {
             self.set_status("Storage poke requires RPC mode", StatusLevel::Warn);
             return;
         }
-        self.pending_storage_request = Some(StorageRequest { address, slot });
-        self.set_status("Fetching storage…", StatusLevel::Info);
+        let message = if slots.len() == 1 {
+            "Fetching storage…".to_string()
+        } else {
+            format!("Fetching {} storage slots…", slots.len())
+        };
+        self.pending_storage_request = Some(StorageRequest { address, slots });
+        self.set_status(message, StatusLevel::Info);
     }
 
     pub fn apply_rpc_connected(
         &mut self,
         endpoint: String,
         node_kind: String,
+        chain_id: Option<u64>,
         accounts: Vec<String>,
     ) {
         self.data_mode = DataMode::Rpc;
+        self.log_event(
+            StatusLevel::Info,
+            format!("Connected to {} ({})", endpoint, node_kind),
+        );
         self.rpc_endpoint = endpoint;
         if let Some(index) = self
             .rpc_endpoints
@@ -2049,15 +2406,18 @@ impl App {
             self.rpc_endpoint_index = index;
         }
         self.node_kind = node_kind;
+        self.chain_id = chain_id;
         self.blocks.clear();
         self.txs.clear();
         self.traces.clear();
         self.addresses.clear();
         self.contracts.clear();
         self.token_balances.clear();
+        self.finality = FinalityHeads::default();
         self.storage_cache.clear();
+        self.delegations.clear();
         for addr in accounts {
-            let label = self.labels.get(&normalize_hex_address(&addr)).cloned();
+            let label = self.label_for(&addr).cloned();
             self.addresses.push(AddressInfo {
                 address: addr,
                 label,
@@ -2097,6 +2457,7 @@ impl App {
     pub fn ingest_block(&mut self, block: BlockInfo, mut txs: Vec<TxInfo>) {
         self.decorate_txs_with_abi(&mut txs);
         self.observe_contracts_from_txs(&txs, block.number);
+        self.observe_authorizations(&txs);
         // Only auto-follow if explicitly enabled AND in Overview (not detail views)
         let in_detail_view = self.current_view() != View::Overview;
         let was_tail = self.follow_blocks
@@ -2165,6 +2526,21 @@ impl App {
         self.set_status("Trace loaded", StatusLevel::Info);
     }
 
+    pub fn apply_transfer_summary(&mut self, summary: TransferSummary) {
+        // Remember token metadata discovered on chain for configured tokens
+        for spec in &mut self.tokens {
+            if let Some(meta) = summary.tokens.get(&normalize_hex_address(&spec.address)) {
+                if spec.symbol.is_none() {
+                    spec.symbol = meta.symbol.clone();
+                }
+                if spec.decimals.is_none() {
+                    spec.decimals = meta.decimals;
+                }
+            }
+        }
+        self.transfer_summary = Some(summary);
+    }
+
     pub fn apply_balance(&mut self, address: String, balance_eth: f64) {
         let mut applied = false;
         for item in &mut self.addresses {
@@ -2198,10 +2574,25 @@ impl App {
         for balance in balances {
             let token = normalize_hex_address(&balance.token);
             self.token_balances
-                .insert((owner.clone(), token), balance.balance);
-            if let Some(spec) = self.tokens.iter_mut().find(|spec| {
-                normalize_hex_address(&spec.address) == normalize_hex_address(&balance.token)
-            }) {
+                .insert((owner.clone(), token.clone()), balance.balance);
+            let known = self
+                .tokens
+                .iter()
+                .chain(self.discovered_tokens.iter())
+                .any(|spec| spec.normalized_address() == token);
+            if !known {
+                self.discovered_tokens.push(TokenSpec {
+                    address: token.clone(),
+                    symbol: None,
+                    decimals: None,
+                });
+            }
+            if let Some(spec) = self
+                .tokens
+                .iter_mut()
+                .chain(self.discovered_tokens.iter_mut())
+                .find(|spec| spec.normalized_address() == token)
+            {
                 if spec
                     .symbol
                     .as_ref()
@@ -2248,10 +2639,237 @@ impl App {
         }
     }
 
+    /// Merge a contract ABI fetched from Sourcify, e.g. a delegated EOA's delegate
+    pub fn apply_resolved_abi(
+        &mut self,
+        address: String,
+        contract_name: Option<String>,
+        functions: Vec<FunctionSignature>,
+    ) {
+        let key = normalize_hex_address(&address);
+        // A second lookup of the same contract adds nothing
+        if self.resolved_functions.contains_key(&key) {
+            return;
+        }
+        let count = functions.len();
+        let registry = self.abi_registry.get_or_insert_with(AbiRegistry::new);
+        for function in &functions {
+            registry.insert(function.clone());
+        }
+        self.resolved_functions.insert(key, functions);
+        self.decode_all_txs();
+        self.decorate_trace_with_abi();
+        let name = contract_name.unwrap_or(address);
+        self.set_status(format!("ABI resolved for {name}: {count} functions"), StatusLevel::Info);
+    }
+
+    /// Record the delegate of an inspected address, as read from its code
+    pub fn apply_delegation(&mut self, address: String, delegate: Option<String>) {
+        if let Some(delegate) = delegate.as_ref() {
+            self.set_status(format!("Delegated EOA -> {delegate}"), StatusLevel::Info);
+        }
+        self.set_delegation(&address, delegate);
+    }
+
+    fn set_delegation(&mut self, address: &str, delegate: Option<String>) {
+        let key = normalize_hex_address(address);
+        let delegated = delegate.is_some();
+        match delegate {
+            Some(delegate) => self.delegations.insert(key, delegate),
+            None => self.delegations.remove(&key),
+        };
+        for item in &mut self.addresses {
+            if !item.address.eq_ignore_ascii_case(address) {
+                continue;
+            }
+            if delegated {
+                item.kind = AddressKind::Delegated;
+            } else if item.kind == AddressKind::Delegated {
+                item.kind = AddressKind::Eoa;
+            }
+        }
+    }
+
+    /// Follow delegations set by type 4 transactions. Entries for another chain are
+    /// skipped; ones the node skipped for a stale nonce are only corrected once the
+    /// address is inspected
+    fn observe_authorizations(&mut self, txs: &[TxInfo]) {
+        for auth in txs.iter().flat_map(|tx| &tx.authorizations) {
+            if auth.chain_id != 0 && Some(auth.chain_id) != self.chain_id {
+                continue;
+            }
+            let Some(authority) = auth.authority.as_ref() else {
+                continue;
+            };
+            let delegate = (!auth.clears()).then(|| auth.delegate.clone());
+            self.set_delegation(authority, delegate);
+        }
+    }
+
+    pub fn delegate_of(&self, address: &str) -> Option<&String> {
+        self.delegations.get(&normalize_hex_address(address))
+    }
+
+    /// Switch the metrics charts to the next window (5m -> 1h -> 24h)
+    pub fn cycle_metrics_window(&mut self) {
+        self.metrics_window = self.metrics_window.next();
+        self.pending_metrics_window = Some(self.metrics_window);
+    }
+
+    pub fn apply_metrics(&mut self, window: MetricsWindow, series: Vec<SeriesView>) {
+        // Ignore a tick computed for the previous window
+        if window == self.metrics_window {
+            self.metrics = series;
+        }
+    }
+
+    pub fn apply_rpc_stats(&mut self, mut rows: Vec<RpcStatRow>) {
+        sort_rows(&mut rows, self.rpc_stats_sort);
+        self.rpc_stats = rows;
+    }
+
+    /// Tell the user when the worker slows down for an endpoint's rate limits
+    pub fn apply_budget(&mut self, endpoint: String, status: BudgetStatus) {
+        let previous = self.rpc_budget.as_ref().map_or(Pressure::Normal, |b| b.pressure);
+        if status.pressure != previous {
+            let (message, level) = match status.pressure {
+                Pressure::Normal => (format!("{endpoint}: request budget recovered"), StatusLevel::Info),
+                pressure => (
+                    format!(
+                        "{endpoint}: rate limited, polling every {}s{}",
+                        pressure.poll_interval().as_secs(),
+                        if pressure.fetch_receipts() { "" } else { ", receipts skipped" }
+                    ),
+                    StatusLevel::Warn,
+                ),
+            };
+            self.log_event(level, message.clone());
+            self.set_status(message, level);
+        }
+        self.rpc_budget = Some(status);
+    }
+
+    /// Highest block seen from the execution client
+    pub fn head_number(&self) -> Option<u64> {
+        self.blocks.iter().map(|block| block.number).max()
+    }
+
+    pub fn apply_finality(&mut self, heads: FinalityHeads) {
+        self.finality = heads;
+    }
+
+    /// Log consensus-layer problems as they appear
+    pub fn apply_beacon(&mut self, status: Option<BeaconStatus>) {
+        if let Some(status) = &status {
+            let head = self.head_number();
+            // Counts change every slot; only log when a kind of problem appears
+            let kind = |warning: &String| -> String {
+                warning.chars().filter(|c| !c.is_ascii_digit()).collect()
+            };
+            let previous: Vec<String> = self
+                .beacon
+                .as_ref()
+                .map(|beacon| beacon.warnings(head).iter().map(kind).collect())
+                .unwrap_or_default();
+            for warning in status.warnings(head) {
+                if !previous.contains(&kind(&warning)) {
+                    self.log_event(StatusLevel::Warn, format!("Beacon: {warning}"));
+                }
+            }
+        }
+        self.beacon = status;
+    }
+
+    pub fn apply_l1_origin(&mut self, origin: Option<L1Origin>) {
+        self.l1_origin = origin;
+    }
+
+    /// Sort the RPC stats table by the next column
+    pub fn cycle_rpc_stats_sort(&mut self) {
+        self.rpc_stats_sort = self.rpc_stats_sort.next();
+        sort_rows(&mut self.rpc_stats, self.rpc_stats_sort);
+    }
+
+    pub fn apply_quorum(&mut self, endpoints: Vec<String>) {
+        let message = if endpoints.is_empty() {
+            "Quorum reads off".to_string()
+        } else {
+            format!("Quorum reads across {} endpoints", endpoints.len())
+        };
+        self.log_event(StatusLevel::Info, message.clone());
+        self.set_status(message, StatusLevel::Info);
+        self.quorum_endpoints = endpoints;
+    }
+
+    /// Flag reads where members disagree; the worker already used the majority answer
+    pub fn apply_quorum_report(&mut self, report: QuorumReport) {
+        if !report.agreed() {
+            let divergent: Vec<&str> = report
+                .divergent()
+                .iter()
+                .map(|response| response.endpoint.as_str())
+                .collect();
+            let message = format!(
+                "Quorum mismatch on {} {}: {}",
+                report.method,
+                report.params,
+                divergent.join(", ")
+            );
+            self.log_event(StatusLevel::Warn, message.clone());
+            self.set_status(message, StatusLevel::Warn);
+        }
+        self.quorum_reports.push_back(report);
+        while self.quorum_reports.len() > MAX_QUORUM_REPORTS {
+            self.quorum_reports.pop_front();
+        }
+    }
+
+    pub fn apply_node_comparison(&mut self, comparisons: Vec<NodeComparison>) {
+        self.node_comparison = Some(comparisons);
+    }
+
+    pub fn apply_alert(&mut self, event: AlertEvent) {
+        match event.state {
+            AlertState::Fired => {
+                self.set_status(
+                    format!("Alert {}: {} ({})", event.rule, event.message, event.detail),
+                    StatusLevel::Warn,
+                );
+                self.active_alerts.insert(event.rule.clone(), event.clone());
+            }
+            AlertState::Resolved => {
+                self.active_alerts.remove(&event.rule);
+            }
+        }
+        self.alert_history.push_back(event);
+        while self.alert_history.len() > MAX_ALERT_HISTORY {
+            self.alert_history.pop_front();
+        }
+    }
+
     pub fn apply_rpc_error(&mut self, message: String) {
+        self.log_event(StatusLevel::Error, message.clone());
         self.set_status(message, StatusLevel::Error);
     }
 
+    /// The worker left an endpoint on its own: failover, or failback once it recovered
+    pub fn apply_failover(&mut self, from: String, to: String, reason: String) {
+        let message = format!("Switched {} -> {} ({})", from, to, reason);
+        self.log_event(StatusLevel::Warn, message.clone());
+        self.set_status(message, StatusLevel::Warn);
+    }
+
+    pub fn log_event(&mut self, level: StatusLevel, message: impl Into<String>) {
+        self.event_log.push_back(LogEntry {
+            at: chrono::Utc::now().timestamp(),
+            level,
+            message: message.into(),
+        });
+        while self.event_log.len() > MAX_EVENT_LOG {
+            self.event_log.pop_front();
+        }
+    }
+
     pub fn set_list_selection(&mut self, selection: usize) {
         match self.list_kind() {
             ListKind::Blocks => {
@@ -2345,99 +2963,15 @@ impl App {
     }
 
     fn decode_all_txs(&mut self) {
-        for tx in &mut self.txs {
-            // First try local ABI registry
-            if let Some(registry) = self.abi_registry.as_ref() {
-                if let Some(function) = registry.lookup_hex(&tx.selector) {
-                    tx.method = function.name.clone();
-                    tx.signature = Some(function.signature.clone());
-                    match decode_calldata_hex(function, &tx.input) {
-                        Ok(args) => {
-                            tx.decoded_args = Some(args);
-                            tx.decode_error = None;
-                        }
-                        Err(err) => {
-                            tx.decoded_args = None;
-                            tx.decode_error = Some(err.to_string());
-                        }
-                    }
-                    continue;
-                }
-            }
-
-            // Fallback to signature cache (from 4byte lookups)
-            if let Some((name, sig)) = self.signature_cache.get(&tx.selector) {
-                tx.method = name.clone();
-                tx.signature = Some(sig.clone());
-            } else {
-                tx.decoded_args = None;
-                tx.decode_error = None;
-            }
-        }
+        decorate_txs(&mut self.txs, self.abi_registry.as_ref(), &self.signature_cache);
     }
 
-    fn decorate_txs_with_abi(&self, txs: &mut [TxInfo]) {
-        for tx in txs {
-            // First try local ABI registry
-            if let Some(registry) = self.abi_registry.as_ref() {
-                if let Some(function) = registry.lookup_hex(&tx.selector) {
-                    tx.method = function.name.clone();
-                    tx.signature = Some(function.signature.clone());
-                    match decode_calldata_hex(function, &tx.input) {
-                        Ok(args) => {
-                            tx.decoded_args = Some(args);
-                            tx.decode_error = None;
-                        }
-                        Err(err) => {
-                            tx.decoded_args = None;
-                            tx.decode_error = Some(err.to_string());
-                        }
-                    }
-                    continue;
-                }
-            }
-
-            // Fallback to signature cache (from 4byte lookups)
-            if let Some((name, sig)) = self.signature_cache.get(&tx.selector) {
-                tx.method = name.clone();
-                tx.signature = Some(sig.clone());
-            }
-        }
+    pub fn decorate_txs_with_abi(&self, txs: &mut [TxInfo]) {
+        decorate_txs(txs, self.abi_registry.as_ref(), &self.signature_cache);
     }
 
     fn decorate_trace_with_abi(&mut self) {
-        for frame in &mut self.traces {
-            let Some(selector) = frame.selector.as_ref() else {
-                continue;
-            };
-
-            // First try local ABI registry
-            if let Some(registry) = self.abi_registry.as_ref() {
-                if let Some(function) = registry.lookup_hex(selector) {
-                    frame.method = Some(function.name.clone());
-                    frame.signature = Some(function.signature.clone());
-                    if let Some(input) = frame.input.as_ref() {
-                        match decode_calldata_hex(function, input) {
-                            Ok(args) => {
-                                frame.decoded_args = Some(args);
-                                frame.decode_error = None;
-                            }
-                            Err(err) => {
-                                frame.decoded_args = None;
-                                frame.decode_error = Some(err.to_string());
-                            }
-                        }
-                    }
-                    continue;
-                }
-            }
-
-            // Fallback to signature cache (from 4byte lookups)
-            if let Some((name, sig)) = self.signature_cache.get(selector) {
-                frame.method = Some(name.clone());
-                frame.signature = Some(sig.clone());
-            }
-        }
+        decorate_trace(&mut self.traces, self.abi_registry.as_ref(), &self.signature_cache);
     }
 
     fn observe_contracts_from_txs(&mut self, txs: &[TxInfo], block_number: u64) {
@@ -2463,7 +2997,7 @@ impl App {
                 existing.last_call = block_number;
                 continue;
             }
-            let label = self.labels.get(&normalize_hex_address(&tx.to)).cloned();
+            let label = self.label_for(&tx.to).cloned();
             self.contracts.push(ContractInfo {
                 address: tx.to.clone(),
                 label,
@@ -2497,6 +3031,48 @@ fn normalize_hex_address(value: &str) -> String {
     format!("0x{}", payload.to_lowercase())
 }
 
+/// Alert transitions kept for the Alerts panel
+const MAX_ALERT_HISTORY: usize = 200;
+
+/// Quorum reads kept for the Ops panel
+const MAX_QUORUM_REPORTS: usize = 100;
+
+/// Entries kept for the Ops event log
+const MAX_EVENT_LOG: usize = 200;
+
+/// Most slots one storage prompt may read
+const MAX_STORAGE_BATCH: usize = 64;
+
+/// Parse a slot list such as `0, 1, 0x5..0x8` (ranges are end-exclusive)
+fn parse_storage_slots(input: &str) -> Option<Vec<String>> {
+    let mut slots = Vec::new();
+    for part in input.split(|ch: char| ch == ',' || ch.is_whitespace()) {
+        if part.is_empty() {
+            continue;
+        }
+        if let Some((start, end)) = part.split_once("..") {
+            let start = parse_slot_number(start)?;
+            let end = parse_slot_number(end)?;
+            if end <= start || (end - start) as usize > MAX_STORAGE_BATCH {
+                return None;
+            }
+            slots.extend((start..end).map(|slot| format!("0x{slot:064x}")));
+        } else {
+            slots.push(normalize_storage_slot(part)?);
+        }
+    }
+    if slots.is_empty() || slots.len() > MAX_STORAGE_BATCH {
+        return None;
+    }
+    slots.dedup();
+    Some(slots)
+}
+
+fn parse_slot_number(input: &str) -> Option<u128> {
+    let slot = normalize_storage_slot(input)?;
+    u128::from_str_radix(&slot[2..], 16).ok()
+}
+
 fn normalize_storage_slot(input: &str) -> Option<String> {
     let trimmed = input.trim();
     if trimmed.is_empty() {
@@ -2661,10 +3237,92 @@ fn seed_from_str(value: &str) -> u64 {
     })
 }
 
+/// Name and decode each transaction's calldata: local ABI registry first, then
+/// names from the 4byte signature cache
+pub fn decorate_txs(
+    txs: &mut [TxInfo],
+    registry: Option<&AbiRegistry>,
+    signature_cache: &BTreeMap<String, (String, String)>,
+) {
+    let lookup = selector_lookup(registry, signature_cache);
+    for tx in txs {
+        // First try local ABI registry
+        if let Some(function) = registry.and_then(|registry| registry.lookup_hex(&tx.selector)) {
+            tx.method = function.name.clone();
+            tx.signature = Some(function.signature.clone());
+            match decode_calldata_hex(function, &tx.input, &lookup) {
+                Ok(args) => {
+                    tx.decoded_args = Some(args);
+                    tx.decode_error = None;
+                }
+                Err(err) => {
+                    tx.decoded_args = None;
+                    tx.decode_error = Some(err.to_string());
+                }
+            }
+            continue;
+        }
+
+        // Fallback to signature cache (from 4byte lookups)
+        if let Some((name, sig)) = signature_cache.get(&tx.selector) {
+            tx.method = name.clone();
+            tx.signature = Some(sig.clone());
+        } else {
+            tx.decoded_args = None;
+            tx.decode_error = None;
+        }
+    }
+}
+
+/// Name trace frames and decode their inputs and return values, like `decorate_txs`
+pub fn decorate_trace(
+    frames: &mut [TraceFrame],
+    registry: Option<&AbiRegistry>,
+    signature_cache: &BTreeMap<String, (String, String)>,
+) {
+    let lookup = selector_lookup(registry, signature_cache);
+    for frame in frames {
+        let Some(selector) = frame.selector.as_ref() else {
+            continue;
+        };
+
+        // First try local ABI registry
+        if let Some(function) = registry.and_then(|registry| registry.lookup_hex(selector)) {
+            frame.method = Some(function.name.clone());
+            frame.signature = Some(function.signature.clone());
+            if let Some(input) = frame.input.as_ref() {
+                match decode_calldata_hex(function, input, &lookup) {
+                    Ok(args) => {
+                        frame.decoded_args = Some(args);
+                        frame.decode_error = None;
+                    }
+                    Err(err) => {
+                        frame.decoded_args = None;
+                        frame.decode_error = Some(err.to_string());
+                    }
+                }
+            }
+            // Reverted frames carry revert data, not return values
+            frame.decoded_output = match (frame.status, frame.output.as_ref()) {
+                (CallStatus::Ok, Some(output)) => decode_return_hex(function, output).ok(),
+                _ => None,
+            };
+            continue;
+        }
+
+        // Fallback to signature cache (from 4byte lookups)
+        if let Some((name, sig)) = signature_cache.get(selector) {
+            frame.method = Some(name.clone());
+            frame.signature = Some(sig.clone());
+        }
+    }
+}
+
 /// Decode calldata from hex string using alloy-dyn-abi
 fn decode_calldata_hex(
-    function: &crate::domain::abi::FunctionSignature,
+    function: &FunctionSignature,
     input_hex: &str,
+    lookup: &SelectorLookup,
 ) -> anyhow::Result<Vec<DecodedArg>> {
     use alloy_dyn_abi::{DynSolType, DynSolValue};
 
@@ -2696,9 +3354,10 @@ fn decode_calldata_hex(
     let decoded_values = if types.is_empty() {
         Vec::new()
     } else {
+        // Calldata is a parameter list, not a single (possibly dynamic) tuple
         let tuple_type = DynSolType::Tuple(types);
         let decoded = tuple_type
-            .abi_decode(args_data)
+            .abi_decode_params(args_data)
             .map_err(|e| anyhow::anyhow!("Failed to decode calldata: {}", e))?;
 
         match decoded {
@@ -2707,13 +3366,16 @@ fn decode_calldata_hex(
         }
     };
 
+    let nested = nested_calls_for_args(function, &decoded_values, lookup);
+
     // Build decoded arguments
     let arguments: Vec<DecodedArg> = function
         .inputs
         .iter()
         .zip(decoded_values.iter())
+        .zip(nested)
         .enumerate()
-        .map(|(idx, (param, value))| {
+        .map(|(idx, ((param, value), nested))| {
             let name = if param.name.trim().is_empty() {
                 format!("arg{}", idx)
             } else {
@@ -2724,6 +3386,7 @@ fn decode_calldata_hex(
                 name,
                 kind: param.kind.clone(),
                 value: format_dyn_sol_value(value),
+                nested,
             }
         })
         .collect();
@@ -2731,6 +3394,71 @@ fn decode_calldata_hex(
     Ok(arguments)
 }
 
+/// Decode call return data against the function's output types
+fn decode_return_hex(function: &FunctionSignature, output_hex: &str) -> anyhow::Result<Vec<DecodedArg>> {
+    use alloy_dyn_abi::{DynSolType, DynSolValue};
+
+    if function.outputs.is_empty() {
+        anyhow::bail!("no output types for {}", function.signature);
+    }
+
+    let normalized = output_hex
+        .strip_prefix("0x")
+        .or_else(|| output_hex.strip_prefix("0X"))
+        .unwrap_or(output_hex);
+    let data = hex::decode(normalized).map_err(|e| anyhow::anyhow!("Invalid hex output: {}", e))?;
+
+    let types: Vec<DynSolType> = function
+        .outputs
+        .iter()
+        .map(|param| {
+            param.kind.parse::<DynSolType>()
+                .map_err(|e| anyhow::anyhow!("Failed to parse type '{}': {}", param.kind, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let values = match DynSolType::Tuple(types)
+        .abi_decode_params(&data)
+        .map_err(|e| anyhow::anyhow!("Failed to decode return data: {}", e))?
+    {
+        DynSolValue::Tuple(values) => values,
+        other => vec![other],
+    };
+
+    Ok(function
+        .outputs
+        .iter()
+        .zip(values.iter())
+        .enumerate()
+        .map(|(idx, (param, value))| DecodedArg {
+            name: if param.name.trim().is_empty() {
+                format!("ret{}", idx)
+            } else {
+                param.name.clone()
+            },
+            kind: param.kind.clone(),
+            value: format_dyn_sol_value(value),
+            nested: Vec::new(),
+        })
+        .collect())
+}
+
+/// Resolve selectors for nested calldata: local ABI registry first, then the 4byte cache
+fn selector_lookup<'a>(
+    registry: Option<&'a AbiRegistry>,
+    signature_cache: &'a BTreeMap<String, (String, String)>,
+) -> impl Fn(&str) -> Option<FunctionSignature> + 'a {
+    move |selector: &str| {
+        registry
+            .and_then(|registry| registry.lookup_hex(selector).cloned())
+            .or_else(|| {
+                signature_cache
+                    .get(selector)
+                    .and_then(|(_, sig)| function_from_signature(sig))
+            })
+    }
+}
+
 /// Format a DynSolValue for display
 fn format_dyn_sol_value(value: &alloy_dyn_abi::DynSolValue) -> String {
     use alloy_dyn_abi::DynSolValue;
@@ -2784,3 +3512,151 @@ fn format_dyn_sol_value(value: &alloy_dyn_abi::DynSolValue) -> String {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::abi::{ContractDeployment, ParamSpec};
+    use alloy_dyn_abi::DynSolValue;
+    use alloy_primitives::{Address, U256};
+
+    fn function(outputs: &[(&str, &str)]) -> FunctionSignature {
+        FunctionSignature {
+            selector: [0; 4],
+            name: "probe".to_string(),
+            signature: "probe()".to_string(),
+            inputs: Vec::new(),
+            outputs: outputs
+                .iter()
+                .map(|(name, kind)| ParamSpec {
+                    name: name.to_string(),
+                    kind: kind.to_string(),
+                })
+                .collect(),
+            source: std::path::PathBuf::new(),
+        }
+    }
+
+    fn encode(values: Vec<DynSolValue>) -> String {
+        format!("0x{}", hex::encode(DynSolValue::Tuple(values).abi_encode_params()))
+    }
+
+    #[test]
+    fn test_decode_return_static_outputs() {
+        let owner: Address = "0x1111111111111111111111111111111111111111".parse().unwrap();
+        let output = encode(vec![
+            DynSolValue::Uint(U256::from(42u64), 256),
+            DynSolValue::Address(owner),
+        ]);
+        let decoded = decode_return_hex(&function(&[("amount", "uint256"), ("", "address")]), &output)
+            .unwrap();
+
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].name, "amount");
+        assert_eq!(decoded[0].value, "42");
+        assert_eq!(decoded[1].name, "ret1");
+        assert_eq!(decoded[1].kind, "address");
+        assert!(decoded[1].value.eq_ignore_ascii_case(&format!("{owner:?}")));
+    }
+
+    #[test]
+    fn test_decode_return_dynamic_outputs() {
+        let output = encode(vec![
+            DynSolValue::String("Wrapped Ether".to_string()),
+            DynSolValue::Array(vec![
+                DynSolValue::Uint(U256::from(1u64), 256),
+                DynSolValue::Uint(U256::from(2u64), 256),
+            ]),
+        ]);
+        let decoded =
+            decode_return_hex(&function(&[("name", "string"), ("ids", "uint256[]")]), &output)
+                .unwrap();
+
+        assert_eq!(decoded[0].value, "\"Wrapped Ether\"");
+        assert_eq!(decoded[1].value, "[1, 2]");
+    }
+
+    #[test]
+    fn test_decode_return_rejects_malformed_output() {
+        let balance = function(&[("", "uint256")]);
+        assert!(decode_return_hex(&balance, "0xzz").is_err());
+        assert!(decode_return_hex(&balance, "0x1234").is_err());
+        assert!(decode_return_hex(&function(&[]), "0x").is_err());
+    }
+
+    #[test]
+    fn test_parse_storage_slots() {
+        assert_eq!(
+            parse_storage_slots("0x0, 1").unwrap(),
+            vec![format!("0x{:064x}", 0), format!("0x{:064x}", 1)]
+        );
+        assert_eq!(parse_storage_slots("2..5").unwrap().len(), 3);
+        assert_eq!(parse_storage_slots("0x05 5").unwrap(), vec![format!("0x{:064x}", 5)]);
+    }
+
+    #[test]
+    fn test_parse_storage_slots_rejects_bad_input() {
+        assert!(parse_storage_slots("").is_none());
+        assert!(parse_storage_slots("5..2").is_none());
+        assert!(parse_storage_slots(&format!("0..{}", MAX_STORAGE_BATCH + 1)).is_none());
+        assert!(parse_storage_slots("slot").is_none());
+    }
+
+    #[test]
+    fn test_resolved_abi_merged_once_per_address() {
+        let mut app = App::new();
+        app.apply_resolved_abi(format!("0x{:040x}", 0xab), None, vec![function(&[])]);
+        app.apply_resolved_abi(format!("0x{:040X}", 0xab), None, vec![function(&[])]);
+        assert_eq!(app.resolved_functions.len(), 1);
+        assert_eq!(app.resolved_functions.values().flatten().count(), 1);
+    }
+
+    #[test]
+    fn test_authorizations_for_other_chains_ignored() {
+        let mut app = App::new();
+        app.chain_id = Some(1);
+        let authorization = |chain_id: u64, authority: u64| Authorization {
+            chain_id,
+            delegate: format!("0x{:040x}", 0x7702),
+            nonce: 0,
+            authority: Some(format!("0x{authority:040x}")),
+        };
+        let mut tx = TxInfo::mock(1, 100);
+        tx.authorizations = vec![authorization(10, 1), authorization(0, 2), authorization(1, 3)];
+        app.observe_authorizations(&[tx]);
+
+        assert_eq!(app.delegate_of(&format!("0x{:040x}", 1)), None);
+        assert!(app.delegate_of(&format!("0x{:040x}", 2)).is_some());
+        assert!(app.delegate_of(&format!("0x{:040x}", 3)).is_some());
+    }
+
+    #[test]
+    fn test_deployment_labels_kept_apart_from_user_labels() {
+        let deployed = format!("0x{:040x}", 0xd1);
+        let named = format!("0x{:040x}", 0xd2);
+        let registry = |names: &[(&String, &str)]| {
+            let mut registry = AbiRegistry::new();
+            registry.deployments = names
+                .iter()
+                .map(|(address, name)| ContractDeployment {
+                    address: address.to_string(),
+                    name: name.to_string(),
+                    network: None,
+                    source: std::path::PathBuf::new(),
+                })
+                .collect();
+            registry
+        };
+        let mut app = App::new();
+        app.labels.insert(named.clone(), "mine".to_string());
+        app.apply_deployment_labels(&registry(&[(&deployed, "Vault"), (&named, "Token")]));
+
+        assert!(!app.labels.contains_key(&deployed));
+        assert_eq!(app.label_for(&deployed).map(String::as_str), Some("Vault"));
+        assert_eq!(app.label_for(&named).map(String::as_str), Some("mine"));
+
+        app.apply_deployment_labels(&registry(&[]));
+        assert_eq!(app.label_for(&deployed), None);
+        assert_eq!(app.label_for(&named).map(String::as_str), Some("mine"));
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -10,11 +11,138 @@ pub struct TokenSpec {
     pub decimals: Option<u8>,
 }
 
+/// String values in `[[endpoints]]` may reference environment variables as
+/// `${NAME}`; unset ones are recorded so the endpoint is refused, not connected
+/// with a placeholder
 #[derive(Debug, Clone, Deserialize)]
 pub struct EndpointConfig {
     pub name: Option<String>,
     pub rpc: Option<String>,
+    pub ws: Option<String>,
     pub ipc: Option<String>,
+    /// Extra HTTP headers sent with every request, e.g. an API key
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    pub basic_auth: Option<BasicAuthConfig>,
+    /// Hex key signing an HS256 bearer token per request, as for the engine API
+    pub jwt_secret: Option<String>,
+    /// File holding the JWT key, e.g. a node's jwt.hex
+    pub jwt_secret_file: Option<String>,
+    /// Node Prometheus endpoint, e.g. http://localhost:6060/debug/metrics/prometheus
+    pub metrics_url: Option<String>,
+    /// Consensus client REST API, e.g. http://localhost:5052
+    pub beacon_url: Option<String>,
+    /// Extra HTTP headers for the beacon API; the RPC credentials are never sent to it
+    #[serde(default)]
+    pub beacon_headers: BTreeMap<String, String>,
+    pub beacon_basic_auth: Option<BasicAuthConfig>,
+    /// Requests per second allowed by the provider
+    pub max_rps: Option<f64>,
+    /// Compute units per second allowed by the provider
+    pub cu_per_second: Option<f64>,
+    /// Compute units to spend per day; polling slows down as it runs out
+    pub daily_cu: Option<u64>,
+    /// Compute units per method, overriding the built-in costs
+    #[serde(default)]
+    pub cu_costs: BTreeMap<String, u32>,
+    /// `${NAME}` references left unresolved because the variable is unset
+    #[serde(skip)]
+    pub unresolved_vars: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BasicAuthConfig {
+    pub username: String,
+    pub password: String,
+}
+
+impl EndpointConfig {
+    /// Substitute `${NAME}` references from the environment
+    fn expand_env(&mut self) {
+        let lookup = |name: &str| std::env::var(name).ok();
+        let mut unresolved = Vec::new();
+        let mut expand = |value: &mut String| match expand_vars(value, lookup) {
+            Ok(expanded) => *value = expanded,
+            Err(names) => unresolved.extend(names),
+        };
+        for value in [
+            &mut self.rpc,
+            &mut self.ws,
+            &mut self.ipc,
+            &mut self.metrics_url,
+            &mut self.beacon_url,
+            &mut self.jwt_secret,
+            &mut self.jwt_secret_file,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            expand(value);
+        }
+        for value in self.headers.values_mut().chain(self.beacon_headers.values_mut()) {
+            expand(value);
+        }
+        for auth in [&mut self.basic_auth, &mut self.beacon_basic_auth].into_iter().flatten() {
+            expand(&mut auth.username);
+            expand(&mut auth.password);
+        }
+        unresolved.sort();
+        unresolved.dedup();
+        self.unresolved_vars = unresolved;
+    }
+}
+
+/// Replace `${NAME}` with `lookup(NAME)`; fails with the names that don't resolve
+pub fn expand_vars(value: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String, Vec<String>> {
+    let mut out = String::with_capacity(value.len());
+    let mut missing = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find('}') {
+            Some(end) => {
+                let name = &after[..end];
+                match lookup(name) {
+                    Some(resolved) => out.push_str(&resolved),
+                    None => missing.push(name.to_string()),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    if missing.is_empty() {
+        Ok(out)
+    } else {
+        Err(missing)
+    }
+}
+
+/// `[[alerts]]` entry; `kind` is one of peers_below, latency_above,
+/// head_lag_above, no_block_for, base_fee_above, watched_activity, revert_rate_above
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertRuleConfig {
+    pub name: String,
+    pub kind: String,
+    pub threshold: Option<f64>,
+    /// Blocks considered by revert_rate_above
+    pub window: Option<usize>,
+    /// Addresses for watched_activity; empty means the watch list
+    #[serde(default)]
+    pub addresses: Vec<String>,
+    pub message: Option<String>,
+    /// Shell command run on fire/resolve (POKE_ALERT_* env vars describe the event)
+    pub command: Option<String>,
+    /// URL receiving a JSON POST on fire/resolve
+    pub webhook: Option<String>,
+    /// watched_activity only: wait until the block is finalized
+    #[serde(default)]
+    pub finalized: bool,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -27,6 +155,23 @@ pub struct Config {
 
     #[serde(default)]
     pub abi_paths: Vec<String>,
+
+    /// Artifact layouts to scan (foundry, hardhat, hardhat-deploy, truffle, vyper, abi).
+    /// Empty means all of them.
+    #[serde(default)]
+    pub abi_layouts: Vec<String>,
+
+    /// Signature dumps (text, 4byte JSON or CSV) imported into the local signature DB
+    #[serde(default)]
+    pub signature_files: Vec<String>,
+
+    /// Disable all remote lookups (OpenChain, Sourcify)
+    #[serde(default)]
+    pub offline: bool,
+
+    /// Alert rules evaluated continuously; built-in defaults when empty
+    #[serde(default)]
+    pub alerts: Vec<AlertRuleConfig>,
 }
 
 impl TokenSpec {
@@ -50,7 +195,9 @@ pub fn load() -> Config {
         Ok(content) => content,
         Err(_) => return Config::default(),
     };
-    toml::from_str::<Config>(&content).unwrap_or_default()
+    let mut config = toml::from_str::<Config>(&content).unwrap_or_default();
+    config.endpoints.iter_mut().for_each(EndpointConfig::expand_env);
+    config
 }
 
 pub fn config_path() -> Option<PathBuf> {
@@ -82,6 +229,14 @@ pub fn labels_db_path() -> Option<PathBuf> {
     data_dir().map(|dir| dir.join("labels.sqlite3"))
 }
 
+pub fn signatures_db_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("signatures.sqlite3"))
+}
+
+pub fn tokens_db_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("tokens.sqlite3"))
+}
+
 fn normalize_address(address: &str) -> String {
     let trimmed = address.trim();
     let payload = trimmed
@@ -107,3 +262,33 @@ fn short_addr(value: &str) -> String {
         .collect();
     format!("{}..{}", start, end)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_vars() {
+        let lookup = |name: &str| (name == "KEY").then(|| "abc123".to_string());
+        assert_eq!(
+            expand_vars("https://rpc.example/v3/${KEY}", lookup).as_deref(),
+            Ok("https://rpc.example/v3/abc123")
+        );
+        assert_eq!(expand_vars("${KEY}:${KEY}", lookup).as_deref(), Ok("abc123:abc123"));
+        assert_eq!(
+            expand_vars("${USER}:${KEY}:${PASS}", lookup),
+            Err(vec!["USER".to_string(), "PASS".to_string()])
+        );
+        assert_eq!(expand_vars("cost $5 ${KEY", lookup).as_deref(), Ok("cost $5 ${KEY"));
+    }
+
+    #[test]
+    fn test_unset_vars_recorded() {
+        let mut entry: EndpointConfig = toml::from_str(
+            "rpc = \"https://rpc.example/${POKE_TEST_UNSET}\"\n[headers]\nX-Api-Key = \"${POKE_TEST_UNSET}\"",
+        )
+        .unwrap();
+        entry.expand_env();
+        assert_eq!(entry.unresolved_vars, vec!["POKE_TEST_UNSET".to_string()]);
+    }
+}
@@ -39,6 +39,8 @@ pub enum Command {
     Alerts,
     Mempool,
     RpcStats,
+    /// Quorum reads across all endpoints: "on", "off", or toggle
+    Quorum(Option<String>),
 
     // Node management
     Connect(String),
@@ -108,6 +110,7 @@ pub fn parse_command(input: &str) -> Command {
         "alerts" | "alert" => Command::Alerts,
         "mempool" | "pool" => Command::Mempool,
         "rpc-stats" | "rpcstats" | "stats" => Command::RpcStats,
+        "quorum" => Command::Quorum(args),
 
         // Node management
         "connect" | "conn" => {
@@ -182,6 +185,11 @@ mod tests {
         assert_eq!(parse_command("metric"), Command::Metrics);
         assert_eq!(parse_command("alerts"), Command::Alerts);
         assert_eq!(parse_command("alert"), Command::Alerts);
+        assert_eq!(parse_command("quorum"), Command::Quorum(None));
+        assert_eq!(
+            parse_command("quorum off"),
+            Command::Quorum(Some("off".to_string()))
+        );
     }
 
     #[test]
@@ -27,6 +27,34 @@ pub struct DecodedCall {
     pub arguments: Vec<DecodedArg>,
 }
 
+/// A call found inside a `bytes` argument (multicall, Safe, router commands)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NestedCall {
+    /// Call target when the wrapper carries one (aggregate3, execTransaction, multiSend)
+    pub target: Option<String>,
+    /// Selector of the inner calldata, if it has one
+    pub selector: Option<String>,
+    /// Function or router command name ("unknown" if unresolved)
+    pub name: String,
+    /// Full signature when known
+    pub signature: Option<String>,
+    /// ETH value forwarded with the call, in wei
+    pub value: Option<String>,
+    /// Decoded arguments
+    pub args: Vec<NestedArg>,
+    /// Why the inner calldata could not be decoded
+    pub error: Option<String>,
+}
+
+/// An argument of a nested call, which may itself wrap further calls
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NestedArg {
+    pub name: String,
+    pub kind: String,
+    pub value: String,
+    pub calls: Vec<NestedCall>,
+}
+
 /// Trait for ABI decoding implementations
 ///
 /// This trait abstracts over the actual ABI decoding implementation,
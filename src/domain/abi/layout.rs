@@ -0,0 +1,67 @@
+//! Artifact layouts understood by the ABI scanner
+
+/// Build-tool artifact layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AbiLayout {
+    /// Foundry `out/<File>.sol/<Contract>.json`
+    Foundry,
+    /// Hardhat `artifacts/**/<Contract>.json` (without `.dbg.json`)
+    Hardhat,
+    /// hardhat-deploy `deployments/<network>/<Contract>.json`, with addresses
+    HardhatDeploy,
+    /// Truffle `build/contracts/<Contract>.json`
+    Truffle,
+    /// Vyper `-f abi` output: `<Contract>.abi.json` or `<Contract>.json` next to `<Contract>.vy`
+    Vyper,
+    /// Plain `.abi` files holding a JSON ABI array
+    RawAbi,
+}
+
+impl AbiLayout {
+    pub const ALL: [AbiLayout; 6] = [
+        AbiLayout::Foundry,
+        AbiLayout::Hardhat,
+        AbiLayout::HardhatDeploy,
+        AbiLayout::Truffle,
+        AbiLayout::Vyper,
+        AbiLayout::RawAbi,
+    ];
+
+    /// Parse a config name (e.g. "foundry", "hardhat-deploy", "abi")
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().replace('_', "-").as_str() {
+            "foundry" | "forge" => Some(AbiLayout::Foundry),
+            "hardhat" => Some(AbiLayout::Hardhat),
+            "hardhat-deploy" | "deployments" => Some(AbiLayout::HardhatDeploy),
+            "truffle" => Some(AbiLayout::Truffle),
+            "vyper" => Some(AbiLayout::Vyper),
+            "abi" | "raw" | "raw-abi" => Some(AbiLayout::RawAbi),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AbiLayout::Foundry => "foundry",
+            AbiLayout::Hardhat => "hardhat",
+            AbiLayout::HardhatDeploy => "hardhat-deploy",
+            AbiLayout::Truffle => "truffle",
+            AbiLayout::Vyper => "vyper",
+            AbiLayout::RawAbi => "abi",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_layout_names() {
+        for layout in AbiLayout::ALL {
+            assert_eq!(AbiLayout::parse(layout.name()), Some(layout));
+        }
+        assert_eq!(AbiLayout::parse("Hardhat_Deploy"), Some(AbiLayout::HardhatDeploy));
+        assert_eq!(AbiLayout::parse("brownie"), None);
+    }
+}
@@ -4,7 +4,9 @@
 //! independent of the underlying implementation (alloy-dyn-abi).
 
 mod decoder;
+mod layout;
 mod registry;
 
-pub use decoder::{AbiDecoder, DecodedArg, DecodedCall};
-pub use registry::{AbiRegistry, FunctionSignature, ParamSpec};
+pub use decoder::{AbiDecoder, DecodedArg, DecodedCall, NestedArg, NestedCall};
+pub use layout::AbiLayout;
+pub use registry::{AbiRegistry, ContractDeployment, FunctionSignature, ParamSpec};
@@ -26,6 +26,9 @@ pub struct FunctionSignature {
     pub signature: String,
     /// Input parameters
     pub inputs: Vec<ParamSpec>,
+    /// Output parameters (empty when only the text signature is known)
+    #[serde(default)]
+    pub outputs: Vec<ParamSpec>,
     /// Source file where this ABI was found
     pub source: PathBuf,
 }
@@ -37,11 +40,26 @@ impl FunctionSignature {
     }
 }
 
+/// A deployed contract address discovered next to its ABI (e.g. hardhat-deploy)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractDeployment {
+    /// Lowercase 0x-prefixed address
+    pub address: String,
+    /// Contract name (artifact file stem)
+    pub name: String,
+    /// Network directory the deployment was found in
+    pub network: Option<String>,
+    /// Source file of the deployment
+    pub source: PathBuf,
+}
+
 /// Registry of function signatures indexed by selector
 #[derive(Debug, Default, Clone)]
 pub struct AbiRegistry {
     /// Functions indexed by 4-byte selector
     functions: HashMap<[u8; 4], FunctionSignature>,
+    /// Deployed addresses found in artifacts
+    pub deployments: Vec<ContractDeployment>,
     /// Number of files scanned
     pub scanned_files: usize,
     /// Number of functions loaded
@@ -107,6 +125,7 @@ impl AbiRegistry {
     pub fn merge(&mut self, other: Self) {
         self.scanned_files = self.scanned_files.saturating_add(other.scanned_files);
         self.errors.extend(other.errors);
+        self.deployments.extend(other.deployments);
         for (selector, function) in other.functions {
             self.functions.entry(selector).or_insert(function);
         }
@@ -145,6 +164,10 @@ mod tests {
                     kind: "uint256".to_string(),
                 },
             ],
+            outputs: vec![ParamSpec {
+                name: String::new(),
+                kind: "bool".to_string(),
+            }],
             source: PathBuf::from("test.json"),
         };
 
@@ -165,6 +188,7 @@ mod tests {
             name: "transfer".to_string(),
             signature: "transfer(address,uint256)".to_string(),
             inputs: vec![],
+            outputs: vec![],
             source: PathBuf::from("first.json"),
         };
 
@@ -173,6 +197,7 @@ mod tests {
             name: "transferV2".to_string(),
             signature: "transferV2(address,uint256)".to_string(),
             inputs: vec![],
+            outputs: vec![],
             source: PathBuf::from("second.json"),
         };
 
@@ -0,0 +1,595 @@
+//! Alert rules - threshold conditions over node state with fired/resolved tracking
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use anyhow::{bail, Result};
+
+/// Blocks of revert tallies kept for revert-rate rules
+const MAX_REVERT_WINDOW: usize = 256;
+
+/// Blocks with watched activity held back for rules waiting on finality
+const MAX_UNFINALIZED: usize = 1024;
+
+/// A block's place in the chain, to tell blocks later reorged out from canonical ones
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockRef {
+    pub number: u64,
+    pub hash: String,
+    pub parent_hash: String,
+}
+
+/// Alert condition types
+#[derive(Clone, Debug, PartialEq)]
+pub enum AlertCondition {
+    PeerCountBelow(u32),
+    RpcLatencyAbove(u64),
+    /// Blocks behind the highest block the node knows of (eth_syncing)
+    HeadLagAbove(u64),
+    /// Seconds without a new head
+    NoBlockFor(u64),
+    /// Base fee in gwei
+    BaseFeeAbove(u64),
+    /// Any transaction from or to these addresses; empty means the watch list
+    WatchedActivity(Vec<String>),
+    /// Share of reverted transactions over the last `window` blocks
+    RevertRateAbove { percent: f64, window: usize },
+}
+
+impl AlertCondition {
+    /// Build a condition from its config `kind` name
+    pub fn from_kind(
+        kind: &str,
+        threshold: Option<f64>,
+        window: Option<usize>,
+        addresses: &[String],
+    ) -> Result<Self> {
+        let needs_threshold = || match threshold {
+            Some(value) if value >= 0.0 => Ok(value),
+            Some(_) => bail!("alert kind '{kind}' needs a non-negative threshold"),
+            None => bail!("alert kind '{kind}' needs a threshold"),
+        };
+        Ok(match kind {
+            "peers_below" => Self::PeerCountBelow(needs_threshold()? as u32),
+            "latency_above" => Self::RpcLatencyAbove(needs_threshold()? as u64),
+            "head_lag_above" => Self::HeadLagAbove(needs_threshold()? as u64),
+            "no_block_for" => Self::NoBlockFor(needs_threshold()? as u64),
+            "base_fee_above" => Self::BaseFeeAbove(needs_threshold()? as u64),
+            "watched_activity" => Self::WatchedActivity(
+                addresses.iter().map(|a| a.trim().to_lowercase()).collect(),
+            ),
+            "revert_rate_above" => Self::RevertRateAbove {
+                percent: needs_threshold()?,
+                window: window.unwrap_or(10).clamp(1, MAX_REVERT_WINDOW),
+            },
+            other => bail!("unknown alert kind '{other}'"),
+        })
+    }
+
+    /// Message used when a rule doesn't configure its own
+    pub fn default_message(&self) -> String {
+        match self {
+            Self::PeerCountBelow(n) => format!("Peer count below {n}"),
+            Self::RpcLatencyAbove(ms) => format!("RPC latency above {ms}ms"),
+            Self::HeadLagAbove(n) => format!("Head more than {n} blocks behind"),
+            Self::NoBlockFor(secs) => format!("No new block for {secs}s"),
+            Self::BaseFeeAbove(gwei) => format!("Base fee above {gwei} gwei"),
+            Self::WatchedActivity(_) => "Watched address active".to_string(),
+            Self::RevertRateAbove { percent, window } => {
+                format!("Revert rate above {percent}% over {window} blocks")
+            }
+        }
+    }
+}
+
+/// Alert rule definition
+#[derive(Clone, Debug)]
+pub struct AlertRule {
+    pub name: String,
+    pub condition: AlertCondition,
+    pub message: String,
+    /// Shell command run on every fired/resolved transition
+    pub command: Option<String>,
+    /// URL that receives a JSON POST on every transition
+    pub webhook: Option<String>,
+    /// Watched activity only fires once its block is finalized, so reorged
+    /// transactions never alert
+    pub finalized: bool,
+}
+
+impl AlertRule {
+    pub fn new(name: impl Into<String>, condition: AlertCondition) -> Self {
+        let message = condition.default_message();
+        Self {
+            name: name.into(),
+            condition,
+            message,
+            command: None,
+            webhook: None,
+            finalized: false,
+        }
+    }
+
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Rules used when config.toml defines none
+    pub fn defaults() -> Vec<AlertRule> {
+        vec![
+            AlertRule::new("peer_count_low", AlertCondition::PeerCountBelow(3))
+                .message("Peer count critically low"),
+            AlertRule::new("rpc_latency_high", AlertCondition::RpcLatencyAbove(500))
+                .message("RPC latency spike detected"),
+        ]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlertState {
+    Fired,
+    Resolved,
+}
+
+impl AlertState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertState::Fired => "fired",
+            AlertState::Resolved => "resolved",
+        }
+    }
+}
+
+/// A rule changing state
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlertEvent {
+    pub rule: String,
+    pub state: AlertState,
+    pub message: String,
+    /// What was observed, e.g. "peers=1"
+    pub detail: String,
+    /// Unix seconds
+    pub at: u64,
+}
+
+/// Transaction seen in a new block
+#[derive(Clone, Debug)]
+pub struct TxSample {
+    pub from: String,
+    pub to: String,
+    /// None when the receipt wasn't fetched
+    pub reverted: Option<bool>,
+}
+
+/// Evaluates rules against the latest node state and reports transitions
+#[derive(Debug, Default)]
+pub struct AlertEngine {
+    rules: Vec<AlertRule>,
+    firing: Vec<bool>,
+    watched: BTreeSet<String>,
+    peer_count: Option<u32>,
+    rpc_latency_ms: Option<u64>,
+    head_lag: Option<u64>,
+    last_block: Option<(u64, u64)>,
+    base_fee_gwei: Option<u64>,
+    /// Addresses touched by the latest block
+    activity: BTreeSet<String>,
+    /// (reverted, with known status) per block, newest last
+    outcomes: VecDeque<(usize, usize)>,
+    /// Addresses watched by finalized rules, per block not yet finalized that
+    /// touched any, oldest first
+    unfinalized: VecDeque<(BlockRef, BTreeSet<String>)>,
+    /// Held blocks dropped for lack of room since the last `take_dropped`
+    dropped: usize,
+    finalized_head: Option<u64>,
+    /// (block, address) touched by the blocks finalized in the latest step
+    finalized_activity: Vec<(u64, String)>,
+}
+
+impl AlertEngine {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        Self {
+            firing: vec![false; rules.len()],
+            rules,
+            ..Default::default()
+        }
+    }
+
+    pub fn rule(&self, name: &str) -> Option<&AlertRule> {
+        self.rules.iter().find(|rule| rule.name == name)
+    }
+
+    /// Addresses watched in the UI, used by activity rules without their own list
+    pub fn set_watched(&mut self, addresses: impl IntoIterator<Item = String>) {
+        self.watched = addresses.into_iter().map(|a| a.to_lowercase()).collect();
+    }
+
+    pub fn observe_status(
+        &mut self,
+        peer_count: Option<u32>,
+        rpc_latency_ms: Option<u64>,
+        head_lag: Option<u64>,
+    ) {
+        self.peer_count = peer_count;
+        self.rpc_latency_ms = rpc_latency_ms;
+        self.head_lag = head_lag;
+    }
+
+    pub fn observe_block(&mut self, block: BlockRef, base_fee_gwei: u64, txs: &[TxSample], now: u64) {
+        let number = block.number;
+        let activity: BTreeSet<String> = txs
+            .iter()
+            .flat_map(|tx| [tx.from.to_lowercase(), tx.to.to_lowercase()])
+            .collect();
+        // A block at or below a held one replaced it in a reorg
+        while self.unfinalized.back().is_some_and(|(held, _)| held.number >= number) {
+            self.unfinalized.pop_back();
+        }
+        // Only activity a finalized rule waits for is held, so fast chains with
+        // long finality don't run out of room
+        let held: BTreeSet<String> = activity
+            .iter()
+            .filter(|address| self.held_for_finality(address))
+            .cloned()
+            .collect();
+        if !held.is_empty() && self.finalized_head.is_none_or(|finalized| number > finalized) {
+            self.unfinalized.push_back((block, held));
+            if self.unfinalized.len() > MAX_UNFINALIZED {
+                self.unfinalized.pop_front();
+                self.dropped += 1;
+            }
+        }
+
+        if self.last_block.is_some_and(|(last, _)| number <= last) {
+            return;
+        }
+        self.last_block = Some((number, now));
+        self.base_fee_gwei = Some(base_fee_gwei);
+        self.activity = activity;
+
+        let known = txs.iter().filter(|tx| tx.reverted.is_some()).count();
+        let reverted = txs.iter().filter(|tx| tx.reverted == Some(true)).count();
+        self.outcomes.push_back((reverted, known));
+        if self.outcomes.len() > MAX_REVERT_WINDOW {
+            self.outcomes.pop_front();
+        }
+    }
+
+    pub fn finalized_head(&self) -> Option<u64> {
+        self.finalized_head
+    }
+
+    /// Number of held blocks dropped unfinalized since the last call
+    pub fn take_dropped(&mut self) -> usize {
+        std::mem::take(&mut self.dropped)
+    }
+
+    /// Whether a rule waiting on finality watches `address`
+    fn held_for_finality(&self, address: &str) -> bool {
+        self.rules.iter().any(|rule| match &rule.condition {
+            AlertCondition::WatchedActivity(addresses) if rule.finalized => {
+                watches(addresses, &self.watched, address)
+            }
+            _ => false,
+        })
+    }
+
+    /// Held blocks at or below `finalized`, newest first: the ones to look up on
+    /// the canonical chain before `observe_finalized`
+    pub fn held_until(&self, finalized: u64) -> Vec<BlockRef> {
+        self.unfinalized
+            .iter()
+            .rev()
+            .filter(|(block, _)| block.number <= finalized)
+            .map(|(block, _)| block.clone())
+            .collect()
+    }
+
+    /// The node's finalized head moved; release the activity of newly finalized
+    /// blocks whose hash is the canonical one at their height. Blocks with
+    /// another hash were reorged out and are dropped; blocks missing from
+    /// `canonical` stay held for the next step.
+    pub fn observe_finalized(&mut self, finalized: u64, canonical: &BTreeMap<u64, String>) {
+        if self.finalized_head.is_some_and(|head| finalized <= head) {
+            return;
+        }
+        self.finalized_head = Some(finalized);
+        self.finalized_activity.clear();
+        let mut kept = VecDeque::new();
+        for (block, activity) in std::mem::take(&mut self.unfinalized) {
+            if block.number > finalized {
+                kept.push_back((block, activity));
+                continue;
+            }
+            match canonical.get(&block.number) {
+                Some(hash) if hash.eq_ignore_ascii_case(&block.hash) => self
+                    .finalized_activity
+                    .extend(activity.into_iter().map(|address| (block.number, address))),
+                Some(_) => {}
+                None => kept.push_back((block, activity)),
+            }
+        }
+        self.unfinalized = kept;
+    }
+
+    /// Forget chain state, e.g. after switching endpoints
+    pub fn reset_chain(&mut self) {
+        self.last_block = None;
+        self.base_fee_gwei = None;
+        self.activity.clear();
+        self.outcomes.clear();
+        self.unfinalized.clear();
+        self.dropped = 0;
+        self.finalized_head = None;
+        self.finalized_activity.clear();
+    }
+
+    /// Re-check every rule and return the ones that changed state
+    pub fn evaluate(&mut self, now: u64) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
+        for (index, rule) in self.rules.iter().enumerate() {
+            let observed = self.check(rule, now);
+            let state = match (&observed, self.firing[index]) {
+                (Some(_), false) => AlertState::Fired,
+                (None, true) => AlertState::Resolved,
+                _ => continue,
+            };
+            self.firing[index] = state == AlertState::Fired;
+            events.push(AlertEvent {
+                rule: rule.name.clone(),
+                state,
+                message: rule.message.clone(),
+                detail: observed.unwrap_or_default(),
+                at: now,
+            });
+        }
+        events
+    }
+
+    /// Observed value when the condition holds
+    fn check(&self, rule: &AlertRule, now: u64) -> Option<String> {
+        match &rule.condition {
+            AlertCondition::PeerCountBelow(threshold) => self
+                .peer_count
+                .filter(|peers| peers < threshold)
+                .map(|peers| format!("peers={peers}")),
+            AlertCondition::RpcLatencyAbove(threshold) => self
+                .rpc_latency_ms
+                .filter(|ms| ms > threshold)
+                .map(|ms| format!("latency={ms}ms")),
+            AlertCondition::HeadLagAbove(threshold) => self
+                .head_lag
+                .filter(|lag| lag > threshold)
+                .map(|lag| format!("lag={lag} blocks")),
+            AlertCondition::NoBlockFor(secs) => {
+                let (number, seen_at) = self.last_block?;
+                let idle = now.saturating_sub(seen_at);
+                (idle >= *secs).then(|| format!("last block #{number} {idle}s ago"))
+            }
+            AlertCondition::BaseFeeAbove(gwei) => self
+                .base_fee_gwei
+                .filter(|fee| fee > gwei)
+                .map(|fee| format!("base fee={fee} gwei")),
+            AlertCondition::WatchedActivity(addresses) => {
+                let matches = |address: &String| watches(addresses, &self.watched, address);
+                if rule.finalized {
+                    let hits: Vec<String> = self
+                        .finalized_activity
+                        .iter()
+                        .filter(|(_, address)| matches(address))
+                        .map(|(number, address)| format!("{address} in block #{number}"))
+                        .collect();
+                    return (!hits.is_empty()).then(|| format!("{} (finalized)", hits.join(", ")));
+                }
+                let hits: Vec<&str> = self
+                    .activity
+                    .iter()
+                    .filter(|address| matches(address))
+                    .map(String::as_str)
+                    .collect();
+                let (number, _) = self.last_block?;
+                (!hits.is_empty()).then(|| format!("{} in block #{number}", hits.join(", ")))
+            }
+            AlertCondition::RevertRateAbove { percent, window } => {
+                let (reverted, known) = self
+                    .outcomes
+                    .iter()
+                    .rev()
+                    .take(*window)
+                    .fold((0, 0), |(r, k), (br, bk)| (r + br, k + bk));
+                if known == 0 {
+                    return None;
+                }
+                let rate = reverted as f64 * 100.0 / known as f64;
+                (rate > *percent).then(|| format!("revert rate={rate:.1}% ({reverted}/{known})"))
+            }
+        }
+    }
+}
+
+/// Whether an activity rule's `addresses` cover `address`; an empty list
+/// means the UI watch list
+fn watches(addresses: &[String], watched: &BTreeSet<String>, address: &str) -> bool {
+    if addresses.is_empty() {
+        watched.contains(address)
+    } else {
+        addresses.iter().any(|watched| watched == address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(number: u64) -> BlockRef {
+        BlockRef {
+            number,
+            hash: format!("0x{number:x}"),
+            parent_hash: format!("0x{:x}", number.saturating_sub(1)),
+        }
+    }
+
+    /// Canonical hashes of `blocks`
+    fn canonical(blocks: &[BlockRef]) -> BTreeMap<u64, String> {
+        blocks.iter().map(|block| (block.number, block.hash.clone())).collect()
+    }
+
+    fn tx(from: &str, to: &str, reverted: Option<bool>) -> TxSample {
+        TxSample {
+            from: from.to_string(),
+            to: to.to_string(),
+            reverted,
+        }
+    }
+
+    #[test]
+    fn test_fire_and_resolve() {
+        let mut engine = AlertEngine::new(AlertRule::defaults());
+        engine.observe_status(Some(1), Some(50), None);
+        let events = engine.evaluate(100);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].rule, "peer_count_low");
+        assert_eq!(events[0].state, AlertState::Fired);
+        assert_eq!(events[0].detail, "peers=1");
+
+        // Still firing: no repeat
+        assert!(engine.evaluate(102).is_empty());
+
+        engine.observe_status(Some(8), Some(50), None);
+        let events = engine.evaluate(104);
+        assert_eq!(events[0].state, AlertState::Resolved);
+    }
+
+    #[test]
+    fn test_no_block_for() {
+        let mut engine = AlertEngine::new(vec![AlertRule::new(
+            "stalled",
+            AlertCondition::NoBlockFor(30),
+        )]);
+        // Nothing observed yet: can't tell
+        assert!(engine.evaluate(1_000).is_empty());
+
+        engine.observe_block(block(10), 1, &[], 1_000);
+        assert!(engine.evaluate(1_020).is_empty());
+        assert_eq!(engine.evaluate(1_030)[0].state, AlertState::Fired);
+
+        engine.observe_block(block(11), 1, &[], 1_031);
+        assert_eq!(engine.evaluate(1_031)[0].state, AlertState::Resolved);
+    }
+
+    #[test]
+    fn test_watched_activity_uses_watch_list() {
+        let mut engine = AlertEngine::new(vec![AlertRule::new(
+            "watched",
+            AlertCondition::WatchedActivity(Vec::new()),
+        )]);
+        engine.set_watched(["0xAbC".to_string()]);
+
+        engine.observe_block(block(1), 1, &[tx("0x111", "0xabc", Some(false))], 10);
+        let events = engine.evaluate(10);
+        assert_eq!(events[0].state, AlertState::Fired);
+        assert_eq!(events[0].detail, "0xabc in block #1");
+
+        engine.observe_block(block(2), 1, &[tx("0x111", "0x222", None)], 22);
+        assert_eq!(engine.evaluate(22)[0].state, AlertState::Resolved);
+    }
+
+    #[test]
+    fn test_watched_activity_waits_for_finality() {
+        let mut rule = AlertRule::new(
+            "treasury",
+            AlertCondition::WatchedActivity(vec!["0xabc".to_string()]),
+        );
+        rule.finalized = true;
+        let mut engine = AlertEngine::new(vec![rule]);
+
+        let orphan = BlockRef {
+            hash: "0xdead".to_string(),
+            ..block(11)
+        };
+        engine.observe_block(block(10), 1, &[tx("0x111", "0x222", None)], 10);
+        engine.observe_block(orphan, 1, &[tx("0x111", "0xabc", None)], 22);
+        assert!(engine.evaluate(22).is_empty());
+
+        // Block 11 is reorged out before finality; its transfer never alerts
+        engine.observe_block(block(11), 1, &[tx("0x111", "0x222", None)], 24);
+        engine.observe_finalized(11, &canonical(&[block(10), block(11)]));
+        assert!(engine.evaluate(30).is_empty());
+
+        engine.observe_block(block(12), 1, &[tx("0xABC", "0x222", None)], 36);
+        assert!(engine.evaluate(36).is_empty());
+        engine.observe_finalized(12, &canonical(&[block(12)]));
+        let events = engine.evaluate(40);
+        assert_eq!(events[0].state, AlertState::Fired);
+        assert_eq!(events[0].detail, "0xabc in block #12 (finalized)");
+
+        // Fires until the next finalized step without hits
+        engine.observe_finalized(12, &canonical(&[block(12)]));
+        assert!(engine.evaluate(41).is_empty());
+        engine.observe_block(block(13), 1, &[], 48);
+        engine.observe_finalized(13, &canonical(&[block(13)]));
+        assert_eq!(engine.evaluate(50)[0].state, AlertState::Resolved);
+
+        // Polling moves past a replaced block without seeing its replacement:
+        // the held block loses on the hash check instead
+        let orphan = BlockRef {
+            hash: "0xdead".to_string(),
+            ..block(14)
+        };
+        engine.observe_block(orphan.clone(), 1, &[tx("0x111", "0xabc", None)], 60);
+        engine.observe_block(block(15), 1, &[], 72);
+        // Blocks without watched activity aren't held
+        assert_eq!(engine.held_until(15), vec![orphan]);
+        // Unknown canonical hashes keep blocks held
+        engine.observe_finalized(14, &BTreeMap::new());
+        assert_eq!(engine.held_until(15).len(), 1);
+        engine.observe_finalized(15, &canonical(&[block(14), block(15)]));
+        assert!(engine.held_until(15).is_empty());
+        assert!(engine.evaluate(80).is_empty());
+    }
+
+    #[test]
+    fn test_held_activity_dropped_is_counted() {
+        let mut rule = AlertRule::new("treasury", AlertCondition::WatchedActivity(Vec::new()));
+        rule.finalized = true;
+        let mut engine = AlertEngine::new(vec![rule]);
+        engine.set_watched(["0xabc".to_string()]);
+
+        for number in 1..=MAX_UNFINALIZED as u64 * 2 {
+            let txs = if number % 2 == 0 { vec![tx("0x111", "0xABC", None)] } else { Vec::new() };
+            engine.observe_block(block(number), 1, &txs, number);
+        }
+        assert_eq!(engine.held_until(u64::MAX).len(), MAX_UNFINALIZED);
+        assert_eq!(engine.take_dropped(), 0);
+
+        engine.observe_block(block(MAX_UNFINALIZED as u64 * 2 + 1), 1, &[tx("0xabc", "0x1", None)], 0);
+        assert_eq!(engine.take_dropped(), 1);
+        assert_eq!(engine.take_dropped(), 0);
+    }
+
+    #[test]
+    fn test_revert_rate_window() {
+        let condition = AlertCondition::from_kind("revert_rate_above", Some(40.0), Some(2), &[])
+            .unwrap();
+        let mut engine = AlertEngine::new(vec![AlertRule::new("reverts", condition)]);
+
+        engine.observe_block(block(1), 1, &[tx("a", "b", Some(true)), tx("a", "b", Some(true))], 1);
+        engine.observe_block(block(2), 1, &[tx("a", "b", Some(false)), tx("a", "b", None)], 2);
+        let events = engine.evaluate(2);
+        assert_eq!(events[0].detail, "revert rate=66.7% (2/3)");
+
+        // Block 1 falls out of the two-block window
+        engine.observe_block(block(3), 1, &[tx("a", "b", Some(false))], 3);
+        assert_eq!(engine.evaluate(3)[0].state, AlertState::Resolved);
+    }
+
+    #[test]
+    fn test_from_kind() {
+        assert_eq!(
+            AlertCondition::from_kind("base_fee_above", Some(50.0), None, &[]).unwrap(),
+            AlertCondition::BaseFeeAbove(50)
+        );
+        assert!(AlertCondition::from_kind("peers_below", None, None, &[]).is_err());
+        assert!(AlertCondition::from_kind("sync_stalled", Some(1.0), None, &[]).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! Consensus-layer status - beacon head, finality and how far the execution
+//! client trails it
+
+pub const SLOTS_PER_EPOCH: u64 = 32;
+
+/// Epochs since finality before the chain counts as not finalizing
+pub const FINALITY_LIMIT: u64 = 3;
+
+/// Snapshot of a beacon node from the standard Beacon REST API
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BeaconStatus {
+    pub head_slot: u64,
+    /// Slots the node is behind the wall clock
+    pub sync_distance: u64,
+    pub is_syncing: bool,
+    /// Head imported without a verdict from the execution client
+    pub is_optimistic: bool,
+    /// The beacon node can't reach its execution client
+    pub el_offline: bool,
+    pub finalized_epoch: Option<u64>,
+    pub justified_epoch: Option<u64>,
+    pub peers: Option<u64>,
+    /// Execution block number in the beacon head block
+    pub execution_head: Option<u64>,
+}
+
+impl BeaconStatus {
+    pub fn head_epoch(&self) -> u64 {
+        self.head_slot / SLOTS_PER_EPOCH
+    }
+
+    /// Epochs between the head and the last finalized checkpoint
+    pub fn finality_distance(&self) -> Option<u64> {
+        self.finalized_epoch
+            .map(|finalized| self.head_epoch().saturating_sub(finalized))
+    }
+
+    /// Blocks the execution client's head trails the beacon head; negative when ahead
+    pub fn el_lag(&self, el_head: Option<u64>) -> Option<i64> {
+        Some(self.execution_head? as i64 - el_head? as i64)
+    }
+
+    /// Problems worth flagging, most severe first
+    pub fn warnings(&self, el_head: Option<u64>) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.el_offline {
+            warnings.push("execution client offline".to_string());
+        }
+        if self.is_syncing {
+            warnings.push(format!("syncing, {} slots behind", self.sync_distance));
+        }
+        if self.is_optimistic {
+            warnings.push("optimistic head".to_string());
+        }
+        if let Some(distance) = self.finality_distance().filter(|d| *d > FINALITY_LIMIT) {
+            warnings.push(format!("not finalizing for {distance} epochs"));
+        }
+        if let Some(lag) = self.el_lag(el_head).filter(|lag| *lag > 2) {
+            warnings.push(format!("execution head {lag} blocks behind"));
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lag_and_warnings() {
+        let status = BeaconStatus {
+            head_slot: 9_600_010,
+            finalized_epoch: Some(299_996),
+            justified_epoch: Some(299_999),
+            execution_head: Some(20_000_105),
+            ..BeaconStatus::default()
+        };
+        assert_eq!(status.head_epoch(), 300_000);
+        assert_eq!(status.finality_distance(), Some(4));
+        assert_eq!(status.el_lag(Some(20_000_100)), Some(5));
+        assert_eq!(status.el_lag(None), None);
+        assert_eq!(
+            status.warnings(Some(20_000_100)),
+            vec!["not finalizing for 4 epochs", "execution head 5 blocks behind"]
+        );
+        assert!(status.warnings(Some(20_000_105)).len() == 1);
+    }
+}
@@ -0,0 +1,120 @@
+//! EIP-4844 blob gas - blob counts and the blob base fee from block headers
+
+/// Blob gas charged per blob
+pub const GAS_PER_BLOB: u64 = 1 << 17;
+
+const MIN_BLOB_BASE_FEE: u128 = 1;
+
+/// Blob limits and fee responsiveness of one fork
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobParams {
+    pub target: u64,
+    pub max: u64,
+    pub update_fraction: u64,
+}
+
+pub const CANCUN: BlobParams = BlobParams {
+    target: 3,
+    max: 6,
+    update_fraction: 3_338_477,
+};
+
+/// Prague, unchanged by Osaka itself
+pub const PRAGUE: BlobParams = BlobParams {
+    target: 6,
+    max: 9,
+    update_fraction: 5_007_716,
+};
+
+pub const BPO1: BlobParams = BlobParams {
+    target: 10,
+    max: 15,
+    update_fraction: 8_346_193,
+};
+
+pub const BPO2: BlobParams = BlobParams {
+    target: 14,
+    max: 21,
+    update_fraction: 11_684_671,
+};
+
+/// Activation times per chain id, newest first
+const SCHEDULES: [(u64, &[(u64, BlobParams)]); 4] = [
+    // Mainnet
+    (
+        1,
+        &[(1_767_747_671, BPO2), (1_765_290_071, BPO1), (1_746_612_311, PRAGUE), (0, CANCUN)],
+    ),
+    // Sepolia
+    (
+        11_155_111,
+        &[(1_761_607_008, BPO2), (1_761_017_184, BPO1), (1_741_159_776, PRAGUE), (0, CANCUN)],
+    ),
+    // Holesky
+    (
+        17_000,
+        &[(1_760_389_824, BPO2), (1_759_800_000, BPO1), (1_740_434_112, PRAGUE), (0, CANCUN)],
+    ),
+    // Hoodi
+    (
+        560_048,
+        &[(1_762_955_544, BPO2), (1_762_365_720, BPO1), (1_742_999_832, PRAGUE), (0, CANCUN)],
+    ),
+];
+
+/// Blob parameters in force at a block timestamp on `chain_id`. None for
+/// chains without a known schedule, such as L2s and other L1s with their own
+/// blob limits, where a fee computed from Ethereum's would be wrong.
+pub fn blob_params(chain_id: u64, timestamp: u64) -> Option<BlobParams> {
+    let (_, schedule) = SCHEDULES.iter().find(|(id, _)| *id == chain_id)?;
+    schedule
+        .iter()
+        .find(|(activation, _)| timestamp >= *activation)
+        .map(|(_, params)| *params)
+}
+
+pub fn blob_count(blob_gas_used: u64) -> u64 {
+    blob_gas_used / GAS_PER_BLOB
+}
+
+/// Blob base fee in wei for a header's excess blob gas
+pub fn blob_base_fee(excess_blob_gas: u64, params: BlobParams) -> u128 {
+    fake_exponential(MIN_BLOB_BASE_FEE, excess_blob_gas as u128, params.update_fraction as u128)
+}
+
+/// factor * e ** (numerator / denominator), by Taylor expansion as in EIP-4844
+fn fake_exponential(factor: u128, numerator: u128, denominator: u128) -> u128 {
+    let mut output: u128 = 0;
+    let mut accum = factor.saturating_mul(denominator);
+    let mut i: u128 = 1;
+    while accum > 0 {
+        output = output.saturating_add(accum);
+        accum = accum.saturating_mul(numerator) / denominator.saturating_mul(i);
+        i += 1;
+    }
+    output / denominator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blob_base_fee() {
+        assert_eq!(blob_base_fee(0, CANCUN), 1);
+        // Vectors from the EIP-4844 reference fake_exponential
+        assert_eq!(blob_base_fee(10 * GAS_PER_BLOB, CANCUN), 1);
+        assert_eq!(blob_base_fee(50_000_000, CANCUN), 3_194_333);
+        assert_eq!(blob_base_fee(50_000_000, PRAGUE), 21_689);
+        assert_eq!(blob_base_fee(80_000_000, PRAGUE), 8_669_718);
+
+        assert_eq!(blob_params(1, 1_710_338_135), Some(CANCUN));
+        assert_eq!(blob_params(1, 1_750_000_000), Some(PRAGUE));
+        assert_eq!(blob_params(1, 1_790_000_000), Some(BPO2));
+        // Sepolia ran BPO2 while mainnet was still on Prague
+        assert_eq!(blob_params(1, 1_762_000_000), Some(PRAGUE));
+        assert_eq!(blob_params(11_155_111, 1_762_000_000), Some(BPO2));
+        assert_eq!(blob_params(100, 1_762_000_000), None);
+        assert_eq!(blob_count(786_432), 6);
+    }
+}
@@ -0,0 +1,368 @@
+//! Request budgets for hosted endpoints - rate limits, compute units, 429 backoff
+//! and how far the worker should back off polling under pressure
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// Compute units charged when a method has no known cost
+const DEFAULT_COST: u32 = 20;
+
+/// Delay after a 429 without a Retry-After header; doubles per throttle in a row
+const THROTTLE_BASE: Duration = Duration::from_secs(1);
+
+const THROTTLE_MAX: Duration = Duration::from_secs(60);
+
+/// How long a throttle or a rate-limit wait keeps the endpoint under pressure
+const PRESSURE_HOLD: Duration = Duration::from_secs(30);
+
+/// Share of the daily budget after which polling slows down
+const DAILY_SOFT_LIMIT: f64 = 0.8;
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Compute units per call, roughly what hosted providers charge
+pub fn default_cost(method: &str) -> u32 {
+    match method {
+        "eth_chainId" | "web3_clientVersion" | "net_peerCount" | "eth_syncing" => 0,
+        "eth_blockNumber" | "eth_accounts" | "eth_subscribe" => 10,
+        "eth_getTransactionReceipt" => 15,
+        "eth_getBlockByNumber" => 16,
+        "eth_getStorageAt" => 17,
+        "eth_getBalance" => 19,
+        "eth_call" | "eth_getCode" => 26,
+        "debug_traceTransaction" => 309,
+        _ => DEFAULT_COST,
+    }
+}
+
+/// Limits for one endpoint; all optional, an empty config never throttles
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BudgetConfig {
+    /// Requests per second
+    pub max_rps: Option<f64>,
+    /// Compute units per second
+    pub cu_per_second: Option<f64>,
+    /// Compute units per day; a soft limit that slows polling rather than failing calls
+    pub daily_cu: Option<u64>,
+    /// Per-method cost overrides
+    pub costs: BTreeMap<String, u32>,
+}
+
+impl BudgetConfig {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_rps.is_none() && self.cu_per_second.is_none() && self.daily_cu.is_none()
+    }
+
+    pub fn cost(&self, method: &str) -> u32 {
+        self.costs.get(method).copied().unwrap_or_else(|| default_cost(method))
+    }
+}
+
+/// How hard the worker should back off
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Pressure {
+    #[default]
+    Normal,
+    /// Recently throttled or waiting on the rate limit: skip receipts, poll slower
+    Constrained,
+    /// Daily budget spent or blocked by Retry-After: heads only, rarely
+    Exhausted,
+}
+
+impl Pressure {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Pressure::Normal => "normal",
+            Pressure::Constrained => "constrained",
+            Pressure::Exhausted => "exhausted",
+        }
+    }
+
+    /// How often HTTP endpoints are polled for new heads
+    pub fn poll_interval(&self) -> Duration {
+        match self {
+            Pressure::Normal => Duration::from_millis(500),
+            Pressure::Constrained => Duration::from_secs(4),
+            Pressure::Exhausted => Duration::from_secs(30),
+        }
+    }
+
+    /// How often peers, sync state and latency are refreshed
+    pub fn status_interval(&self) -> Duration {
+        match self {
+            Pressure::Normal => Duration::from_secs(2),
+            Pressure::Constrained => Duration::from_secs(10),
+            Pressure::Exhausted => Duration::from_secs(60),
+        }
+    }
+
+    /// Whether new blocks are enriched with transaction receipts
+    pub fn fetch_receipts(&self) -> bool {
+        *self == Pressure::Normal
+    }
+}
+
+/// Snapshot for the UI
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetStatus {
+    pub pressure: Pressure,
+    /// Compute units spent in the current day window
+    pub used_cu: u64,
+    pub daily_cu: Option<u64>,
+    /// 429s and rate-limit errors seen so far
+    pub throttles: u64,
+    /// Time left before requests may go out again after a 429
+    pub retry_in: Option<Duration>,
+}
+
+/// Token buckets for requests and compute units, plus a rolling daily total
+#[derive(Debug, Clone)]
+pub struct RequestBudget {
+    config: BudgetConfig,
+    /// Available requests and compute units; negative while calls are queued
+    requests: f64,
+    units: f64,
+    refilled_at: Instant,
+    day_started: Instant,
+    used_cu: u64,
+    /// No requests before this, from Retry-After or backoff
+    blocked_until: Option<Instant>,
+    /// Throttles in a row, reset by a successful call
+    streak: u32,
+    throttles: u64,
+    /// Last time a 429 came back or a call had to wait for tokens
+    pressed_at: Option<Instant>,
+}
+
+impl RequestBudget {
+    pub fn new(config: BudgetConfig, now: Instant) -> Self {
+        Self {
+            requests: config.max_rps.unwrap_or_default(),
+            units: config.cu_per_second.unwrap_or_default(),
+            config,
+            refilled_at: now,
+            day_started: now,
+            used_cu: 0,
+            blocked_until: None,
+            streak: 0,
+            throttles: 0,
+            pressed_at: None,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.refilled_at = now;
+        if let Some(rps) = self.config.max_rps {
+            self.requests = (self.requests + elapsed * rps).min(rps);
+        }
+        if let Some(cups) = self.config.cu_per_second {
+            self.units = (self.units + elapsed * cups).min(cups);
+        }
+        if now.saturating_duration_since(self.day_started) >= DAY {
+            self.day_started = now;
+            self.used_cu = 0;
+        }
+    }
+
+    /// Charge one call and return how long to wait before sending it. A wait
+    /// longer than `max_wait` charges nothing and comes back as the error, so
+    /// the caller can fail fast instead of holding up whoever awaits it.
+    pub fn reserve(&mut self, method: &str, now: Instant, max_wait: Duration) -> Result<Duration, Duration> {
+        self.refill(now);
+        let cost = self.config.cost(method);
+
+        let mut wait = self
+            .blocked_until
+            .map(|until| until.saturating_duration_since(now))
+            .unwrap_or_default();
+        let mut requests = self.requests;
+        if let Some(rps) = self.config.max_rps.filter(|rps| *rps > 0.0) {
+            requests -= 1.0;
+            if requests < 0.0 {
+                wait = wait.max(Duration::from_secs_f64(-requests / rps));
+            }
+        }
+        let mut units = self.units;
+        if let Some(cups) = self.config.cu_per_second.filter(|cups| *cups > 0.0) {
+            units -= cost as f64;
+            if units < 0.0 {
+                wait = wait.max(Duration::from_secs_f64(-units / cups));
+            }
+        }
+        if !wait.is_zero() {
+            self.pressed_at = Some(now);
+        }
+        if wait > max_wait {
+            return Err(wait);
+        }
+        self.requests = requests;
+        self.units = units;
+        self.used_cu += cost as u64;
+        Ok(wait)
+    }
+
+    pub fn record_success(&mut self) {
+        self.streak = 0;
+    }
+
+    /// The endpoint answered 429 or a rate-limit error; block until `retry_after`,
+    /// or back off exponentially without one. Returns the delay.
+    pub fn throttle(&mut self, retry_after: Option<Duration>, now: Instant) -> Duration {
+        self.streak += 1;
+        self.throttles += 1;
+        self.pressed_at = Some(now);
+        let delay = retry_after.unwrap_or_else(|| {
+            let exp = (self.streak - 1).min(16);
+            THROTTLE_BASE.saturating_mul(1 << exp).min(THROTTLE_MAX)
+        });
+        let until = now + delay;
+        self.blocked_until = Some(self.blocked_until.map_or(until, |at| at.max(until)));
+        delay
+    }
+
+    pub fn pressure(&self, now: Instant) -> Pressure {
+        let daily_share = self
+            .config
+            .daily_cu
+            .map(|limit| self.used_cu as f64 / limit.max(1) as f64)
+            .unwrap_or_default();
+        let blocked = self
+            .blocked_until
+            .is_some_and(|until| until.saturating_duration_since(now) > Pressure::Constrained.status_interval());
+        if daily_share >= 1.0 || blocked {
+            Pressure::Exhausted
+        } else if daily_share >= DAILY_SOFT_LIMIT
+            || self
+                .pressed_at
+                .is_some_and(|at| now.saturating_duration_since(at) < PRESSURE_HOLD)
+        {
+            Pressure::Constrained
+        } else {
+            Pressure::Normal
+        }
+    }
+
+    pub fn status(&self, now: Instant) -> BudgetStatus {
+        BudgetStatus {
+            pressure: self.pressure(now),
+            used_cu: self.used_cu,
+            daily_cu: self.config.daily_cu,
+            throttles: self.throttles,
+            retry_in: self
+                .blocked_until
+                .map(|until| until.saturating_duration_since(now))
+                .filter(|left| !left.is_zero()),
+        }
+    }
+}
+
+/// Parse a Retry-After header given in seconds; HTTP dates fall back to backoff
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(|secs| Duration::from_secs_f64(secs.min(THROTTLE_MAX.as_secs_f64() * 10.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Long enough that no reservation in these tests is refused
+    const WAIT: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_rate_limits_queue_calls() {
+        let now = Instant::now();
+        let config = BudgetConfig {
+            max_rps: Some(2.0),
+            cu_per_second: Some(30.0),
+            ..BudgetConfig::default()
+        };
+        let mut budget = RequestBudget::new(config, now);
+
+        assert_eq!(budget.reserve("eth_blockNumber", now, WAIT), Ok(Duration::ZERO));
+        // 10 + 26 CU is over the 30 CU/s bucket
+        assert_eq!(budget.reserve("eth_call", now, WAIT), Ok(Duration::from_millis(200)));
+        // Third request in the same second waits on the request bucket
+        assert_eq!(budget.reserve("eth_chainId", now, WAIT), Ok(Duration::from_millis(500)));
+        assert_eq!(budget.pressure(now), Pressure::Constrained);
+        assert_eq!(budget.pressure(now + PRESSURE_HOLD), Pressure::Normal);
+
+        let later = now + Duration::from_secs(5);
+        assert_eq!(budget.reserve("eth_blockNumber", later, WAIT), Ok(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_long_waits_fail_without_charging() {
+        let now = Instant::now();
+        let config = BudgetConfig {
+            max_rps: Some(1.0),
+            ..BudgetConfig::default()
+        };
+        let mut budget = RequestBudget::new(config, now);
+        assert_eq!(budget.reserve("eth_call", now, Duration::ZERO), Ok(Duration::ZERO));
+        assert_eq!(budget.reserve("eth_call", now, Duration::ZERO), Err(Duration::from_secs(1)));
+        assert_eq!(budget.reserve("eth_call", now, Duration::ZERO), Err(Duration::from_secs(1)));
+        assert_eq!(budget.status(now).used_cu, 26);
+        assert_eq!(budget.pressure(now), Pressure::Constrained);
+        // The refused calls took no tokens, so the next second has one again
+        let later = now + Duration::from_secs(1);
+        assert_eq!(budget.reserve("eth_call", later, Duration::ZERO), Ok(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_throttle_honours_retry_after_and_backs_off() {
+        let now = Instant::now();
+        let mut budget = RequestBudget::new(BudgetConfig::default(), now);
+        assert_eq!(budget.reserve("eth_call", now, WAIT), Ok(Duration::ZERO));
+
+        budget.throttle(Some(Duration::from_secs(3)), now);
+        assert_eq!(budget.reserve("eth_call", now, WAIT), Ok(Duration::from_secs(3)));
+        assert_eq!(budget.reserve("eth_call", now, Duration::from_secs(1)), Err(Duration::from_secs(3)));
+        assert_eq!(budget.pressure(now), Pressure::Constrained);
+
+        assert_eq!(budget.throttle(None, now), Duration::from_secs(2));
+        assert_eq!(budget.throttle(None, now), Duration::from_secs(4));
+        budget.record_success();
+        assert_eq!(budget.throttle(None, now), Duration::from_secs(1));
+
+        budget.throttle(Some(Duration::from_secs(120)), now);
+        let status = budget.status(now);
+        assert_eq!(status.pressure, Pressure::Exhausted);
+        assert_eq!(status.throttles, 5);
+        assert_eq!(status.retry_in, Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn test_daily_budget_pressure() {
+        let now = Instant::now();
+        let config = BudgetConfig {
+            daily_cu: Some(100),
+            costs: BTreeMap::from([("eth_blockNumber".to_string(), 45)]),
+            ..BudgetConfig::default()
+        };
+        let mut budget = RequestBudget::new(config, now);
+        let _ = budget.reserve("eth_blockNumber", now, WAIT);
+        assert_eq!(budget.pressure(now), Pressure::Normal);
+        let _ = budget.reserve("eth_blockNumber", now, WAIT);
+        assert_eq!(budget.pressure(now), Pressure::Constrained);
+        let _ = budget.reserve("eth_blockNumber", now, WAIT);
+        assert_eq!(budget.pressure(now), Pressure::Exhausted);
+        assert!(!budget.pressure(now).fetch_receipts());
+
+        // A new day window resets the count
+        let _ = budget.reserve("eth_chainId", now + DAY, WAIT);
+        assert_eq!(budget.status(now + DAY).used_cu, 0);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("5"), Some(Duration::from_secs(5)));
+        assert_eq!(parse_retry_after(" 0.5 "), Some(Duration::from_millis(500)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+    }
+}
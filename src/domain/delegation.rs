@@ -0,0 +1,63 @@
+//! EIP-7702 delegation - EOAs that run another contract's code
+
+/// Code of a delegated EOA is this prefix followed by the 20-byte delegate address
+pub const DELEGATION_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+/// One entry of a type 4 transaction's authorization list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Authorization {
+    /// 0 makes the authorization valid on any chain
+    pub chain_id: u64,
+    /// Contract whose code the authority delegates to; the zero address clears the delegation
+    pub delegate: String,
+    pub nonce: u64,
+    /// Signer recovered from the authorization; None when the signature is invalid
+    pub authority: Option<String>,
+}
+
+impl Authorization {
+    pub fn clears(&self) -> bool {
+        is_zero_address(&self.delegate)
+    }
+}
+
+/// Delegate address encoded in account code, None unless the code is a delegation designator
+pub fn parse_delegation(code: &[u8]) -> Option<String> {
+    let address = code.strip_prefix(&DELEGATION_PREFIX)?;
+    if address.len() != 20 {
+        return None;
+    }
+    Some(format!("0x{}", hex::encode(address)))
+}
+
+pub fn is_zero_address(address: &str) -> bool {
+    address.eq_ignore_ascii_case(ZERO_ADDRESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_delegation() {
+        let mut code = DELEGATION_PREFIX.to_vec();
+        code.extend([0x63; 20]);
+        assert_eq!(
+            parse_delegation(&code).as_deref(),
+            Some("0x6363636363636363636363636363636363636363")
+        );
+        assert_eq!(parse_delegation(&code[..22]), None);
+        assert_eq!(parse_delegation(&[0x60, 0x80, 0x60, 0x40]), None);
+        assert_eq!(parse_delegation(&[]), None);
+
+        let auth = Authorization {
+            chain_id: 0,
+            delegate: ZERO_ADDRESS.to_string(),
+            nonce: 3,
+            authority: None,
+        };
+        assert!(auth.clears());
+    }
+}
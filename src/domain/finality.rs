@@ -0,0 +1,66 @@
+//! Block finality - where the safe and finalized heads sit behind the chain head
+
+/// How settled a block is, from the node's "safe" and "finalized" tags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Finality {
+    Unsafe,
+    Safe,
+    Finalized,
+}
+
+impl Finality {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Finality::Unsafe => "unsafe",
+            Finality::Safe => "safe",
+            Finality::Finalized => "finalized",
+        }
+    }
+}
+
+/// Latest safe and finalized block numbers; None when the node doesn't report the tag
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FinalityHeads {
+    pub safe: Option<u64>,
+    pub finalized: Option<u64>,
+}
+
+impl FinalityHeads {
+    /// None on chains without finality tags, e.g. pre-merge or some dev nodes
+    pub fn classify(&self, number: u64) -> Option<Finality> {
+        if self.safe.is_none() && self.finalized.is_none() {
+            return None;
+        }
+        Some(if self.finalized.is_some_and(|finalized| number <= finalized) {
+            Finality::Finalized
+        } else if self.safe.is_some_and(|safe| number <= safe) {
+            Finality::Safe
+        } else {
+            Finality::Unsafe
+        })
+    }
+
+    /// Blocks between the head and the finalized block
+    pub fn finalized_lag(&self, head: u64) -> Option<u64> {
+        self.finalized.map(|finalized| head.saturating_sub(finalized))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let heads = FinalityHeads {
+            safe: Some(100),
+            finalized: Some(68),
+        };
+        assert_eq!(heads.classify(50), Some(Finality::Finalized));
+        assert_eq!(heads.classify(68), Some(Finality::Finalized));
+        assert_eq!(heads.classify(69), Some(Finality::Safe));
+        assert_eq!(heads.classify(101), Some(Finality::Unsafe));
+        assert_eq!(heads.finalized_lag(130), Some(62));
+        assert_eq!(FinalityHeads::default().classify(1), None);
+    }
+}
@@ -0,0 +1,337 @@
+//! Endpoint health scoring - error rate, latency and head lag per endpoint, with
+//! retry backoff and failover/failback decisions
+
+use std::time::{Duration, Instant};
+
+/// How often endpoints other than the current one are probed
+pub const PROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// First retry delay after a failure; doubles per consecutive failure
+const BACKOFF_BASE: Duration = Duration::from_secs(1);
+
+const BACKOFF_MAX: Duration = Duration::from_secs(60);
+
+/// Weight of the newest sample in the error-rate and latency averages
+const EWMA_ALPHA: f64 = 0.3;
+
+/// Blocks behind the best known head before an endpoint counts as degraded
+pub const LAG_LIMIT: u64 = 5;
+
+/// Error rate above which the current endpoint is abandoned for a healthy one
+const ERROR_LIMIT: f64 = 0.5;
+
+/// Successful probes in a row before a failed preferred endpoint is trusted again
+const RECOVERY_STREAK: u32 = 3;
+
+/// Rolling health of one endpoint
+#[derive(Debug, Clone)]
+pub struct EndpointHealth {
+    /// Average share of failed requests, 0..1
+    pub error_rate: f64,
+    /// Average latency of successful requests
+    pub latency_ms: Option<f64>,
+    pub head: Option<u64>,
+    /// Chain the endpoint reported; heads are only compared within a chain
+    pub chain_id: Option<u64>,
+    /// Failures in a row; non-zero means the endpoint is down
+    pub failures: u32,
+    /// Successes in a row
+    pub successes: u32,
+    /// No connection attempts before this
+    retry_at: Option<Instant>,
+    /// Next background probe
+    next_probe: Instant,
+}
+
+impl EndpointHealth {
+    fn new(now: Instant) -> Self {
+        Self {
+            error_rate: 0.0,
+            latency_ms: None,
+            head: None,
+            chain_id: None,
+            failures: 0,
+            successes: 0,
+            retry_at: None,
+            next_probe: now,
+        }
+    }
+}
+
+/// Why the worker should move to another endpoint
+#[derive(Debug, Clone, PartialEq)]
+pub struct Switch {
+    pub to: usize,
+    pub reason: String,
+}
+
+/// Health of every configured endpoint, indexed like the endpoint list
+#[derive(Debug, Clone)]
+pub struct HealthTracker {
+    endpoints: Vec<EndpointHealth>,
+    /// The endpoint the user picked; failed-over connections return to it
+    preferred: usize,
+}
+
+impl HealthTracker {
+    pub fn new(count: usize, now: Instant) -> Self {
+        Self {
+            endpoints: (0..count).map(|_| EndpointHealth::new(now)).collect(),
+            preferred: 0,
+        }
+    }
+
+    pub fn set_preferred(&mut self, index: usize) {
+        if index < self.endpoints.len() {
+            self.preferred = index;
+            // An explicit choice is tried right away
+            self.endpoints[index].retry_at = None;
+        }
+    }
+
+    /// Record the chain an endpoint serves; a different chain forgets the old head
+    pub fn set_chain(&mut self, index: usize, chain_id: u64) {
+        let Some(health) = self.endpoints.get_mut(index) else {
+            return;
+        };
+        if health.chain_id != Some(chain_id) {
+            health.head = None;
+        }
+        health.chain_id = Some(chain_id);
+    }
+
+    pub fn chain_id(&self, index: usize) -> Option<u64> {
+        self.endpoints.get(index).and_then(|health| health.chain_id)
+    }
+
+    /// Whether two endpoints may stand in for each other: same chain, or not known yet
+    fn compatible(&self, a: usize, b: usize) -> bool {
+        match (self.chain_id(a), self.chain_id(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => true,
+        }
+    }
+
+    pub fn record_success(&mut self, index: usize, latency_ms: f64, head: u64, now: Instant) {
+        let Some(health) = self.endpoints.get_mut(index) else {
+            return;
+        };
+        health.error_rate *= 1.0 - EWMA_ALPHA;
+        health.latency_ms = Some(match health.latency_ms {
+            Some(avg) => avg + EWMA_ALPHA * (latency_ms - avg),
+            None => latency_ms,
+        });
+        health.head = Some(health.head.map_or(head, |known| known.max(head)));
+        health.failures = 0;
+        health.successes += 1;
+        health.retry_at = None;
+        health.next_probe = now + PROBE_INTERVAL;
+    }
+
+    /// Record a failure and back off; `jitter` in 0..1 spreads retries by ±20%
+    pub fn record_failure(&mut self, index: usize, now: Instant, jitter: f64) -> Duration {
+        let Some(health) = self.endpoints.get_mut(index) else {
+            return Duration::ZERO;
+        };
+        health.error_rate = health.error_rate * (1.0 - EWMA_ALPHA) + EWMA_ALPHA;
+        health.failures += 1;
+        health.successes = 0;
+
+        let exp = health.failures.saturating_sub(1).min(16);
+        let delay = BACKOFF_BASE.saturating_mul(1 << exp).min(BACKOFF_MAX);
+        let delay = delay.mul_f64(0.8 + 0.4 * jitter.clamp(0.0, 1.0));
+        health.retry_at = Some(now + delay);
+        health.next_probe = now + delay;
+        delay
+    }
+
+    /// Whether a connection attempt is allowed now
+    pub fn is_available(&self, index: usize, now: Instant) -> bool {
+        self.endpoints
+            .get(index)
+            .is_some_and(|health| health.retry_at.is_none_or(|at| at <= now))
+    }
+
+    /// When the next backed-off endpoint may be retried
+    pub fn next_retry(&self) -> Option<Instant> {
+        self.endpoints.iter().filter_map(|health| health.retry_at).min()
+    }
+
+    /// Blocks behind the best head seen on any endpoint of the same chain
+    pub fn head_lag(&self, index: usize) -> u64 {
+        let Some(chain_id) = self.chain_id(index) else {
+            return 0;
+        };
+        let best = self
+            .endpoints
+            .iter()
+            .filter(|health| health.chain_id == Some(chain_id))
+            .filter_map(|health| health.head)
+            .max();
+        match (best, self.endpoints.get(index).and_then(|health| health.head)) {
+            (Some(best), Some(head)) => best.saturating_sub(head),
+            _ => 0,
+        }
+    }
+
+    /// 0..100, higher is healthier; zero while down
+    pub fn score(&self, index: usize) -> f64 {
+        let Some(health) = self.endpoints.get(index) else {
+            return 0.0;
+        };
+        if health.failures > 0 {
+            return 0.0;
+        }
+        let latency = health.latency_ms.map_or(0.0, |ms| (ms / 20.0).min(25.0));
+        let lag = (self.head_lag(index) as f64 * 5.0).min(25.0);
+        (100.0 - health.error_rate * 50.0 - latency - lag).max(0.0)
+    }
+
+    /// Endpoints other than `current` whose background probe is due
+    pub fn due_probes(&mut self, current: usize, now: Instant) -> Vec<usize> {
+        let mut due = Vec::new();
+        for (index, health) in self.endpoints.iter_mut().enumerate() {
+            if index != current && health.next_probe <= now {
+                // Pushed back again when the result comes in
+                health.next_probe = now + PROBE_INTERVAL;
+                due.push(index);
+            }
+        }
+        due
+    }
+
+    /// Healthiest endpoint on the chain of `current` that may be tried now,
+    /// `current` itself only when `include_current`; the preferred endpoint wins
+    /// ties, then configuration order
+    pub fn best_available(&self, current: usize, include_current: bool, now: Instant) -> Option<usize> {
+        (0..self.endpoints.len())
+            .filter(|index| include_current || *index != current)
+            .filter(|index| self.compatible(*index, current) && self.is_available(*index, now))
+            .max_by(|a, b| {
+                self.score(*a)
+                    .total_cmp(&self.score(*b))
+                    .then((*a == self.preferred).cmp(&(*b == self.preferred)))
+                    .then(b.cmp(a))
+            })
+    }
+
+    /// Whether the connected endpoint should be swapped for a healthier one
+    pub fn decide(&self, current: usize, now: Instant) -> Option<Switch> {
+        let preferred = &self.endpoints[self.preferred];
+        if current != self.preferred
+            && self.compatible(current, self.preferred)
+            && preferred.failures == 0
+            && preferred.successes >= RECOVERY_STREAK
+            && self.head_lag(self.preferred) <= LAG_LIMIT
+        {
+            return Some(Switch {
+                to: self.preferred,
+                reason: "preferred endpoint recovered".to_string(),
+            });
+        }
+
+        let lag = self.head_lag(current);
+        let error_rate = self.endpoints[current].error_rate;
+        let reason = if lag > LAG_LIMIT {
+            format!("{lag} blocks behind")
+        } else if error_rate > ERROR_LIMIT {
+            format!("{:.0}% of requests failing", error_rate * 100.0)
+        } else {
+            return None;
+        };
+        let to = self.best_available(current, false, now)?;
+        let healthy = self.endpoints[to].failures == 0
+            && self.endpoints[to].successes > 0
+            && self.head_lag(to) <= LAG_LIMIT;
+        (healthy && self.score(to) > self.score(current)).then_some(Switch { to, reason })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_grows_and_caps() {
+        let now = Instant::now();
+        let mut health = HealthTracker::new(2, now);
+        assert_eq!(health.record_failure(1, now, 0.5), Duration::from_secs(1));
+        assert_eq!(health.record_failure(1, now, 0.5), Duration::from_secs(2));
+        assert_eq!(health.record_failure(1, now, 0.0), Duration::from_millis(3_200));
+        for _ in 0..10 {
+            health.record_failure(1, now, 1.0);
+        }
+        assert_eq!(health.record_failure(1, now, 1.0), Duration::from_secs(72));
+
+        assert!(!health.is_available(1, now));
+        assert!(health.is_available(1, now + Duration::from_secs(72)));
+        assert_eq!(health.best_available(1, true, now), Some(0));
+        assert_eq!(health.score(1), 0.0);
+    }
+
+    #[test]
+    fn test_fails_over_on_lag_and_back_on_recovery() {
+        let now = Instant::now();
+        let mut health = HealthTracker::new(3, now);
+        for index in 0..3 {
+            health.set_chain(index, 1);
+        }
+        health.record_success(0, 50.0, 100, now);
+        health.record_success(1, 80.0, 110, now);
+        health.record_success(2, 20.0, 90, now);
+
+        let switch = health.decide(0, now).unwrap();
+        assert_eq!(switch.to, 1);
+        assert_eq!(switch.reason, "10 blocks behind");
+
+        // Preferred endpoint 0 catches up and stays up for a few probes
+        for _ in 0..RECOVERY_STREAK {
+            health.record_success(0, 50.0, 110, now);
+        }
+        let switch = health.decide(1, now).unwrap();
+        assert_eq!(switch.to, 0);
+        assert!(health.decide(0, now).is_none());
+    }
+
+    #[test]
+    fn test_preferred_wins_ties() {
+        let now = Instant::now();
+        let mut health = HealthTracker::new(3, now);
+        health.set_preferred(2);
+        assert_eq!(health.best_available(2, true, now), Some(2));
+        assert_eq!(health.best_available(2, false, now), Some(0));
+        assert_eq!(health.due_probes(2, now), vec![0, 1]);
+        assert!(health.due_probes(2, now).is_empty());
+    }
+
+    #[test]
+    fn test_heads_compared_within_chain_only() {
+        let now = Instant::now();
+        // Local Anvil (preferred) next to a mainnet endpoint millions of blocks ahead
+        let mut health = HealthTracker::new(3, now);
+        health.set_chain(0, 31337);
+        health.set_chain(1, 1);
+        health.set_chain(2, 1);
+        health.record_success(0, 5.0, 120, now);
+        health.record_success(1, 80.0, 21_000_000, now);
+        health.record_success(2, 60.0, 20_999_990, now);
+
+        assert_eq!(health.head_lag(0), 0);
+        assert_eq!(health.head_lag(2), 10);
+        assert!(health.decide(0, now).is_none());
+        assert_eq!(health.decide(2, now).unwrap().to, 1);
+
+        // Anvil failing over lands nowhere rather than on mainnet
+        health.record_failure(0, now, 0.5);
+        assert_eq!(health.best_available(0, false, now), None);
+
+        // Failing back to Anvil is judged on Anvil's own chain
+        for _ in 0..RECOVERY_STREAK {
+            health.record_success(0, 5.0, 121, now);
+        }
+        assert!(health.decide(1, now).is_none());
+        health.set_chain(2, 31337);
+        health.record_success(2, 5.0, 121, now);
+        assert_eq!(health.decide(2, now).unwrap().to, 0);
+    }
+}
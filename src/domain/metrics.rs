@@ -0,0 +1,294 @@
+//! Rolling time series for node metrics (block time, gas, fees, peers, RPC latency)
+
+use std::collections::{BTreeMap, VecDeque};
+
+/// How long samples are kept
+pub const RETENTION_SECS: u64 = 24 * 60 * 60;
+
+/// Buckets per chart, regardless of window
+pub const CHART_BUCKETS: usize = 60;
+
+/// Prefix of per-method RPC latency series
+const RPC_PREFIX: &str = "rpc:";
+
+/// Prefix of series scraped from the node's own metrics endpoint
+const NODE_PREFIX: &str = "node:";
+
+/// Time range shown by the metrics charts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MetricsWindow {
+    #[default]
+    FiveMinutes,
+    Hour,
+    Day,
+}
+
+impl MetricsWindow {
+    pub const ALL: [MetricsWindow; 3] = [
+        MetricsWindow::FiveMinutes,
+        MetricsWindow::Hour,
+        MetricsWindow::Day,
+    ];
+
+    pub fn secs(&self) -> u64 {
+        match self {
+            MetricsWindow::FiveMinutes => 5 * 60,
+            MetricsWindow::Hour => 60 * 60,
+            MetricsWindow::Day => RETENTION_SECS,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MetricsWindow::FiveMinutes => "5m",
+            MetricsWindow::Hour => "1h",
+            MetricsWindow::Day => "24h",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            MetricsWindow::FiveMinutes => MetricsWindow::Hour,
+            MetricsWindow::Hour => MetricsWindow::Day,
+            MetricsWindow::Day => MetricsWindow::FiveMinutes,
+        }
+    }
+}
+
+/// Samples of one metric, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct TimeSeries {
+    points: VecDeque<(u64, f64)>,
+}
+
+impl TimeSeries {
+    /// Append a sample taken at `at` (unix seconds) and drop expired ones
+    pub fn push(&mut self, at: u64, value: f64) {
+        self.points.push_back((at, value));
+        let cutoff = at.saturating_sub(RETENTION_SECS);
+        while self.points.front().is_some_and(|(t, _)| *t < cutoff) {
+            self.points.pop_front();
+        }
+    }
+
+    /// Average per bucket over `[now - window, now]`, `None` for empty buckets
+    pub fn buckets(&self, now: u64, window: u64, count: usize) -> Vec<Option<f64>> {
+        let start = now.saturating_sub(window);
+        let width = (window as f64 / count as f64).max(1.0);
+        let mut sums = vec![(0.0, 0usize); count];
+        for &(at, value) in self.points.iter().filter(|(at, _)| *at >= start && *at <= now) {
+            let index = (((at - start) as f64 / width) as usize).min(count - 1);
+            sums[index].0 += value;
+            sums[index].1 += 1;
+        }
+        sums.into_iter()
+            .map(|(sum, n)| (n > 0).then(|| sum / n as f64))
+            .collect()
+    }
+}
+
+/// One series prepared for display
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesView {
+    pub key: String,
+    pub label: String,
+    pub unit: &'static str,
+    pub buckets: Vec<Option<f64>>,
+    pub last: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub avg: Option<f64>,
+}
+
+/// The rolling metrics kept by the worker
+#[derive(Debug, Default)]
+pub struct MetricsStore {
+    series: BTreeMap<String, TimeSeries>,
+    /// Label and unit of each node series
+    node_series: BTreeMap<String, (String, &'static str)>,
+    /// (number, timestamp) of the newest block, for block times
+    last_block: Option<(u64, u64)>,
+}
+
+/// Fixed series in display order: (key, label, unit)
+const FIXED_SERIES: [(&str, &str, &str); 7] = [
+    ("block_time", "Block time", "s"),
+    ("gas_ratio", "Gas used/limit", "%"),
+    ("base_fee", "Base fee", "gwei"),
+    ("tx_count", "Tx count", "txs"),
+    ("blob_count", "Blobs", "blobs"),
+    ("blob_fee", "Blob base fee", "gwei"),
+    ("peers", "Peers", ""),
+];
+
+impl MetricsStore {
+    /// Forget everything, e.g. after switching to another endpoint
+    pub fn reset(&mut self) {
+        self.series.clear();
+        self.node_series.clear();
+        self.last_block = None;
+    }
+
+    /// Record a block, timestamped with its own header time
+    pub fn observe_block(
+        &mut self,
+        number: u64,
+        timestamp: u64,
+        gas_used: u64,
+        gas_limit: u64,
+        base_fee_wei: Option<u64>,
+        tx_count: usize,
+    ) -> bool {
+        match self.last_block {
+            Some((last, _)) if number <= last => return false,
+            Some((last, last_ts)) if number == last + 1 => {
+                self.record("block_time", timestamp, timestamp.saturating_sub(last_ts) as f64);
+            }
+            _ => {}
+        }
+        self.last_block = Some((number, timestamp));
+
+        if gas_limit > 0 {
+            self.record("gas_ratio", timestamp, gas_used as f64 * 100.0 / gas_limit as f64);
+        }
+        if let Some(fee) = base_fee_wei {
+            self.record("base_fee", timestamp, fee as f64 / 1e9);
+        }
+        self.record("tx_count", timestamp, tx_count as f64);
+        true
+    }
+
+    /// Blob count of a block already seen by `observe_block`, plus its blob base fee when the schedule is known
+    pub fn observe_blobs(&mut self, timestamp: u64, blob_count: u64, blob_base_fee_wei: Option<u128>) {
+        self.record("blob_count", timestamp, blob_count as f64);
+        if let Some(fee) = blob_base_fee_wei {
+            self.record("blob_fee", timestamp, fee as f64 / 1e9);
+        }
+    }
+
+    pub fn observe_peers(&mut self, at: u64, peers: u32) {
+        self.record("peers", at, peers as f64);
+    }
+
+    /// Average latency of one RPC method over the last sampling period
+    pub fn observe_latency(&mut self, at: u64, method: &str, ms: f64) {
+        self.record(&format!("{RPC_PREFIX}{method}"), at, ms);
+    }
+
+    /// A series scraped from the node's metrics endpoint
+    pub fn observe_node(&mut self, at: u64, key: &str, label: &str, unit: &'static str, value: f64) {
+        let key = format!("{NODE_PREFIX}{key}");
+        self.node_series
+            .entry(key.clone())
+            .or_insert_with(|| (label.to_string(), unit));
+        self.record(&key, at, value);
+    }
+
+    fn record(&mut self, key: &str, at: u64, value: f64) {
+        self.series.entry(key.to_string()).or_default().push(at, value);
+    }
+
+    /// Every non-empty series bucketed over the window: block metrics, then
+    /// node metrics, then RPC latencies
+    pub fn view(&self, now: u64, window: MetricsWindow) -> Vec<SeriesView> {
+        let fixed = FIXED_SERIES
+            .iter()
+            .map(|(key, label, unit)| (key.to_string(), label.to_string(), *unit));
+        let node = self
+            .node_series
+            .iter()
+            .map(|(key, (label, unit))| (key.clone(), label.clone(), *unit));
+        let rpc = self
+            .series
+            .keys()
+            .filter_map(|key| key.strip_prefix(RPC_PREFIX).map(|method| (key, method)))
+            .map(|(key, method)| (key.clone(), method.to_string(), "ms"));
+
+        fixed
+            .chain(node)
+            .chain(rpc)
+            .filter_map(|(key, label, unit)| {
+                let series = self.series.get(&key)?;
+                let buckets = series.buckets(now, window.secs(), CHART_BUCKETS);
+                let values: Vec<f64> = buckets.iter().flatten().copied().collect();
+                if values.is_empty() {
+                    return None;
+                }
+                Some(SeriesView {
+                    key,
+                    label,
+                    unit,
+                    last: values.last().copied(),
+                    min: values.iter().copied().reduce(f64::min),
+                    max: values.iter().copied().reduce(f64::max),
+                    avg: Some(values.iter().sum::<f64>() / values.len() as f64),
+                    buckets,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retention() {
+        let mut series = TimeSeries::default();
+        series.push(0, 1.0);
+        series.push(RETENTION_SECS, 2.0);
+        assert_eq!(series.points.len(), 2);
+        series.push(RETENTION_SECS + 1, 3.0);
+        assert_eq!(series.points.len(), 2);
+        assert_eq!(series.points.front(), Some(&(RETENTION_SECS, 2.0)));
+    }
+
+    #[test]
+    fn test_buckets() {
+        let mut series = TimeSeries::default();
+        series.push(1_000, 2.0);
+        series.push(1_001, 4.0);
+        series.push(1_299, 10.0);
+        // Outside a 5 minute window ending at 1300
+        series.push(900, 99.0);
+
+        let buckets = series.buckets(1_300, 300, 3);
+        assert_eq!(buckets, vec![Some(3.0), None, Some(10.0)]);
+    }
+
+    #[test]
+    fn test_block_metrics() {
+        let mut store = MetricsStore::default();
+        store.observe_block(10, 1_000, 15_000_000, 30_000_000, Some(20_000_000_000), 150);
+        store.observe_block(11, 1_012, 30_000_000, 30_000_000, Some(25_000_000_000), 200);
+        // Reorged or repeated heads are ignored
+        assert!(!store.observe_block(11, 1_013, 0, 30_000_000, None, 0));
+        store.observe_latency(1_012, "eth_getBlockByNumber", 42.0);
+        store.observe_node(1_015, "txpool", "Txpool size", "txs", 150.0);
+        store.observe_blobs(1_012, 6, Some(2_000_000_000));
+
+        let view = store.view(1_020, MetricsWindow::FiveMinutes);
+        let keys: Vec<&str> = view.iter().map(|s| s.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "block_time",
+                "gas_ratio",
+                "base_fee",
+                "tx_count",
+                "blob_count",
+                "blob_fee",
+                "node:txpool",
+                "rpc:eth_getBlockByNumber"
+            ]
+        );
+        assert_eq!(view[0].last, Some(12.0));
+        assert_eq!(view[1].max, Some(100.0));
+        assert_eq!(view[2].min, Some(20.0));
+        assert_eq!(view[3].avg, Some(175.0));
+        assert_eq!(view[5].last, Some(2.0));
+        assert_eq!(view[6].label, "Txpool size");
+        assert_eq!(view[7].label, "eth_getBlockByNumber");
+    }
+}
@@ -1,4 +1,17 @@
 //! Domain layer - Pure business logic types
 
 pub mod abi;
+pub mod alerts;
+pub mod beacon;
+pub mod blobs;
+pub mod budget;
+pub mod delegation;
+pub mod finality;
+pub mod health;
+pub mod metrics;
+pub mod nodes;
+pub mod optimism;
+pub mod quorum;
+pub mod rpc_stats;
+pub mod transfers;
 
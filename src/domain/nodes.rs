@@ -0,0 +1,252 @@
+//! Node comparison - lines up every configured endpoint and flags the sick ones
+
+use std::collections::BTreeMap;
+
+/// Blocks a node may trail the best head before it counts as lagging
+pub const LAG_THRESHOLD: u64 = 2;
+
+/// What one endpoint reported during a comparison round
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeProbe {
+    pub endpoint: String,
+    /// Last chain id the endpoint reported; heads and hashes are only compared within a chain
+    pub chain_id: Option<u64>,
+    pub client: Option<String>,
+    pub head: Option<u64>,
+    pub head_hash: Option<String>,
+    /// Hash of the block at the round's common height
+    pub check_hash: Option<String>,
+    pub rtt_ms: Option<u64>,
+    pub peers: Option<u32>,
+    /// (current, highest) while the node reports eth_syncing
+    pub sync: Option<(u64, u64)>,
+    pub error: Option<String>,
+}
+
+/// Verdict for one node, worst condition first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeVerdict {
+    /// Unreachable or failing RPC
+    Down,
+    /// Different block hash at the common height than the majority
+    Forked,
+    Syncing,
+    /// Head this many blocks behind the best node
+    Lagging(u64),
+    Healthy,
+}
+
+impl NodeVerdict {
+    pub fn label(&self) -> String {
+        match self {
+            NodeVerdict::Down => "down".to_string(),
+            NodeVerdict::Forked => "hash mismatch".to_string(),
+            NodeVerdict::Syncing => "syncing".to_string(),
+            NodeVerdict::Lagging(behind) => format!("{behind} behind"),
+            NodeVerdict::Healthy => "ok".to_string(),
+        }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        *self == NodeVerdict::Healthy
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeStatus {
+    pub probe: NodeProbe,
+    pub verdict: NodeVerdict,
+}
+
+/// One comparison round across the endpoints of one chain
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeComparison {
+    /// None for endpoints that never reported a chain id
+    pub chain_id: Option<u64>,
+    /// Height every reachable node has, where hashes are compared
+    pub height: Option<u64>,
+    /// Majority hash at `height`
+    pub canonical: Option<String>,
+    pub best_head: Option<u64>,
+    pub nodes: Vec<NodeStatus>,
+    /// Unix seconds the round finished
+    pub at: u64,
+}
+
+impl NodeComparison {
+    /// Nodes needing attention
+    pub fn unhealthy(&self) -> usize {
+        self.nodes.iter().filter(|node| !node.verdict.is_healthy()).count()
+    }
+}
+
+/// Lowest head among reachable nodes of `chain_id`: the newest block all of
+/// them should agree on
+pub fn common_height(probes: &[NodeProbe], chain_id: Option<u64>) -> Option<u64> {
+    probes
+        .iter()
+        .filter(|probe| probe.chain_id == chain_id && probe.error.is_none())
+        .filter_map(|probe| probe.head)
+        .min()
+}
+
+/// Compare each chain's endpoints among themselves, chains in order of first endpoint
+pub fn compare_by_chain(probes: Vec<NodeProbe>, at: u64) -> Vec<NodeComparison> {
+    let mut chains: Vec<(Option<u64>, Vec<NodeProbe>)> = Vec::new();
+    for probe in probes {
+        match chains.iter_mut().find(|(chain_id, _)| *chain_id == probe.chain_id) {
+            Some((_, group)) => group.push(probe),
+            None => chains.push((probe.chain_id, vec![probe])),
+        }
+    }
+    chains
+        .into_iter()
+        .map(|(chain_id, group)| {
+            let height = common_height(&group, chain_id);
+            NodeComparison {
+                chain_id,
+                ..compare(group, height, at)
+            }
+        })
+        .collect()
+}
+
+/// Judge every probe against the best head and the majority hash at `height`
+pub fn compare(probes: Vec<NodeProbe>, height: Option<u64>, at: u64) -> NodeComparison {
+    let best_head = probes
+        .iter()
+        .filter(|probe| probe.error.is_none())
+        .filter_map(|probe| probe.head)
+        .max();
+
+    // Majority vote; ties go to the hash seen first in endpoint order
+    let mut votes: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for (order, hash) in probes
+        .iter()
+        .filter_map(|probe| probe.check_hash.as_deref())
+        .enumerate()
+    {
+        votes.entry(hash).or_insert((0, order)).0 += 1;
+    }
+    let canonical = votes
+        .into_iter()
+        .max_by(|(_, (a, a_order)), (_, (b, b_order))| a.cmp(b).then(b_order.cmp(a_order)))
+        .map(|(hash, _)| hash.to_string());
+
+    let nodes = probes
+        .into_iter()
+        .map(|probe| {
+            let verdict = verdict(&probe, best_head, canonical.as_deref());
+            NodeStatus { probe, verdict }
+        })
+        .collect();
+
+    NodeComparison {
+        chain_id: None,
+        height,
+        canonical,
+        best_head,
+        nodes,
+        at,
+    }
+}
+
+fn verdict(probe: &NodeProbe, best_head: Option<u64>, canonical: Option<&str>) -> NodeVerdict {
+    let Some(head) = probe.head.filter(|_| probe.error.is_none()) else {
+        return NodeVerdict::Down;
+    };
+    if let (Some(hash), Some(canonical)) = (probe.check_hash.as_deref(), canonical) {
+        if !hash.eq_ignore_ascii_case(canonical) {
+            return NodeVerdict::Forked;
+        }
+    }
+    if probe.sync.is_some() {
+        return NodeVerdict::Syncing;
+    }
+    let behind = best_head.unwrap_or(head).saturating_sub(head);
+    if behind > LAG_THRESHOLD {
+        NodeVerdict::Lagging(behind)
+    } else {
+        NodeVerdict::Healthy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probe(endpoint: &str, head: u64, check_hash: &str) -> NodeProbe {
+        NodeProbe {
+            endpoint: endpoint.to_string(),
+            head: Some(head),
+            check_hash: Some(check_hash.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_flags_lagging_forked_and_down() {
+        let probes = vec![
+            probe("a", 100, "0xaa"),
+            probe("b", 101, "0xaa"),
+            probe("c", 100, "0xbb"),
+            probe("d", 95, "0xaa"),
+            NodeProbe {
+                endpoint: "e".to_string(),
+                error: Some("connection refused".to_string()),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(common_height(&probes, None), Some(95));
+
+        let comparison = compare(probes, Some(95), 1_000);
+        assert_eq!(comparison.best_head, Some(101));
+        assert_eq!(comparison.canonical.as_deref(), Some("0xaa"));
+        let verdicts: Vec<NodeVerdict> = comparison.nodes.iter().map(|n| n.verdict).collect();
+        assert_eq!(
+            verdicts,
+            vec![
+                NodeVerdict::Healthy,
+                NodeVerdict::Healthy,
+                NodeVerdict::Forked,
+                NodeVerdict::Lagging(6),
+                NodeVerdict::Down,
+            ]
+        );
+        assert_eq!(comparison.unhealthy(), 3);
+    }
+
+    #[test]
+    fn test_tie_goes_to_first_endpoint() {
+        let mut syncing = probe("b", 50, "0x02");
+        syncing.sync = Some((50, 900));
+        let comparison = compare(vec![probe("a", 50, "0x01"), syncing], Some(50), 0);
+        assert_eq!(comparison.canonical.as_deref(), Some("0x01"));
+        assert_eq!(comparison.nodes[0].verdict, NodeVerdict::Healthy);
+        assert_eq!(comparison.nodes[1].verdict, NodeVerdict::Forked);
+    }
+
+    #[test]
+    fn test_chains_compared_separately() {
+        let on = |chain_id, mut probe: NodeProbe| {
+            probe.chain_id = Some(chain_id);
+            probe
+        };
+        let probes = vec![
+            on(1, probe("mainnet-a", 20_000_000, "0xaa")),
+            on(10, probe("optimism", 120_000_000, "0xcc")),
+            on(1, probe("mainnet-b", 20_000_001, "0xaa")),
+        ];
+        assert_eq!(common_height(&probes, Some(1)), Some(20_000_000));
+
+        let comparisons = compare_by_chain(probes, 0);
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].chain_id, Some(1));
+        assert_eq!(comparisons[0].best_head, Some(20_000_001));
+        assert_eq!(comparisons[0].height, Some(20_000_000));
+        assert_eq!(comparisons[0].nodes.len(), 2);
+        assert_eq!(comparisons[1].chain_id, Some(10));
+        // Neither chain's nodes lag or fork against the other chain's
+        assert!(comparisons.iter().all(|comparison| comparison.unhealthy() == 0));
+    }
+}
@@ -0,0 +1,88 @@
+//! OP Stack specifics - deposit transactions, L1 data fees and the L1 origin of L2 blocks
+
+/// Transaction type of L1-to-L2 deposits
+pub const DEPOSIT_TX_TYPE: u8 = 0x7e;
+
+/// L1Block predeploy, updated by the first deposit of every L2 block
+pub const L1_BLOCK_ADDRESS: &str = "0x4200000000000000000000000000000000000015";
+
+/// Fields only a deposit transaction carries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepositInfo {
+    /// Identifies the L1 event that caused the deposit
+    pub source_hash: String,
+    /// Wei minted on L2 before the deposit executes
+    pub mint: u128,
+    /// Pre-Regolith system deposits, which used no gas
+    pub is_system_tx: bool,
+}
+
+/// L1 data fee an OP Stack receipt charges on top of L2 execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct L1Fee {
+    /// Wei charged for posting the transaction's data to L1
+    pub fee: u128,
+    pub gas_used: Option<u128>,
+    /// Ecotone scalars; None on older receipts
+    pub base_fee_scalar: Option<u128>,
+    pub blob_base_fee_scalar: Option<u128>,
+}
+
+impl L1Fee {
+    /// Share of the total fee that went to L1 data, 0.0 to 1.0
+    pub fn share(&self, execution_fee: u128) -> f64 {
+        let total = self.fee.saturating_add(execution_fee);
+        if total == 0 {
+            return 0.0;
+        }
+        self.fee as f64 / total as f64
+    }
+}
+
+/// L1 block the L2 chain last derived from, as stored in the L1Block predeploy
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct L1Origin {
+    pub number: u64,
+    pub timestamp: u64,
+    pub hash: String,
+    pub base_fee: u128,
+    /// Ecotone fields; None before the upgrade
+    pub blob_base_fee: Option<u128>,
+    pub base_fee_scalar: Option<u32>,
+    pub blob_base_fee_scalar: Option<u32>,
+    /// L2 blocks built on this origin so far, 0 for the first
+    pub sequence_number: u64,
+    pub batcher_hash: String,
+}
+
+impl L1Origin {
+    /// Seconds since the origin block was produced
+    pub fn age(&self, now: u64) -> u64 {
+        now.saturating_sub(self.timestamp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_l1_fee_share_and_origin_age() {
+        let fee = L1Fee {
+            fee: 3_000,
+            gas_used: Some(1_600),
+            base_fee_scalar: Some(1_368),
+            blob_base_fee_scalar: Some(810_949),
+        };
+        assert_eq!(fee.share(1_000), 0.75);
+        assert_eq!(L1Fee { fee: 0, ..fee }.share(0), 0.0);
+
+        let origin = L1Origin {
+            number: 21_000_000,
+            timestamp: 1_730_000_000,
+            ..L1Origin::default()
+        };
+        assert_eq!(origin.age(1_730_000_030), 30);
+        assert_eq!(origin.age(1_729_999_990), 0);
+    }
+}
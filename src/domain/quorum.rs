@@ -0,0 +1,117 @@
+//! Quorum reads - the same request answered by several providers, and whether they agree
+
+/// One provider's answer, rendered to a comparable string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuorumResponse {
+    pub endpoint: String,
+    pub value: Result<String, String>,
+}
+
+/// A read sent to every quorum member
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuorumReport {
+    pub method: String,
+    pub params: String,
+    pub responses: Vec<QuorumResponse>,
+    /// Value returned by more than half of the members
+    pub majority: Option<String>,
+    /// Unix seconds
+    pub at: u64,
+}
+
+impl QuorumReport {
+    pub fn new(method: &str, params: String, responses: Vec<QuorumResponse>, at: u64) -> Self {
+        let majority = majority(&responses);
+        Self {
+            method: method.to_string(),
+            params,
+            responses,
+            majority,
+            at,
+        }
+    }
+
+    /// Every member answered, and with the same value
+    pub fn agreed(&self) -> bool {
+        self.majority.is_some() && self.divergent().is_empty()
+    }
+
+    /// Members that failed or answered something other than the majority; all of
+    /// them when there is no majority
+    pub fn divergent(&self) -> Vec<&QuorumResponse> {
+        self.responses
+            .iter()
+            .filter(|response| {
+                self.majority.is_none() || response.value.as_ref().ok() != self.majority.as_ref()
+            })
+            .collect()
+    }
+}
+
+fn majority(responses: &[QuorumResponse]) -> Option<String> {
+    let values: Vec<&String> = responses
+        .iter()
+        .filter_map(|response| response.value.as_ref().ok())
+        .collect();
+    values
+        .iter()
+        .find(|value| values.iter().filter(|other| other == value).count() * 2 > responses.len())
+        .map(|value| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(endpoint: &str, value: Result<&str, &str>) -> QuorumResponse {
+        QuorumResponse {
+            endpoint: endpoint.to_string(),
+            value: value.map(str::to_string).map_err(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_majority_and_divergence() {
+        let report = QuorumReport::new(
+            "eth_getBalance",
+            "0xabc".to_string(),
+            vec![
+                response("a", Ok("100")),
+                response("b", Ok("100")),
+                response("c", Ok("90")),
+                response("d", Err("timeout")),
+            ],
+            0,
+        );
+        // 2 of 4 is not a majority
+        assert_eq!(report.majority, None);
+        assert!(!report.agreed());
+        assert_eq!(report.divergent().len(), 4);
+
+        let report = QuorumReport::new(
+            "eth_getBalance",
+            "0xabc".to_string(),
+            vec![
+                response("a", Ok("100")),
+                response("b", Ok("100")),
+                response("c", Ok("90")),
+            ],
+            0,
+        );
+        assert_eq!(report.majority.as_deref(), Some("100"));
+        assert!(!report.agreed());
+        let divergent: Vec<&str> = report.divergent().iter().map(|r| r.endpoint.as_str()).collect();
+        assert_eq!(divergent, vec!["c"]);
+    }
+
+    #[test]
+    fn test_agreement() {
+        let report = QuorumReport::new(
+            "eth_getStorageAt",
+            "0xabc 0x0".to_string(),
+            vec![response("a", Ok("0x01")), response("b", Ok("0x01"))],
+            0,
+        );
+        assert!(report.agreed());
+    }
+}
@@ -0,0 +1,166 @@
+//! Per-method RPC statistics - call and error counts with latency percentiles
+
+use std::collections::VecDeque;
+
+/// Latency samples kept per method and endpoint
+const MAX_SAMPLES: usize = 1024;
+
+/// Counters for one JSON-RPC method on one endpoint
+#[derive(Debug, Clone, Default)]
+pub struct MethodStats {
+    pub calls: u64,
+    pub errors: u64,
+    /// Latencies of successful calls in ms, newest last
+    samples: VecDeque<f64>,
+}
+
+impl MethodStats {
+    pub fn record(&mut self, ms: f64, ok: bool) {
+        self.calls += 1;
+        if !ok {
+            self.errors += 1;
+            return;
+        }
+        self.samples.push_back(ms);
+        if self.samples.len() > MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Nearest-rank percentile (0..100) over the kept samples
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<f64> = self.samples.iter().copied().collect();
+        sorted.sort_by(f64::total_cmp);
+        let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.clamp(1, sorted.len()) - 1])
+    }
+
+    pub fn row(&self, endpoint: &str, method: &str) -> RpcStatRow {
+        RpcStatRow {
+            endpoint: endpoint.to_string(),
+            method: method.to_string(),
+            calls: self.calls,
+            errors: self.errors,
+            p50: self.percentile(50.0),
+            p95: self.percentile(95.0),
+            p99: self.percentile(99.0),
+        }
+    }
+}
+
+/// One line of the RPC stats table
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcStatRow {
+    pub endpoint: String,
+    pub method: String,
+    pub calls: u64,
+    pub errors: u64,
+    pub p50: Option<f64>,
+    pub p95: Option<f64>,
+    pub p99: Option<f64>,
+}
+
+/// Column the RPC stats table is sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RpcStatsSort {
+    Calls,
+    Errors,
+    P50,
+    #[default]
+    P95,
+    P99,
+    Method,
+}
+
+impl RpcStatsSort {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RpcStatsSort::Calls => "calls",
+            RpcStatsSort::Errors => "errors",
+            RpcStatsSort::P50 => "p50",
+            RpcStatsSort::P95 => "p95",
+            RpcStatsSort::P99 => "p99",
+            RpcStatsSort::Method => "method",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            RpcStatsSort::Calls => RpcStatsSort::Errors,
+            RpcStatsSort::Errors => RpcStatsSort::P50,
+            RpcStatsSort::P50 => RpcStatsSort::P95,
+            RpcStatsSort::P95 => RpcStatsSort::P99,
+            RpcStatsSort::P99 => RpcStatsSort::Method,
+            RpcStatsSort::Method => RpcStatsSort::Calls,
+        }
+    }
+}
+
+/// Sort rows by `sort`: counts and latencies descending, methods A-Z
+pub fn sort_rows(rows: &mut [RpcStatRow], sort: RpcStatsSort) {
+    let latency = |value: Option<f64>| value.unwrap_or(-1.0);
+    rows.sort_by(|a, b| {
+        let order = match sort {
+            RpcStatsSort::Calls => b.calls.cmp(&a.calls),
+            RpcStatsSort::Errors => b.errors.cmp(&a.errors),
+            RpcStatsSort::P50 => latency(b.p50).total_cmp(&latency(a.p50)),
+            RpcStatsSort::P95 => latency(b.p95).total_cmp(&latency(a.p95)),
+            RpcStatsSort::P99 => latency(b.p99).total_cmp(&latency(a.p99)),
+            RpcStatsSort::Method => a.method.cmp(&b.method),
+        };
+        order
+            .then_with(|| a.method.cmp(&b.method))
+            .then_with(|| a.endpoint.cmp(&b.endpoint))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles() {
+        let mut stats = MethodStats::default();
+        for ms in 1..=100 {
+            stats.record(ms as f64, true);
+        }
+        stats.record(5_000.0, false);
+
+        let row = stats.row("http://localhost:8545", "eth_call");
+        assert_eq!(row.calls, 101);
+        assert_eq!(row.errors, 1);
+        assert_eq!(row.p50, Some(50.0));
+        assert_eq!(row.p95, Some(95.0));
+        assert_eq!(row.p99, Some(99.0));
+        assert_eq!(MethodStats::default().percentile(50.0), None);
+    }
+
+    #[test]
+    fn test_sort_rows() {
+        let row = |method: &str, calls: u64, p95: Option<f64>| RpcStatRow {
+            endpoint: "a".to_string(),
+            method: method.to_string(),
+            calls,
+            errors: 0,
+            p50: None,
+            p95,
+            p99: None,
+        };
+        let mut rows = vec![
+            row("eth_blockNumber", 500, Some(4.0)),
+            row("debug_traceTransaction", 3, Some(900.0)),
+            row("eth_getBlockByNumber", 80, None),
+        ];
+        sort_rows(&mut rows, RpcStatsSort::P95);
+        let methods: Vec<&str> = rows.iter().map(|r| r.method.as_str()).collect();
+        assert_eq!(
+            methods,
+            vec!["debug_traceTransaction", "eth_blockNumber", "eth_getBlockByNumber"]
+        );
+        sort_rows(&mut rows, RpcStatsSort::Calls);
+        assert_eq!(rows[0].method, "eth_blockNumber");
+    }
+}
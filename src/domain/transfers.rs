@@ -0,0 +1,450 @@
+//! Asset movements within a transaction
+//!
+//! Token transfers are decoded from receipt logs (ERC-20/721/1155 and WETH
+//! deposit/withdrawal), native ETH movements come from call trace frames.
+//! Both are folded into a per-address net change table.
+
+use std::collections::BTreeMap;
+
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_primitives::{b256, Address, B256, U256};
+
+/// `Transfer(address,address,uint256)` - ERC-20 (3 topics) and ERC-721 (4 topics)
+pub const TRANSFER_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+/// `TransferSingle(address,address,address,uint256,uint256)` - ERC-1155
+pub const TRANSFER_SINGLE_TOPIC: B256 =
+    b256!("c3d58168c5ae7397731d063d5bbf3d657854427343f4c083240f7aacaa2d0f62");
+/// `TransferBatch(address,address,address,uint256[],uint256[])` - ERC-1155
+pub const TRANSFER_BATCH_TOPIC: B256 =
+    b256!("4a39dc06d4c0dbc64b70af90fd698a233a518aa5d07e595d983b8c0526c8f7fb");
+/// `Deposit(address,uint256)` - WETH wrap
+pub const DEPOSIT_TOPIC: B256 =
+    b256!("e1fffcc4923d04b559f4d29a8bfc6cda04eb5b0d3c460751c2402c5c5cc9109c");
+/// `Withdrawal(address,uint256)` - WETH unwrap
+pub const WITHDRAWAL_TOPIC: B256 =
+    b256!("7fcf532c15f0a6db0bd6d0e038bea71d30d808c7d98cb3bf7268a95bf5081b65");
+
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+/// WETH9-style wrapped native tokens whose `Deposit`/`Withdrawal` events
+/// stand for a mint/burn, by chain id. Other contracts (staking, vaults)
+/// emit the same signatures without moving a token.
+const WRAPPED_NATIVE: &[(u64, &str)] = &[
+    (1, "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+    (10, "0x4200000000000000000000000000000000000006"),
+    (56, "0xbb4cdb9cbd36b01bd1cbaebf2de08d9173bc095c"),
+    (100, "0xe91d153e0b41518a2ce8dd3d7944fa863463a97d"),
+    (137, "0x0d500b1d8e8ef31e21c99d1db9a6444d3adf1270"),
+    (8453, "0x4200000000000000000000000000000000000006"),
+    (43114, "0xb31f66aa3c1e785363f0875a1b74e27b85fd66c7"),
+    (84532, "0x4200000000000000000000000000000000000006"),
+    (11155111, "0xfff9976782d46cc05630d1f6ebab18b2324d6b14"),
+    (11155420, "0x4200000000000000000000000000000000000006"),
+];
+
+/// Whether `address` (lowercase) is the wrapped native token of `chain_id`
+pub fn is_wrapped_native(chain_id: u64, address: &str) -> bool {
+    WRAPPED_NATIVE
+        .iter()
+        .any(|(id, wrapped)| *id == chain_id && *wrapped == address)
+}
+
+/// Token standard a transfer belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AssetKind {
+    Erc20,
+    Erc721,
+    Erc1155,
+}
+
+/// What moved: native ETH, a fungible token, or a specific NFT id
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Asset {
+    Native,
+    Token {
+        kind: AssetKind,
+        address: String,
+        token_id: Option<U256>,
+    },
+}
+
+impl Asset {
+    pub fn token_address(&self) -> Option<&str> {
+        match self {
+            Asset::Native => None,
+            Asset::Token { address, .. } => Some(address),
+        }
+    }
+}
+
+/// A single asset movement. Addresses are lowercase hex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetTransfer {
+    pub asset: Asset,
+    pub from: String,
+    pub to: String,
+    pub amount: U256,
+}
+
+/// Metadata of a token contract, read via `eth_call` where the contract has it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenMeta {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+    pub total_supply: Option<U256>,
+}
+
+/// Net change of one asset for one address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetChange {
+    pub address: String,
+    pub asset: Asset,
+    pub received: U256,
+    pub sent: U256,
+}
+
+impl AssetChange {
+    /// Absolute net amount and whether it is an increase
+    pub fn net(&self) -> (U256, bool) {
+        if self.received >= self.sent {
+            (self.received - self.sent, true)
+        } else {
+            (self.sent - self.received, false)
+        }
+    }
+}
+
+/// All asset movements of one transaction plus the metadata to render them
+#[derive(Debug, Clone, Default)]
+pub struct TransferSummary {
+    pub tx_hash: String,
+    pub transfers: Vec<AssetTransfer>,
+    /// Token metadata keyed by lowercase token address
+    pub tokens: BTreeMap<String, TokenMeta>,
+    /// False when no call trace was available, so internal ETH movements are missing
+    pub native_traced: bool,
+}
+
+impl TransferSummary {
+    /// Per-address net changes, ordered by address then asset, zero nets dropped
+    pub fn net_changes(&self) -> Vec<AssetChange> {
+        let mut totals: BTreeMap<(String, Asset), (U256, U256)> = BTreeMap::new();
+        for transfer in &self.transfers {
+            if transfer.from == transfer.to || transfer.amount.is_zero() {
+                continue;
+            }
+            let sent = totals
+                .entry((transfer.from.clone(), transfer.asset.clone()))
+                .or_default();
+            sent.1 = sent.1.saturating_add(transfer.amount);
+            let received = totals
+                .entry((transfer.to.clone(), transfer.asset.clone()))
+                .or_default();
+            received.0 = received.0.saturating_add(transfer.amount);
+        }
+
+        totals
+            .into_iter()
+            .filter(|(_, (received, sent))| received != sent)
+            .map(|((address, asset), (received, sent))| AssetChange {
+                address,
+                asset,
+                received,
+                sent,
+            })
+            .collect()
+    }
+
+    /// Symbol to show for an asset (falls back to a shortened token address)
+    pub fn symbol(&self, asset: &Asset) -> String {
+        match asset {
+            Asset::Native => "ETH".to_string(),
+            Asset::Token {
+                address, token_id, ..
+            } => {
+                let base = self
+                    .tokens
+                    .get(address)
+                    .and_then(|meta| meta.symbol.clone())
+                    .filter(|symbol| !symbol.trim().is_empty())
+                    .unwrap_or_else(|| format!("{}…", &address[..address.len().min(10)]));
+                match token_id {
+                    Some(id) => format!("{base} #{id}"),
+                    None => base,
+                }
+            }
+        }
+    }
+
+    /// Format an amount of an asset using its decimals
+    pub fn format_amount(&self, asset: &Asset, amount: U256) -> String {
+        let decimals = match asset {
+            Asset::Native => Some(18),
+            Asset::Token {
+                token_id: Some(_), ..
+            } => None,
+            Asset::Token { address, .. } => self.tokens.get(address).and_then(|meta| meta.decimals),
+        };
+        format_units(amount, decimals)
+    }
+}
+
+/// Decode the asset transfers carried by one log, if it is a known event.
+///
+/// `chain_id` decides which contract's `Deposit`/`Withdrawal` events count as
+/// wrapped-native mints and burns.
+pub fn decode_transfer_log(
+    chain_id: u64,
+    address: &str,
+    topics: &[B256],
+    data: &[u8],
+) -> Vec<AssetTransfer> {
+    let token = address.to_lowercase();
+    let Some(topic0) = topics.first() else {
+        return Vec::new();
+    };
+
+    let token_asset = |kind: AssetKind, token_id: Option<U256>| Asset::Token {
+        kind,
+        address: token.clone(),
+        token_id,
+    };
+
+    let wrapped_native = is_wrapped_native(chain_id, &token);
+
+    match *topic0 {
+        TRANSFER_TOPIC if topics.len() == 3 && data.len() >= 32 => vec![AssetTransfer {
+            asset: token_asset(AssetKind::Erc20, None),
+            from: topic_address(&topics[1]),
+            to: topic_address(&topics[2]),
+            amount: U256::from_be_slice(&data[..32]),
+        }],
+        TRANSFER_TOPIC if topics.len() == 4 => vec![AssetTransfer {
+            asset: token_asset(AssetKind::Erc721, Some(U256::from_be_bytes(topics[3].0))),
+            from: topic_address(&topics[1]),
+            to: topic_address(&topics[2]),
+            amount: U256::from(1),
+        }],
+        TRANSFER_SINGLE_TOPIC if topics.len() == 4 && data.len() >= 64 => vec![AssetTransfer {
+            asset: token_asset(AssetKind::Erc1155, Some(U256::from_be_slice(&data[..32]))),
+            from: topic_address(&topics[2]),
+            to: topic_address(&topics[3]),
+            amount: U256::from_be_slice(&data[32..64]),
+        }],
+        TRANSFER_BATCH_TOPIC if topics.len() == 4 => {
+            let from = topic_address(&topics[2]);
+            let to = topic_address(&topics[3]);
+            decode_batch(data)
+                .into_iter()
+                .map(|(id, amount)| AssetTransfer {
+                    asset: token_asset(AssetKind::Erc1155, Some(id)),
+                    from: from.clone(),
+                    to: to.clone(),
+                    amount,
+                })
+                .collect()
+        }
+        // WETH wrap/unwrap mints and burns the wrapped token
+        DEPOSIT_TOPIC if wrapped_native && topics.len() == 2 && data.len() >= 32 => vec![AssetTransfer {
+            asset: token_asset(AssetKind::Erc20, None),
+            from: ZERO_ADDRESS.to_string(),
+            to: topic_address(&topics[1]),
+            amount: U256::from_be_slice(&data[..32]),
+        }],
+        WITHDRAWAL_TOPIC if wrapped_native && topics.len() == 2 && data.len() >= 32 => vec![AssetTransfer {
+            asset: token_asset(AssetKind::Erc20, None),
+            from: topic_address(&topics[1]),
+            to: ZERO_ADDRESS.to_string(),
+            amount: U256::from_be_slice(&data[..32]),
+        }],
+        _ => Vec::new(),
+    }
+}
+
+/// Format a raw amount with the given decimals, trimming trailing zeros
+pub fn format_units(value: U256, decimals: Option<u8>) -> String {
+    let decimals = match decimals {
+        Some(decimals) if decimals > 0 => decimals,
+        _ => return value.to_string(),
+    };
+
+    let divisor = U256::from(10u64).pow(U256::from(decimals));
+    let whole = value / divisor;
+    let frac = value % divisor;
+    if frac.is_zero() {
+        return whole.to_string();
+    }
+    let frac_str = format!("{:0>width$}", frac, width = decimals as usize);
+    format!("{}.{}", whole, frac_str.trim_end_matches('0'))
+}
+
+fn topic_address(topic: &B256) -> String {
+    format!("{:#x}", Address::from_word(*topic))
+}
+
+fn decode_batch(data: &[u8]) -> Vec<(U256, U256)> {
+    let ty = DynSolType::Tuple(vec![
+        DynSolType::Array(Box::new(DynSolType::Uint(256))),
+        DynSolType::Array(Box::new(DynSolType::Uint(256))),
+    ]);
+    let Ok(DynSolValue::Tuple(values)) = ty.abi_decode_params(data) else {
+        return Vec::new();
+    };
+    let uints = |value: &DynSolValue| match value {
+        DynSolValue::Array(items) => items
+            .iter()
+            .filter_map(|item| item.as_uint().map(|(value, _)| value))
+            .collect::<Vec<_>>(),
+        _ => Vec::new(),
+    };
+    match values.as_slice() {
+        [ids, amounts] => uints(ids).into_iter().zip(uints(amounts)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::keccak256;
+
+    const TOKEN: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+    const ALICE: &str = "0x1111111111111111111111111111111111111111";
+    const BOB: &str = "0x2222222222222222222222222222222222222222";
+
+    fn address_topic(address: &str) -> B256 {
+        address.parse::<Address>().unwrap().into_word()
+    }
+
+    fn word(value: u64) -> Vec<u8> {
+        B256::from(U256::from(value)).to_vec()
+    }
+
+    #[test]
+    fn test_topic_constants() {
+        assert_eq!(TRANSFER_TOPIC, keccak256("Transfer(address,address,uint256)"));
+        assert_eq!(
+            TRANSFER_SINGLE_TOPIC,
+            keccak256("TransferSingle(address,address,address,uint256,uint256)")
+        );
+        assert_eq!(
+            TRANSFER_BATCH_TOPIC,
+            keccak256("TransferBatch(address,address,address,uint256[],uint256[])")
+        );
+        assert_eq!(DEPOSIT_TOPIC, keccak256("Deposit(address,uint256)"));
+        assert_eq!(WITHDRAWAL_TOPIC, keccak256("Withdrawal(address,uint256)"));
+    }
+
+    #[test]
+    fn test_decode_erc20_and_721() {
+        let erc20 = decode_transfer_log(
+            1,
+            TOKEN,
+            &[TRANSFER_TOPIC, address_topic(ALICE), address_topic(BOB)],
+            &word(500),
+        );
+        assert_eq!(erc20.len(), 1);
+        assert_eq!(erc20[0].from, ALICE);
+        assert_eq!(erc20[0].amount, U256::from(500));
+        assert_eq!(erc20[0].asset.token_address(), Some(TOKEN.to_lowercase().as_str()));
+
+        let erc721 = decode_transfer_log(
+            1,
+            TOKEN,
+            &[
+                TRANSFER_TOPIC,
+                address_topic(ALICE),
+                address_topic(BOB),
+                B256::from(U256::from(42)),
+            ],
+            &[],
+        );
+        assert!(matches!(
+            erc721[0].asset,
+            Asset::Token { kind: AssetKind::Erc721, token_id: Some(id), .. } if id == U256::from(42)
+        ));
+    }
+
+    #[test]
+    fn test_decode_erc1155_batch() {
+        let data = DynSolValue::Tuple(vec![
+            DynSolValue::Array(vec![DynSolValue::Uint(U256::from(1), 256), DynSolValue::Uint(U256::from(2), 256)]),
+            DynSolValue::Array(vec![DynSolValue::Uint(U256::from(10), 256), DynSolValue::Uint(U256::from(20), 256)]),
+        ])
+        .abi_encode_params();
+        let transfers = decode_transfer_log(
+            1,
+            TOKEN,
+            &[
+                TRANSFER_BATCH_TOPIC,
+                address_topic(ALICE),
+                address_topic(ALICE),
+                address_topic(BOB),
+            ],
+            &data,
+        );
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[1].amount, U256::from(20));
+        assert_eq!(transfers[1].to, BOB);
+    }
+
+    #[test]
+    fn test_deposit_only_from_wrapped_native() {
+        let topics = [DEPOSIT_TOPIC, address_topic(ALICE)];
+        assert_eq!(decode_transfer_log(1, TOKEN, &topics, &word(5)).len(), 1);
+        // Same event from a staking contract, or WETH's address on another chain
+        assert!(decode_transfer_log(1, BOB, &topics, &word(5)).is_empty());
+        assert!(decode_transfer_log(10, TOKEN, &topics, &word(5)).is_empty());
+        assert!(decode_transfer_log(
+            1,
+            BOB,
+            &[WITHDRAWAL_TOPIC, address_topic(ALICE)],
+            &word(5)
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_net_changes() {
+        let mut summary = TransferSummary::default();
+        summary.transfers.extend(decode_transfer_log(
+            1,
+            TOKEN,
+            &[DEPOSIT_TOPIC, address_topic(ALICE)],
+            &word(1_000),
+        ));
+        summary.transfers.extend(decode_transfer_log(
+            1,
+            TOKEN,
+            &[TRANSFER_TOPIC, address_topic(ALICE), address_topic(BOB)],
+            &word(400),
+        ));
+        summary.transfers.push(AssetTransfer {
+            asset: Asset::Native,
+            from: ALICE.to_string(),
+            to: TOKEN.to_lowercase(),
+            amount: U256::from(1_000),
+        });
+
+        let changes = summary.net_changes();
+        let find = |address: &str, native: bool| {
+            changes
+                .iter()
+                .find(|c| c.address == address && (c.asset == Asset::Native) == native)
+                .map(AssetChange::net)
+        };
+        assert_eq!(find(ALICE, false), Some((U256::from(600), true)));
+        assert_eq!(find(ALICE, true), Some((U256::from(1_000), false)));
+        assert_eq!(find(BOB, false), Some((U256::from(400), true)));
+        assert_eq!(find(ZERO_ADDRESS, false), Some((U256::from(1_000), false)));
+    }
+
+    #[test]
+    fn test_format_units() {
+        assert_eq!(format_units(U256::from(1_500_000u64), Some(6)), "1.5");
+        assert_eq!(format_units(U256::from(42u64), None), "42");
+        assert_eq!(format_units(U256::from(10u64).pow(U256::from(18)), Some(18)), "1");
+    }
+}
@@ -0,0 +1,352 @@
+//! Prometheus exporter - poke's own `/metrics` endpoint for headless runs
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::domain::alerts::{AlertRule, AlertState};
+use crate::infrastructure::runtime::RuntimeEvent;
+
+/// The node counts as down when no status arrived for this long
+const STALE_AFTER: Duration = Duration::from_secs(10);
+
+/// Block hashes kept for reorg detection
+const MAX_RECENT_BLOCKS: usize = 128;
+
+/// What the worker reported so far, rendered on every scrape
+#[derive(Debug, Default)]
+pub struct Exporter {
+    endpoint: String,
+    node_kind: String,
+    last_status: Option<Instant>,
+    head_block: Option<u64>,
+    rtt_ms: Option<u64>,
+    peer_count: Option<u32>,
+    sync_progress: Option<f64>,
+    blocks_total: u64,
+    reorgs_total: u64,
+    errors_total: u64,
+    failovers_total: u64,
+    /// number -> (hash, parent hash)
+    recent: BTreeMap<u64, (String, String)>,
+    /// rule -> (active, times fired)
+    alerts: BTreeMap<String, (bool, u64)>,
+    /// lowercase address -> hits
+    watch_hits: BTreeMap<String, u64>,
+}
+
+impl Exporter {
+    pub fn new(rules: &[AlertRule], watched: &BTreeSet<String>) -> Self {
+        Self {
+            alerts: rules
+                .iter()
+                .map(|rule| (rule.name.clone(), (false, 0)))
+                .collect(),
+            watch_hits: watched
+                .iter()
+                .map(|address| (address.to_lowercase(), 0))
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn apply(&mut self, event: &RuntimeEvent) {
+        match event {
+            RuntimeEvent::Connected {
+                endpoint,
+                node_kind,
+                ..
+            } => {
+                self.endpoint = endpoint.clone();
+                self.node_kind = node_kind.clone();
+                self.recent.clear();
+            }
+            RuntimeEvent::Status {
+                rtt_ms,
+                peer_count,
+                sync_progress,
+            } => self.observe_status(*rtt_ms, *peer_count, *sync_progress),
+            RuntimeEvent::NewBlock { block, txs } => {
+                self.observe_block(block.number, &block.hash, &block.parent_hash);
+                for tx in txs {
+                    self.observe_tx(&tx.from, &tx.to);
+                }
+            }
+            RuntimeEvent::Alert { event } => {
+                let entry = self.alerts.entry(event.rule.clone()).or_default();
+                entry.0 = event.state == AlertState::Fired;
+                if entry.0 {
+                    entry.1 += 1;
+                }
+            }
+            RuntimeEvent::Error { .. } => self.errors_total += 1,
+            RuntimeEvent::Failover { .. } => self.failovers_total += 1,
+            _ => {}
+        }
+    }
+
+    fn observe_status(
+        &mut self,
+        rtt_ms: Option<u64>,
+        peer_count: Option<u32>,
+        sync_progress: Option<f64>,
+    ) {
+        self.last_status = Some(Instant::now());
+        self.rtt_ms = rtt_ms.or(self.rtt_ms);
+        self.peer_count = peer_count.or(self.peer_count);
+        // None means the node is not syncing
+        self.sync_progress = sync_progress;
+    }
+
+    /// Count a reorg when a height or its parent no longer matches what was seen
+    fn observe_block(&mut self, number: u64, hash: &str, parent_hash: &str) {
+        self.blocks_total += 1;
+        let replaced = self
+            .recent
+            .get(&number)
+            .is_some_and(|(seen, _)| seen != hash);
+        let orphaned_parent = number
+            .checked_sub(1)
+            .and_then(|parent| self.recent.get(&parent))
+            .is_some_and(|(seen, _)| seen != parent_hash);
+        if replaced || orphaned_parent {
+            self.reorgs_total += 1;
+            // Heights above the new block belong to the abandoned branch
+            self.recent.split_off(&number);
+        }
+
+        self.recent
+            .insert(number, (hash.to_string(), parent_hash.to_string()));
+        while self.recent.len() > MAX_RECENT_BLOCKS {
+            self.recent.pop_first();
+        }
+        self.head_block = Some(self.head_block.map_or(number, |head| {
+            if replaced || orphaned_parent {
+                number
+            } else {
+                head.max(number)
+            }
+        }));
+    }
+
+    fn observe_tx(&mut self, from: &str, to: &str) {
+        let from = from.to_lowercase();
+        let to = to.to_lowercase();
+        if let Some(hits) = self.watch_hits.get_mut(&from) {
+            *hits += 1;
+        }
+        if to != from {
+            if let Some(hits) = self.watch_hits.get_mut(&to) {
+                *hits += 1;
+            }
+        }
+    }
+
+    /// Prometheus text exposition of the current state
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let up = self
+            .last_status
+            .is_some_and(|at| at.elapsed() < STALE_AFTER);
+        let node = [
+            ("endpoint", self.endpoint.as_str()),
+            ("client", self.node_kind.as_str()),
+        ];
+
+        gauge(&mut out, "poke_up", "Whether the node answered a status check recently");
+        sample(&mut out, "poke_up", &node, if up { 1.0 } else { 0.0 });
+
+        if let Some(head) = self.head_block {
+            gauge(&mut out, "poke_head_block", "Newest block number seen");
+            sample(&mut out, "poke_head_block", &[], head as f64);
+        }
+        if let Some(rtt) = self.rtt_ms {
+            gauge(&mut out, "poke_rpc_rtt_seconds", "Round trip of the last status check");
+            sample(&mut out, "poke_rpc_rtt_seconds", &[], rtt as f64 / 1000.0);
+        }
+        if let Some(peers) = self.peer_count {
+            gauge(&mut out, "poke_peers", "Connected peers reported by the node");
+            sample(&mut out, "poke_peers", &[], peers as f64);
+        }
+
+        gauge(&mut out, "poke_syncing", "Whether the node reports it is syncing");
+        sample(&mut out, "poke_syncing", &[], self.sync_progress.map_or(0.0, |_| 1.0));
+        gauge(&mut out, "poke_sync_progress", "Sync progress from 0 to 1");
+        sample(&mut out, "poke_sync_progress", &[], self.sync_progress.unwrap_or(1.0));
+
+        counter(&mut out, "poke_blocks_total", "Blocks received from the node");
+        sample(&mut out, "poke_blocks_total", &[], self.blocks_total as f64);
+        counter(&mut out, "poke_reorgs_total", "Reorgs detected from replaced block hashes");
+        sample(&mut out, "poke_reorgs_total", &[], self.reorgs_total as f64);
+        counter(&mut out, "poke_errors_total", "Errors reported by the worker");
+        sample(&mut out, "poke_errors_total", &[], self.errors_total as f64);
+        counter(&mut out, "poke_failovers_total", "Endpoint switches made by the worker");
+        sample(&mut out, "poke_failovers_total", &[], self.failovers_total as f64);
+
+        if !self.alerts.is_empty() {
+            gauge(&mut out, "poke_alert_active", "Whether an alert rule is firing");
+            for (rule, (active, _)) in &self.alerts {
+                let value = if *active { 1.0 } else { 0.0 };
+                sample(&mut out, "poke_alert_active", &[("rule", rule)], value);
+            }
+            counter(&mut out, "poke_alert_fired_total", "Times an alert rule fired");
+            for (rule, (_, fired)) in &self.alerts {
+                sample(&mut out, "poke_alert_fired_total", &[("rule", rule)], *fired as f64);
+            }
+        }
+
+        if !self.watch_hits.is_empty() {
+            counter(
+                &mut out,
+                "poke_watched_hits_total",
+                "Transactions touching a watched address",
+            );
+            for (address, hits) in &self.watch_hits {
+                sample(
+                    &mut out,
+                    "poke_watched_hits_total",
+                    &[("address", address)],
+                    *hits as f64,
+                );
+            }
+        }
+        out
+    }
+}
+
+fn gauge(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} gauge");
+}
+
+fn counter(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} counter");
+}
+
+fn sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: f64) {
+    out.push_str(name);
+    if !labels.is_empty() {
+        let labels: Vec<String> = labels
+            .iter()
+            .map(|(key, value)| format!("{key}=\"{}\"", escape_label(value)))
+            .collect();
+        let _ = write!(out, "{{{}}}", labels.join(","));
+    }
+    let _ = writeln!(out, " {value}");
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Answer `GET /metrics` with the exporter state, one connection at a time.
+/// The read timeout in `respond` bounds how long a stalled client can hold
+/// up the next scrape.
+pub fn serve(listener: TcpListener, exporter: Arc<Mutex<Exporter>>) {
+    for stream in listener.incoming().flatten() {
+        let _ = respond(stream, &exporter);
+    }
+}
+
+fn respond(mut stream: TcpStream, exporter: &Mutex<Exporter>) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+
+    let (status, body) = if path == "/metrics" || path.starts_with("/metrics?") {
+        let body = exporter
+            .lock()
+            .map(|exporter| exporter.render())
+            .unwrap_or_default();
+        ("200 OK", body)
+    } else {
+        ("404 Not Found", "see /metrics\n".to_string())
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\ncontent-type: text/plain; version=0.0.4\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn exporter() -> Exporter {
+        let rules = AlertRule::defaults();
+        let watched = BTreeSet::from(["0xAbC0000000000000000000000000000000000001".to_string()]);
+        Exporter::new(&rules, &watched)
+    }
+
+    #[test]
+    fn test_reorg_detection() {
+        let mut exporter = exporter();
+        exporter.observe_block(10, "0xa10", "0xa09");
+        exporter.observe_block(11, "0xa11", "0xa10");
+        assert_eq!(exporter.reorgs_total, 0);
+
+        // Same height, different hash
+        exporter.observe_block(11, "0xb11", "0xa10");
+        assert_eq!(exporter.reorgs_total, 1);
+        // Child of the replaced block is fine
+        exporter.observe_block(12, "0xb12", "0xb11");
+        assert_eq!(exporter.reorgs_total, 1);
+        // Parent hash that does not match the block we saw
+        exporter.observe_block(13, "0xc13", "0xc12");
+        assert_eq!(exporter.reorgs_total, 2);
+        assert_eq!(exporter.head_block, Some(13));
+    }
+
+    #[test]
+    fn test_render() {
+        let mut exporter = exporter();
+        exporter.observe_status(Some(42), Some(7), None);
+        exporter.observe_block(100, "0x1", "0x0");
+        exporter.observe_tx("0xabc0000000000000000000000000000000000001", "0xdef");
+        exporter.alerts.insert("rpc_latency_high".to_string(), (true, 2));
+        exporter.node_kind = "Geth \"v1\"".to_string();
+
+        let text = exporter.render();
+        assert!(text.contains("poke_up{endpoint=\"\",client=\"Geth \\\"v1\\\"\"} 1\n"));
+        assert!(text.contains("# TYPE poke_head_block gauge\npoke_head_block 100\n"));
+        assert!(text.contains("poke_rpc_rtt_seconds 0.042\n"));
+        assert!(text.contains("poke_peers 7\n"));
+        assert!(text.contains("poke_syncing 0\n"));
+        assert!(text.contains("poke_alert_active{rule=\"peer_count_low\"} 0\n"));
+        assert!(text.contains("poke_alert_active{rule=\"rpc_latency_high\"} 1\n"));
+        assert!(text.contains("poke_alert_fired_total{rule=\"rpc_latency_high\"} 2\n"));
+        assert!(text.contains(
+            "poke_watched_hits_total{address=\"0xabc0000000000000000000000000000000000001\"} 1\n"
+        ));
+    }
+
+    #[test]
+    fn test_serve_metrics() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let exporter = Arc::new(Mutex::new(exporter()));
+        exporter.lock().unwrap().observe_block(5, "0x5", "0x4");
+        std::thread::spawn(move || serve(listener, exporter));
+
+        let get = |path: &str| {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            write!(stream, "GET {path} HTTP/1.1\r\nhost: poke\r\n\r\n").unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        let response = get("/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("poke_head_block 5\n"));
+        assert!(get("/").starts_with("HTTP/1.1 404"));
+    }
+}
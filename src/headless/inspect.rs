@@ -0,0 +1,584 @@
+//! One-shot inspection commands - trace, tx, block and decode printed to stdout
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+
+use alloy::primitives::B256;
+use anyhow::{anyhow, bail, Context, Result};
+use futures::stream::{self, StreamExt};
+
+use crate::app::{self, CallStatus, TxStatus};
+use crate::domain::abi::{AbiRegistry, NestedCall};
+use crate::infrastructure::abi::nested::{decode_nested_call, function_from_signature, render_call_tree};
+use crate::infrastructure::abi::AbiResolver;
+use crate::infrastructure::ethereum::{create_provider, EthereumProvider, ProviderConfig};
+use crate::infrastructure::runtime::{block_info, convert_raw_tx};
+use crate::modules::export::{csv_export, json_export};
+
+/// Receipts fetched at once for `poke block`
+const RECEIPT_CONCURRENCY: usize = 8;
+
+/// Resolution passes for calls nested inside already-resolved calls
+const MAX_RESOLVE_PASSES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Tree,
+    Json,
+    Csv,
+}
+
+impl OutputFormat {
+    pub fn from_flags(json: bool, csv: bool) -> Self {
+        if json {
+            OutputFormat::Json
+        } else if csv {
+            OutputFormat::Csv
+        } else {
+            OutputFormat::Tree
+        }
+    }
+}
+
+/// Provider plus the ABI context the TUI decodes with
+pub struct Inspector {
+    provider: Option<Box<dyn EthereumProvider>>,
+    resolver: AbiResolver,
+    registry: AbiRegistry,
+}
+
+impl Inspector {
+    pub fn new(resolver: AbiResolver, registry: AbiRegistry) -> Self {
+        Self {
+            provider: None,
+            resolver,
+            registry,
+        }
+    }
+
+    pub async fn connect(mut self, endpoint: ProviderConfig) -> Result<Self> {
+        let display = endpoint.display();
+        let provider = create_provider(endpoint)
+            .await
+            .with_context(|| format!("connect to {display}"))?;
+        self.provider = Some(provider);
+        Ok(self)
+    }
+
+    fn provider(&self) -> Result<&dyn EthereumProvider> {
+        self.provider
+            .as_deref()
+            .ok_or_else(|| anyhow!("no RPC endpoint connected"))
+    }
+
+    /// Add signatures for selectors the local ABIs don't know (signature DB, then remote)
+    async fn resolve_selectors(&mut self, selectors: impl IntoIterator<Item = String>) {
+        for selector in selectors {
+            if self.registry.lookup_hex(&selector).is_some() {
+                continue;
+            }
+            let Some(bytes) = parse_selector(&selector) else {
+                continue;
+            };
+            if let Ok(Some(resolved)) = self.resolver.lookup_selector(bytes).await {
+                if let Some(function) = function_from_signature(&resolved.signature) {
+                    self.registry.insert(function);
+                }
+            }
+        }
+    }
+
+    pub async fn trace(&mut self, hash: &str) -> Result<Vec<app::TraceFrame>> {
+        let hash = parse_hash(hash)?;
+        let frames = self.provider()?.debug_trace_transaction(hash).await?;
+        self.resolve_selectors(frames.iter().filter_map(|frame| frame.selector.clone()))
+            .await;
+
+        let mut frames: Vec<app::TraceFrame> =
+            frames.into_iter().map(app::TraceFrame::from).collect();
+        app::decorate_trace(&mut frames, Some(&self.registry), &BTreeMap::new());
+        Ok(frames)
+    }
+
+    pub async fn tx(&mut self, hash: &str) -> Result<app::TxInfo> {
+        let parsed = parse_hash(hash)?;
+        let provider = self.provider()?;
+        let receipt = provider
+            .get_receipt(parsed)
+            .await?
+            .ok_or_else(|| anyhow!("transaction {hash} not found or still pending"))?;
+        let number = receipt
+            .block_number
+            .ok_or_else(|| anyhow!("receipt for {hash} has no block number"))?;
+        let block = provider
+            .get_block(number)
+            .await?
+            .ok_or_else(|| anyhow!("block {number} not found"))?;
+        let raw = block
+            .transactions
+            .iter()
+            .find(|tx| tx.hash.eq_ignore_ascii_case(&format!("{parsed:#x}")))
+            .ok_or_else(|| anyhow!("transaction {hash} missing from block {number}"))?;
+
+        let mut txs = vec![app::TxInfo::from(convert_raw_tx(raw, Some(&receipt), number))];
+        self.decorate_txs(&mut txs).await;
+        Ok(txs.remove(0))
+    }
+
+    pub async fn block(&mut self, id: &str) -> Result<(app::BlockInfo, Vec<app::TxInfo>)> {
+        let provider = self.provider()?;
+        let number = match id.trim() {
+            "latest" | "" => provider.block_number().await?,
+            other => parse_block_number(other)?,
+        };
+        let block = provider
+            .get_block(number)
+            .await?
+            .ok_or_else(|| anyhow!("block {number} not found"))?;
+
+        let receipts: Vec<_> = stream::iter(&block.transactions)
+            .map(|tx| async move {
+                match tx.hash.parse::<B256>() {
+                    Ok(hash) => provider.get_receipt(hash).await.ok().flatten(),
+                    Err(_) => None,
+                }
+            })
+            .buffered(RECEIPT_CONCURRENCY)
+            .collect()
+            .await;
+        let mut txs: Vec<app::TxInfo> = block
+            .transactions
+            .iter()
+            .zip(&receipts)
+            .map(|(tx, receipt)| app::TxInfo::from(convert_raw_tx(tx, receipt.as_ref(), number)))
+            .collect();
+        // Without a chain id the blob base fee is left out rather than guessed
+        let chain_id = provider.chain_id().await.unwrap_or_default();
+        self.decorate_txs(&mut txs).await;
+        Ok((block_info(&block, chain_id).into(), txs))
+    }
+
+    async fn decorate_txs(&mut self, txs: &mut [app::TxInfo]) {
+        self.resolve_selectors(
+            txs.iter()
+                .filter(|tx| tx.input.len() > 10)
+                .map(|tx| tx.selector.clone()),
+        )
+        .await;
+        app::decorate_txs(txs, Some(&self.registry), &BTreeMap::new());
+    }
+
+    /// Decode calldata, optionally with a signature for the outer call
+    pub async fn decode(&mut self, calldata: &str, signature: Option<&str>) -> Result<NestedCall> {
+        let hex_data = calldata.trim();
+        let hex_data = hex_data.strip_prefix("0x").unwrap_or(hex_data);
+        let data = hex::decode(hex_data).context("calldata is not valid hex")?;
+        let Some(selector) = data.get(..4).map(|sel| format!("0x{}", hex::encode(sel))) else {
+            bail!("calldata too short (need at least 4 bytes for selector)");
+        };
+
+        if let Some(signature) = signature {
+            let function = function_from_signature(signature)
+                .ok_or_else(|| anyhow!("invalid function signature: {signature}"))?;
+            if function.selector_hex() != selector {
+                bail!(
+                    "{} has selector {}, calldata starts with {}",
+                    function.signature,
+                    function.selector_hex(),
+                    selector
+                );
+            }
+            self.registry.insert(function);
+        }
+
+        self.resolve_selectors([selector.clone()]).await;
+        if self.registry.lookup_hex(&selector).is_none() {
+            bail!("unknown selector {selector}; pass its signature with --sig");
+        }
+
+        let mut pending = BTreeSet::new();
+        for _ in 0..MAX_RESOLVE_PASSES {
+            self.resolve_selectors(std::mem::take(&mut pending)).await;
+            let lookup = |selector: &str| self.registry.lookup_hex(selector).cloned();
+            let call = decode_nested_call(&data, &lookup)
+                .ok_or_else(|| anyhow!("could not decode calldata"))?;
+            unresolved_selectors(std::slice::from_ref(&call), &self.registry, &mut pending);
+            if pending.is_empty() {
+                return Ok(call);
+            }
+        }
+        let lookup = |selector: &str| self.registry.lookup_hex(selector).cloned();
+        decode_nested_call(&data, &lookup).ok_or_else(|| anyhow!("could not decode calldata"))
+    }
+}
+
+fn unresolved_selectors(calls: &[NestedCall], registry: &AbiRegistry, out: &mut BTreeSet<String>) {
+    for call in calls {
+        if let Some(selector) = call.selector.as_ref() {
+            if registry.lookup_hex(selector).is_none() {
+                out.insert(selector.clone());
+            }
+        }
+        for arg in &call.args {
+            unresolved_selectors(&arg.calls, registry, out);
+        }
+    }
+}
+
+fn parse_hash(hash: &str) -> Result<B256> {
+    hash.trim()
+        .parse::<B256>()
+        .with_context(|| format!("invalid transaction hash: {hash}"))
+}
+
+fn parse_selector(selector: &str) -> Option<[u8; 4]> {
+    let bytes = hex::decode(selector.strip_prefix("0x")?).ok()?;
+    bytes.try_into().ok()
+}
+
+fn parse_block_number(id: &str) -> Result<u64> {
+    match id.strip_prefix("0x") {
+        Some(hex_number) => u64::from_str_radix(hex_number, 16),
+        None => id.replace('_', "").parse(),
+    }
+    .with_context(|| format!("invalid block number: {id} (use a number or \"latest\")"))
+}
+
+fn export_error(err: Box<dyn std::error::Error>) -> anyhow::Error {
+    anyhow!("write output: {err}")
+}
+
+pub fn print_trace(frames: &[app::TraceFrame], format: OutputFormat) -> Result<()> {
+    let mut out = io::stdout().lock();
+    match format {
+        OutputFormat::Json => {
+            json_export::write_trace_to(&mut out, frames).map_err(export_error)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Csv => {
+            csv_export::write_trace_to(&mut out, frames).map_err(export_error)?;
+        }
+        OutputFormat::Tree => {
+            for line in trace_tree(frames) {
+                writeln!(out, "{line}")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_tx(tx: &app::TxInfo, format: OutputFormat) -> Result<()> {
+    let mut out = io::stdout().lock();
+    match format {
+        OutputFormat::Json => {
+            json_export::write_transaction_to(&mut out, tx).map_err(export_error)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Csv => {
+            csv_export::write_transactions_to(&mut out, std::slice::from_ref(tx))
+                .map_err(export_error)?;
+        }
+        OutputFormat::Tree => {
+            for line in tx_tree(tx) {
+                writeln!(out, "{line}")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_block(block: &app::BlockInfo, txs: &[app::TxInfo], format: OutputFormat) -> Result<()> {
+    let mut out = io::stdout().lock();
+    match format {
+        OutputFormat::Json => {
+            json_export::write_block_to(&mut out, block, txs).map_err(export_error)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Csv => {
+            csv_export::write_transactions_to(&mut out, txs).map_err(export_error)?;
+        }
+        OutputFormat::Tree => {
+            writeln!(
+                out,
+                "Block #{}  txs {}  gas used {}  base fee {} gwei  miner {}",
+                block.number, block.tx_count, block.gas_used, block.base_fee, block.miner
+            )?;
+            for tx in txs {
+                writeln!(
+                    out,
+                    "  {}  {} → {}  {}  {} ETH{}",
+                    tx.hash,
+                    tx.from,
+                    tx.to,
+                    tx.signature.as_deref().unwrap_or(&tx.method),
+                    tx.value,
+                    status_suffix(tx.status)
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_decoded(call: &NestedCall, format: OutputFormat) -> Result<()> {
+    let mut out = io::stdout().lock();
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, call)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Csv => {
+            let mut wtr = csv::Writer::from_writer(&mut out);
+            wtr.write_record(["depth", "call", "target", "arg", "kind", "value"])?;
+            let mut rows = Vec::new();
+            decoded_rows(std::slice::from_ref(call), 0, &mut rows);
+            for row in rows {
+                wtr.write_record(row)?;
+            }
+            wtr.flush()?;
+        }
+        OutputFormat::Tree => {
+            if let Some(signature) = call.signature.as_ref() {
+                writeln!(out, "{signature}")?;
+            }
+            let mut lines = Vec::new();
+            render_call_tree(std::slice::from_ref(call), 0, &mut lines);
+            for line in lines {
+                writeln!(out, "{line}")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn decoded_rows(calls: &[NestedCall], depth: usize, rows: &mut Vec<[String; 6]>) {
+    for call in calls {
+        let target = call.target.clone().unwrap_or_default();
+        let name = call.signature.clone().unwrap_or_else(|| call.name.clone());
+        if call.args.is_empty() {
+            rows.push([
+                depth.to_string(),
+                name.clone(),
+                target.clone(),
+                String::new(),
+                String::new(),
+                String::new(),
+            ]);
+        }
+        for arg in &call.args {
+            rows.push([
+                depth.to_string(),
+                name.clone(),
+                target.clone(),
+                arg.name.clone(),
+                arg.kind.clone(),
+                arg.value.clone(),
+            ]);
+            decoded_rows(&arg.calls, depth + 1, rows);
+        }
+    }
+}
+
+fn status_suffix(status: TxStatus) -> &'static str {
+    match status {
+        TxStatus::Success => "",
+        TxStatus::Revert => "  [revert]",
+        TxStatus::Unknown => "  [unknown]",
+    }
+}
+
+fn arg_lines(args: &[app::DecodedArg], indent: &str, depth: usize, out: &mut Vec<String>) {
+    for arg in args {
+        out.push(format!("{indent}{} ({}) = {}", arg.name, arg.kind, arg.value));
+        render_call_tree(&arg.nested, depth, out);
+    }
+}
+
+fn trace_tree(frames: &[app::TraceFrame]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for frame in frames {
+        let indent = "  ".repeat(frame.depth);
+        let target = frame
+            .signature
+            .clone()
+            .or_else(|| frame.selector.clone())
+            .unwrap_or_default();
+        let mut line = format!("{indent}{} {} → {} {target}", frame.call, frame.from, frame.to);
+        if frame.value > 0.0 {
+            line.push_str(&format!("  value {} ETH", frame.value));
+        }
+        line.push_str(&format!("  gas {}", frame.gas_used));
+        if frame.status == CallStatus::Revert {
+            line.push_str(&format!("  REVERT {}", frame.note));
+        }
+        lines.push(line);
+
+        let arg_indent = format!("{indent}    ");
+        if let Some(args) = frame.decoded_args.as_ref() {
+            arg_lines(args, &arg_indent, frame.depth + 2, &mut lines);
+        }
+        if let Some(output) = frame.decoded_output.as_ref() {
+            for value in output {
+                lines.push(format!("{arg_indent}→ {} ({}) = {}", value.name, value.kind, value.value));
+            }
+        }
+        if let Some(err) = frame.decode_error.as_ref() {
+            lines.push(format!("{arg_indent}! {err}"));
+        }
+    }
+    lines
+}
+
+fn tx_tree(tx: &app::TxInfo) -> Vec<String> {
+    let status = match tx.status {
+        TxStatus::Success => "success",
+        TxStatus::Revert => "revert",
+        TxStatus::Unknown => "unknown",
+    };
+    let mut lines = vec![
+        format!("Transaction {}", tx.hash),
+        format!("  block     {}", tx.block_number),
+        format!("  from      {}", tx.from),
+        format!("  to        {}", tx.to),
+        format!("  value     {} ETH", tx.value),
+        format!("  gas used  {}", tx.gas_used),
+        format!("  status    {status}"),
+        format!("  method    {}", tx.signature.as_deref().unwrap_or(&tx.method)),
+    ];
+    if let Some(args) = tx.decoded_args.as_ref() {
+        lines.push("  args".to_string());
+        arg_lines(args, "    ", 2, &mut lines);
+    }
+    if let Some(err) = tx.decode_error.as_ref() {
+        lines.push(format!("  decode error  {err}"));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSFER: &str = "0xa9059cbb000000000000000000000000000000000000000000000000000000000000dead0000000000000000000000000000000000000000000000000000000000000064";
+
+    #[tokio::test]
+    async fn test_decode_with_signature() {
+        let mut inspector = Inspector::new(AbiResolver::new().offline(true), AbiRegistry::new());
+        let call = inspector
+            .decode(TRANSFER, Some("transfer(address,uint256)"))
+            .await
+            .unwrap();
+        assert_eq!(call.name, "transfer");
+        assert_eq!(call.args.len(), 2);
+        assert_eq!(call.args[1].value, "100");
+
+        let mut rows = Vec::new();
+        decoded_rows(std::slice::from_ref(&call), 0, &mut rows);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0][1], "transfer(address,uint256)");
+
+        let err = inspector
+            .decode(TRANSFER, Some("approve(address,uint256)"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("0x095ea7b3"));
+    }
+
+    #[tokio::test]
+    async fn test_tx_accepts_hash_without_prefix() {
+        use crate::infrastructure::ethereum::stub::StubProvider;
+        use crate::infrastructure::ethereum::{RawBlock, RawTransaction};
+        use alloy::primitives::{Bytes, U256};
+
+        let hash = format!("0x{}", "ab".repeat(32));
+        let receipt = serde_json::from_value(serde_json::json!({
+            "transactionHash": hash,
+            "transactionIndex": "0x0",
+            "blockHash": format!("0x{}", "11".repeat(32)),
+            "blockNumber": "0x7",
+            "from": "0x1111111111111111111111111111111111111111",
+            "to": "0x2222222222222222222222222222222222222222",
+            "cumulativeGasUsed": "0x5208",
+            "gasUsed": "0x5208",
+            "effectiveGasPrice": "0x1",
+            "contractAddress": null,
+            "logs": [],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "type": "0x2",
+            "status": "0x1"
+        }))
+        .unwrap();
+        let raw = RawTransaction {
+            hash: hash.clone(),
+            from: "0x1111111111111111111111111111111111111111".to_string(),
+            to: Some("0x2222222222222222222222222222222222222222".to_string()),
+            value: U256::ZERO,
+            input: Bytes::new(),
+            gas: 21_000,
+            nonce: 0,
+            tx_type: Some(2),
+            blob_versioned_hashes: Vec::new(),
+            max_fee_per_blob_gas: None,
+            authorization_list: Vec::new(),
+            deposit: None,
+        };
+        let mut stub = StubProvider::named("stub");
+        stub.receipts.insert(hash.parse().unwrap(), receipt);
+        stub.blocks.insert(
+            7,
+            RawBlock {
+                number: 7,
+                hash: format!("0x{}", "11".repeat(32)),
+                parent_hash: format!("0x{}", "10".repeat(32)),
+                timestamp: 0,
+                gas_used: 21_000,
+                gas_limit: 30_000_000,
+                base_fee_per_gas: Some(1),
+                blob_gas_used: None,
+                excess_blob_gas: None,
+                miner: "0x0000000000000000000000000000000000000000".to_string(),
+                transactions: vec![raw],
+            },
+        );
+
+        let mut inspector = Inspector::new(AbiResolver::new().offline(true), AbiRegistry::new());
+        inspector.provider = Some(Box::new(stub));
+        let tx = inspector.tx(&format!("  {} ", &hash[2..])).await.unwrap();
+        assert_eq!(tx.hash, hash);
+        assert_eq!(tx.block_number, 7);
+    }
+
+    #[test]
+    fn test_parse_block_number() {
+        assert_eq!(parse_block_number("0x10").unwrap(), 16);
+        assert_eq!(parse_block_number("19_000_000").unwrap(), 19_000_000);
+        assert!(parse_block_number("safe").is_err());
+        assert_eq!(OutputFormat::from_flags(false, true), OutputFormat::Csv);
+    }
+
+    #[test]
+    fn test_trace_tree() {
+        let frame = |depth: usize, status: CallStatus| app::TraceFrame {
+            depth,
+            call: "CALL".to_string(),
+            from: "0xa".to_string(),
+            to: "0xb".to_string(),
+            value: 0.0,
+            gas_used: 21_000,
+            status,
+            note: "revert: nope".to_string(),
+            collapsed: false,
+            input: None,
+            output: None,
+            selector: Some("0xa9059cbb".to_string()),
+            method: None,
+            signature: None,
+            decoded_args: None,
+            decoded_output: None,
+            decode_error: None,
+        };
+        let lines = trace_tree(&[frame(0, CallStatus::Ok), frame(1, CallStatus::Revert)]);
+        assert_eq!(lines[0], "CALL 0xa → 0xb 0xa9059cbb  gas 21000");
+        assert_eq!(lines[1], "  CALL 0xa → 0xb 0xa9059cbb  gas 21000  REVERT revert: nope");
+    }
+}
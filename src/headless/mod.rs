@@ -0,0 +1,198 @@
+//! Headless modes - run the worker or one-shot commands without the TUI
+
+mod exporter;
+mod inspect;
+mod watch;
+
+use std::collections::BTreeSet;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+
+use crate::domain::abi::AbiRegistry;
+use crate::domain::alerts::AlertRule;
+use crate::infrastructure::abi::AbiResolver;
+use crate::infrastructure::ethereum::ProviderConfig;
+use crate::infrastructure::runtime::{RuntimeBridge, RuntimeCommand};
+
+use exporter::Exporter;
+use inspect::{Inspector, OutputFormat};
+use watch::{EventFormatter, EventSink, RotatingFile};
+
+/// Non-interactive subcommands; output goes to stdout
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    #[command(flatten)]
+    Inspect(InspectCommand),
+    /// Follow the chain and print NDJSON for blocks, watched txs and alerts
+    Watch(WatchArgs),
+}
+
+/// One-shot commands answered from a single RPC endpoint
+#[derive(Debug, Subcommand)]
+pub enum InspectCommand {
+    /// Print the call trace of a transaction (debug_traceTransaction)
+    Trace {
+        /// Transaction hash
+        hash: String,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Print a transaction with its receipt status and decoded calldata
+    Tx {
+        /// Transaction hash
+        hash: String,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Print a block and its decoded transactions
+    Block {
+        /// Block number (decimal or 0x hex) or "latest"
+        #[arg(default_value = "latest")]
+        block: String,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Decode calldata, unwrapping multicall/Safe/router payloads
+    Decode {
+        /// Calldata hex, selector included
+        calldata: String,
+        /// Function signature to use instead of a lookup, e.g. "transfer(address,uint256)"
+        #[arg(long)]
+        sig: Option<String>,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+}
+
+#[derive(Debug, clap::Args)]
+pub struct WatchArgs {
+    /// Address or contract to watch (repeatable), on top of the label DB watch list
+    #[arg(long = "address", value_name = "ADDRESS")]
+    pub addresses: Vec<String>,
+    /// Append events to FILE instead of stdout
+    #[arg(long, value_name = "FILE")]
+    output: Option<PathBuf>,
+    /// Rotate the output file once it reaches this many MiB
+    #[arg(long, default_value_t = 100)]
+    max_size_mb: u64,
+    /// Rotated files to keep (FILE.1, FILE.2, …)
+    #[arg(long, default_value_t = 5)]
+    keep: usize,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct OutputArgs {
+    /// Print JSON instead of a tree
+    #[arg(long, conflicts_with = "csv")]
+    json: bool,
+    /// Print CSV instead of a tree
+    #[arg(long)]
+    csv: bool,
+}
+
+impl OutputArgs {
+    fn format(&self) -> OutputFormat {
+        OutputFormat::from_flags(self.json, self.csv)
+    }
+}
+
+/// Run a one-shot command against `endpoint`, decoding with the local ABIs and resolver
+pub fn run(
+    command: InspectCommand,
+    endpoint: ProviderConfig,
+    resolver: AbiResolver,
+    registry: AbiRegistry,
+) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async move {
+        let inspector = Inspector::new(resolver, registry);
+        match command {
+            InspectCommand::Trace { hash, output } => {
+                let frames = inspector.connect(endpoint).await?.trace(&hash).await?;
+                inspect::print_trace(&frames, output.format())
+            }
+            InspectCommand::Tx { hash, output } => {
+                let tx = inspector.connect(endpoint).await?.tx(&hash).await?;
+                inspect::print_tx(&tx, output.format())
+            }
+            InspectCommand::Block { block, output } => {
+                let (block, txs) = inspector.connect(endpoint).await?.block(&block).await?;
+                inspect::print_block(&block, &txs, output.format())
+            }
+            InspectCommand::Decode {
+                calldata,
+                sig,
+                output,
+            } => {
+                let mut inspector = inspector;
+                let call = inspector.decode(&calldata, sig.as_deref()).await?;
+                inspect::print_decoded(&call, output.format())
+            }
+        }
+    })
+}
+
+/// Follow the chain and serve poke's own Prometheus metrics on `addr` until killed
+pub fn serve_metrics(
+    addr: &str,
+    runtime: RuntimeBridge,
+    rules: &[AlertRule],
+    watched: BTreeSet<String>,
+) -> Result<()> {
+    let listener = TcpListener::bind(addr).with_context(|| format!("bind metrics server {addr}"))?;
+    eprintln!("poke: serving metrics on http://{}/metrics", listener.local_addr()?);
+
+    let exporter = Arc::new(Mutex::new(Exporter::new(rules, &watched)));
+    runtime.send(RuntimeCommand::SetWatchedAddresses {
+        addresses: watched.into_iter().collect(),
+    })?;
+    let server = exporter.clone();
+    thread::spawn(move || exporter::serve(listener, server));
+
+    loop {
+        match runtime.recv_timeout(Duration::from_secs(1)) {
+            Ok(event) => {
+                if let Ok(mut exporter) = exporter.lock() {
+                    exporter.apply(&event);
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => bail!("runtime worker stopped"),
+        }
+    }
+}
+
+/// Follow the chain and write NDJSON events until killed
+pub fn watch(args: WatchArgs, runtime: RuntimeBridge, watched: BTreeSet<String>) -> Result<()> {
+    let mut sink = match args.output {
+        Some(path) => EventSink::File(RotatingFile::open(
+            path,
+            args.max_size_mb.saturating_mul(1024 * 1024),
+            args.keep,
+        )?),
+        None => EventSink::Stdout,
+    };
+    let mut formatter = EventFormatter::new(&watched);
+    runtime.send(RuntimeCommand::SetWatchedAddresses {
+        addresses: watched.into_iter().collect(),
+    })?;
+
+    loop {
+        match runtime.recv_timeout(Duration::from_secs(1)) {
+            Ok(event) => {
+                for record in formatter.records(&event) {
+                    sink.write_record(&record)?;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => bail!("runtime worker stopped"),
+        }
+    }
+}
@@ -0,0 +1,391 @@
+//! Watch daemon - follows the chain and writes NDJSON events
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+
+use crate::infrastructure::runtime::{RuntimeEvent, TxStatus};
+
+/// Written block hashes kept to recognize replays
+const MAX_RECENT_BLOCKS: usize = 128;
+
+/// Turns runtime events into NDJSON records
+#[derive(Debug, Default)]
+pub struct EventFormatter {
+    /// Lowercase watched addresses and contracts
+    watched: BTreeSet<String>,
+    /// number -> hash of recently written blocks. The snapshot replayed after a
+    /// reconnect is not written again, but a block replacing one after a reorg is.
+    written: BTreeMap<u64, String>,
+}
+
+impl EventFormatter {
+    pub fn new(watched: &BTreeSet<String>) -> Self {
+        Self {
+            watched: watched.iter().map(|address| address.to_lowercase()).collect(),
+            written: BTreeMap::new(),
+        }
+    }
+
+    /// Whether the block was written already, or is older than anything written
+    fn is_replay(&self, number: u64, hash: &str) -> bool {
+        let before_first = self.written.first_key_value().is_some_and(|(first, _)| number < *first);
+        before_first || self.written.get(&number).is_some_and(|seen| seen == hash)
+    }
+
+    /// Records for one event: blocks, watched txs, alerts, connects and errors
+    pub fn records(&mut self, event: &RuntimeEvent) -> Vec<Value> {
+        let at = now_secs();
+        match event {
+            RuntimeEvent::Connected {
+                endpoint,
+                node_kind,
+                ..
+            } => vec![json!({
+                "type": "connected",
+                "at": at,
+                "endpoint": endpoint,
+                "client": node_kind,
+            })],
+            RuntimeEvent::NewBlock { block, txs } => {
+                if self.is_replay(block.number, &block.hash) {
+                    return Vec::new();
+                }
+                self.written.insert(block.number, block.hash.clone());
+                while self.written.len() > MAX_RECENT_BLOCKS {
+                    self.written.pop_first();
+                }
+                let mut records = vec![json!({
+                    "type": "block",
+                    "at": at,
+                    "number": block.number,
+                    "hash": block.hash,
+                    "parent_hash": block.parent_hash,
+                    "tx_count": block.tx_count,
+                    "gas_used": block.gas_used,
+                    "base_fee_gwei": block.base_fee,
+                    "miner": block.miner,
+                    "blob_count": block.blob_count,
+                    "blob_base_fee_gwei": block.blob_base_fee.map(|fee| fee as f64 / 1e9),
+                })];
+                for tx in txs {
+                    // Sender, recipient, then log emitters and the parties of its token transfers
+                    let mut matched: Vec<String> = Vec::new();
+                    let candidates = [tx.from.as_str(), tx.to.as_str()]
+                        .into_iter()
+                        .chain(tx.log_addresses.iter().map(String::as_str));
+                    for address in candidates {
+                        let address = address.to_lowercase();
+                        if self.watched.contains(&address) && !matched.contains(&address) {
+                            matched.push(address);
+                        }
+                    }
+                    if matched.is_empty() {
+                        continue;
+                    }
+                    let status = match tx.status {
+                        TxStatus::Success => "success",
+                        TxStatus::Revert => "revert",
+                        TxStatus::Unknown => "unknown",
+                    };
+                    records.push(json!({
+                        "type": "tx",
+                        "at": at,
+                        "block": tx.block_number,
+                        "hash": tx.hash,
+                        "from": tx.from,
+                        "to": tx.to,
+                        "value_eth": tx.value,
+                        "gas_used": tx.gas_used,
+                        "status": status,
+                        "selector": tx.selector,
+                        "method": tx.signature.as_deref().unwrap_or(&tx.method),
+                        "blobs": tx.blob_hashes.len(),
+                        "watched": matched,
+                    }));
+                }
+                records
+            }
+            RuntimeEvent::Alert { event } => vec![json!({
+                "type": "alert",
+                "at": event.at,
+                "rule": event.rule,
+                "state": event.state.as_str(),
+                "message": event.message,
+                "detail": event.detail,
+            })],
+            RuntimeEvent::Failover { from, to, reason } => vec![json!({
+                "type": "failover",
+                "at": at,
+                "from": from,
+                "to": to,
+                "reason": reason,
+            })],
+            RuntimeEvent::Error { message } => vec![json!({
+                "type": "error",
+                "at": at,
+                "message": message,
+            })],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Where NDJSON lines go: stdout, or a file rotated by size
+pub enum EventSink {
+    Stdout,
+    File(RotatingFile),
+}
+
+impl EventSink {
+    pub fn write_record(&mut self, record: &Value) -> Result<()> {
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+        match self {
+            EventSink::Stdout => {
+                let mut out = io::stdout().lock();
+                out.write_all(line.as_bytes())?;
+                out.flush()?;
+            }
+            EventSink::File(file) => file.write_line(&line)?,
+        }
+        Ok(())
+    }
+}
+
+/// Append-only file that moves to `path.1`, `path.2`, … once it reaches `max_bytes`
+pub struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    keep: usize,
+    file: File,
+    written: u64,
+}
+
+impl RotatingFile {
+    pub fn open(path: PathBuf, max_bytes: u64, keep: usize) -> Result<Self> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = open_append(&path)?;
+        let written = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+        Ok(Self {
+            path,
+            max_bytes: max_bytes.max(1),
+            keep,
+            file,
+            written,
+        })
+    }
+
+    fn write_line(&mut self, line: &str) -> Result<()> {
+        if self.written > 0 && self.written + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(line.as_bytes())?;
+        self.file.flush()?;
+        self.written += line.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        if self.keep == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            let _ = fs::remove_file(rotated(&self.path, self.keep));
+            for index in (1..self.keep).rev() {
+                let from = rotated(&self.path, index);
+                if from.exists() {
+                    fs::rename(&from, rotated(&self.path, index + 1))?;
+                }
+            }
+            fs::rename(&self.path, rotated(&self.path, 1))?;
+        }
+        self.file = open_append(&self.path)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("open {}", path.display()))
+}
+
+fn rotated(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::abi::AbiRegistry;
+    use crate::domain::alerts::{AlertEvent, AlertState};
+    use crate::infrastructure::runtime::{BlockInfo, TxInfo};
+
+    fn tx(hash: &str, from: &str, to: &str) -> TxInfo {
+        TxInfo {
+            hash: hash.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            value: 1.5,
+            gas_used: 21_000,
+            status: TxStatus::Success,
+            input: "0x".to_string(),
+            selector: "0x".to_string(),
+            method: "(transfer)".to_string(),
+            block_number: 7,
+            ..Default::default()
+        }
+    }
+
+    fn block(number: u64) -> BlockInfo {
+        BlockInfo {
+            number,
+            hash: format!("0x{number:02x}"),
+            parent_hash: format!("0x{:02x}", number - 1),
+            tx_count: 2,
+            gas_used: 42_000,
+            base_fee: 12,
+            miner: "0xmmmm".to_string(),
+            blob_count: 0,
+            blob_base_fee: None,
+        }
+    }
+
+    #[test]
+    fn test_block_and_watched_txs() {
+        let watched = BTreeSet::from(["0xAAAA".to_string()]);
+        let mut formatter = EventFormatter::new(&watched);
+        let records = formatter.records(&RuntimeEvent::NewBlock {
+            block: block(7),
+            txs: vec![tx("0x01", "0xbbbb", "0xcccc"), tx("0x02", "0xbbbb", "0xaaaa")],
+        });
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["type"], "block");
+        assert_eq!(records[0]["number"], 7);
+        assert_eq!(records[1]["type"], "tx");
+        assert_eq!(records[1]["hash"], "0x02");
+        assert_eq!(records[1]["watched"], json!(["0xaaaa"]));
+    }
+
+    #[test]
+    fn test_token_transfer_to_watched_address() {
+        let watched = BTreeSet::from(["0xaaaa".to_string()]);
+        let mut formatter = EventFormatter::new(&watched);
+        let mut transfer = tx("0x03", "0xbbbb", "0xtoken");
+        transfer.log_addresses = vec!["0xbbbb".to_string(), "0xaaaa".to_string()];
+        let records = formatter.records(&RuntimeEvent::NewBlock {
+            block: block(8),
+            txs: vec![transfer],
+        });
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1]["hash"], "0x03");
+        assert_eq!(records[1]["watched"], json!(["0xaaaa"]));
+    }
+
+    #[test]
+    fn test_replayed_blocks_are_skipped() {
+        let mut formatter = EventFormatter::default();
+        let mut emit = |number: u64| {
+            formatter
+                .records(&RuntimeEvent::NewBlock {
+                    block: block(number),
+                    txs: Vec::new(),
+                })
+                .len()
+        };
+        assert_eq!(emit(9), 1);
+        assert_eq!(emit(10), 1);
+        // Snapshot after a reconnect starts a few blocks back
+        assert_eq!(emit(8), 0);
+        assert_eq!(emit(10), 0);
+        assert_eq!(emit(11), 1);
+    }
+
+    #[test]
+    fn test_reorged_block_is_written() {
+        let watched = BTreeSet::from(["0xaaaa".to_string()]);
+        let mut formatter = EventFormatter::new(&watched);
+        formatter.records(&RuntimeEvent::NewBlock {
+            block: block(12),
+            txs: Vec::new(),
+        });
+        let mut replacement = block(12);
+        replacement.hash = "0x12bb".to_string();
+        let records = formatter.records(&RuntimeEvent::NewBlock {
+            block: replacement.clone(),
+            txs: vec![tx("0x04", "0xaaaa", "0xcccc")],
+        });
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["hash"], "0x12bb");
+        assert_eq!(records[1]["hash"], "0x04");
+        assert!(formatter
+            .records(&RuntimeEvent::NewBlock {
+                block: replacement,
+                txs: Vec::new(),
+            })
+            .is_empty());
+    }
+
+    #[test]
+    fn test_alert_record() {
+        let mut formatter = EventFormatter::default();
+        let records = formatter.records(&RuntimeEvent::Alert {
+            event: AlertEvent {
+                rule: "peer_count_low".to_string(),
+                state: AlertState::Resolved,
+                message: "Peer count critically low".to_string(),
+                detail: "peers=5".to_string(),
+                at: 1_700_000_000,
+            },
+        });
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["type"], "alert");
+        assert_eq!(records[0]["state"], "resolved");
+        assert_eq!(records[0]["at"], 1_700_000_000);
+        assert!(formatter
+            .records(&RuntimeEvent::AbiRegistryReady {
+                registry: AbiRegistry::new()
+            })
+            .is_empty());
+    }
+
+    #[test]
+    fn test_rotation() {
+        let dir = std::env::temp_dir().join(format!("poke-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("events.ndjson");
+        let mut file = RotatingFile::open(path.clone(), 20, 2).unwrap();
+
+        for n in 0..4 {
+            file.write_line(&format!("{{\"n\":{n}}}------\n")).unwrap();
+        }
+        // Each 15-byte line fills a file; only two rotated files are kept
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"n\":3}------\n");
+        assert_eq!(fs::read_to_string(rotated(&path, 1)).unwrap(), "{\"n\":2}------\n");
+        assert_eq!(fs::read_to_string(rotated(&path, 2)).unwrap(), "{\"n\":1}------\n");
+        assert!(!rotated(&path, 3).exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
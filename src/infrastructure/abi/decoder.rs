@@ -122,7 +122,7 @@ impl AbiDecoder for AlloyAbiDecoder {
 }
 
 /// Format a DynSolValue for display
-fn format_dyn_sol_value(value: &DynSolValue) -> String {
+pub(crate) fn format_dyn_sol_value(value: &DynSolValue) -> String {
     match value {
         DynSolValue::Bool(b) => b.to_string(),
         DynSolValue::Int(i, _) => i.to_string(),
@@ -196,6 +196,10 @@ mod tests {
                     kind: "uint256".to_string(),
                 },
             ],
+            outputs: vec![ParamSpec {
+                name: String::new(),
+                kind: "bool".to_string(),
+            }],
             source: PathBuf::from("test.json"),
         }
     }
@@ -1,8 +1,11 @@
 //! ABI infrastructure - Alloy-based ABI scanning and decoding
 
 mod decoder;
+pub mod nested;
 mod resolver;
 mod scanner;
+mod watcher;
 
 pub use resolver::AbiResolver;
 pub use scanner::AbiScanner;
+pub use watcher::AbiWatcher;
@@ -0,0 +1,489 @@
+//! Nested calldata decoding - calls wrapped in `bytes` arguments
+//!
+//! Handles selector-prefixed `bytes`/`bytes[]` (multicall, aggregate,
+//! aggregate3, Safe execTransaction), Safe `multiSend` packed batches and
+//! Uniswap Universal Router `execute(bytes,bytes[])` commands.
+
+use std::path::PathBuf;
+
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_primitives::keccak256;
+
+use super::decoder::format_dyn_sol_value;
+use crate::domain::abi::{FunctionSignature, NestedArg, NestedCall, ParamSpec};
+
+/// Maximum wrapper depth to unwrap (multicall inside execTransaction inside multiSend…)
+const MAX_DEPTH: usize = 4;
+
+/// Selector lookup used while unwrapping (local ABI registry, then signature cache)
+pub type SelectorLookup<'a> = dyn Fn(&str) -> Option<FunctionSignature> + 'a;
+
+/// Build a function signature from its text form, e.g. from the 4byte cache
+pub fn function_from_signature(signature: &str) -> Option<FunctionSignature> {
+    let signature: String = signature.chars().filter(|c| !c.is_whitespace()).collect();
+    let open = signature.find('(')?;
+    let name = signature[..open].to_string();
+    let DynSolType::Tuple(types) = DynSolType::parse(&signature[open..]).ok()? else {
+        return None;
+    };
+    let hash = keccak256(signature.as_bytes());
+    Some(FunctionSignature {
+        selector: [hash[0], hash[1], hash[2], hash[3]],
+        name,
+        inputs: types
+            .iter()
+            .map(|ty| ParamSpec {
+                name: String::new(),
+                kind: ty.sol_type_name().to_string(),
+            })
+            .collect(),
+        outputs: Vec::new(),
+        signature,
+        source: PathBuf::new(),
+    })
+}
+
+/// Nested calls for each top-level argument of an already-decoded call
+pub fn nested_calls_for_args(
+    function: &FunctionSignature,
+    values: &[DynSolValue],
+    lookup: &SelectorLookup,
+) -> Vec<Vec<NestedCall>> {
+    nested_for_args(function, values, lookup, 0)
+}
+
+/// Decode full calldata (selector + args), unwrapping nested calls
+pub fn decode_nested_call(data: &[u8], lookup: &SelectorLookup) -> Option<NestedCall> {
+    decode_call(data, None, None, lookup, 0)
+}
+
+/// Render nested calls as an indented tree, one line per call or argument
+pub fn render_call_tree(calls: &[NestedCall], depth: usize, out: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+    for (idx, call) in calls.iter().enumerate() {
+        let branch = if idx + 1 == calls.len() { "└─" } else { "├─" };
+        let mut line = format!("{indent}{branch} {}", call.name);
+        if let Some(target) = call.target.as_ref() {
+            line.push_str(&format!(" → {}", target));
+        }
+        if let Some(value) = call.value.as_ref() {
+            line.push_str(&format!(" ({} wei)", value));
+        }
+        out.push(line);
+        if let Some(err) = call.error.as_ref() {
+            out.push(format!("{indent}   ! {}", err));
+        }
+        for arg in &call.args {
+            out.push(format!("{indent}   {} ({}) = {}", arg.name, arg.kind, arg.value));
+            render_call_tree(&arg.calls, depth + 2, out);
+        }
+    }
+}
+
+fn nested_for_args(
+    function: &FunctionSignature,
+    values: &[DynSolValue],
+    lookup: &SelectorLookup,
+    depth: usize,
+) -> Vec<Vec<NestedCall>> {
+    if depth >= MAX_DEPTH {
+        return vec![Vec::new(); values.len()];
+    }
+
+    // Universal Router: execute(bytes commands, bytes[] inputs[, uint256 deadline])
+    let kinds: Vec<&str> = function.inputs.iter().map(|p| p.kind.as_str()).collect();
+    if function.name == "execute" && kinds.starts_with(&["bytes", "bytes[]"]) {
+        if let (Some(DynSolValue::Bytes(commands)), Some(DynSolValue::Array(inputs))) =
+            (values.first(), values.get(1))
+        {
+            let mut out = vec![Vec::new(); values.len()];
+            out[1] = router_commands(commands, inputs);
+            return out;
+        }
+    }
+
+    // The first address argument is the call target for wrappers like execTransaction
+    let target = values.iter().find_map(address_of);
+    values
+        .iter()
+        .map(|value| match value {
+            DynSolValue::Bytes(bytes) if function.name == "multiSend" => {
+                multisend_calls(bytes, lookup, depth + 1)
+            }
+            other => nested_in_value(other, target.clone(), lookup, depth + 1),
+        })
+        .collect()
+}
+
+fn nested_in_value(
+    value: &DynSolValue,
+    target: Option<String>,
+    lookup: &SelectorLookup,
+    depth: usize,
+) -> Vec<NestedCall> {
+    match value {
+        DynSolValue::Bytes(bytes) => decode_call(bytes, target, None, lookup, depth)
+            .into_iter()
+            .collect(),
+        DynSolValue::Array(items) | DynSolValue::FixedArray(items) => items
+            .iter()
+            .flat_map(|item| nested_in_value(item, target.clone(), lookup, depth))
+            .collect(),
+        DynSolValue::Tuple(fields) => {
+            // (address target, bool allowFailure, bytes callData) and friends
+            let target = fields.iter().find_map(address_of).or(target);
+            fields
+                .iter()
+                .flat_map(|field| nested_in_value(field, target.clone(), lookup, depth))
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Decode selector-prefixed calldata. Returns None when the selector is unknown,
+/// so arbitrary `bytes` blobs aren't mistaken for calls.
+fn decode_call(
+    data: &[u8],
+    target: Option<String>,
+    value: Option<String>,
+    lookup: &SelectorLookup,
+    depth: usize,
+) -> Option<NestedCall> {
+    if data.len() < 4 || depth > MAX_DEPTH {
+        return None;
+    }
+    let selector = format!("0x{}", hex::encode(&data[..4]));
+    let function = lookup(&selector)?;
+
+    let mut call = NestedCall {
+        target,
+        selector: Some(selector),
+        name: function.name.clone(),
+        signature: Some(function.signature.clone()),
+        value,
+        args: Vec::new(),
+        error: None,
+    };
+
+    match decode_params(&function.inputs, &data[4..]) {
+        Ok(values) => {
+            let nested = nested_for_args(&function, &values, lookup, depth);
+            call.args = build_args(&function.inputs, &values, nested);
+        }
+        Err(err) => call.error = Some(err),
+    }
+    Some(call)
+}
+
+/// Safe MultiSend batch: packed (uint8 operation, address to, uint256 value,
+/// uint256 dataLength, bytes data) records
+fn multisend_calls(packed: &[u8], lookup: &SelectorLookup, depth: usize) -> Vec<NestedCall> {
+    let mut calls = Vec::new();
+    let mut offset = 0;
+    while offset + 85 <= packed.len() {
+        let operation = packed[offset];
+        let to = format!("0x{}", hex::encode(&packed[offset + 1..offset + 21]));
+        let value = alloy_primitives::U256::from_be_slice(&packed[offset + 21..offset + 53]);
+        let len = alloy_primitives::U256::from_be_slice(&packed[offset + 53..offset + 85]);
+        let Ok(len) = usize::try_from(len) else {
+            break;
+        };
+        let start = offset + 85;
+        let Some(end) = start.checked_add(len).filter(|end| *end <= packed.len()) else {
+            break;
+        };
+        let data = &packed[start..end];
+        let value = (!value.is_zero()).then(|| value.to_string());
+
+        let mut call = decode_call(data, Some(to.clone()), value.clone(), lookup, depth)
+            .unwrap_or_else(|| NestedCall {
+                target: Some(to),
+                selector: (data.len() >= 4).then(|| format!("0x{}", hex::encode(&data[..4]))),
+                name: if data.is_empty() { "transfer".to_string() } else { "unknown".to_string() },
+                signature: None,
+                value,
+                args: Vec::new(),
+                error: None,
+            });
+        if operation == 1 {
+            call.name = format!("{} (delegatecall)", call.name);
+        }
+        calls.push(call);
+        offset = end;
+    }
+    calls
+}
+
+/// Universal Router commands, keyed by the low 6 bits of the command byte
+fn router_command(command: u8) -> Option<(&'static str, &'static [(&'static str, &'static str)])> {
+    const SWAP_IN: &[(&str, &str)] = &[
+        ("recipient", "address"),
+        ("amountIn", "uint256"),
+        ("amountOutMin", "uint256"),
+        ("path", "bytes"),
+        ("payerIsUser", "bool"),
+    ];
+    const SWAP_OUT: &[(&str, &str)] = &[
+        ("recipient", "address"),
+        ("amountOut", "uint256"),
+        ("amountInMax", "uint256"),
+        ("path", "bytes"),
+        ("payerIsUser", "bool"),
+    ];
+    const V2_SWAP_IN: &[(&str, &str)] = &[
+        ("recipient", "address"),
+        ("amountIn", "uint256"),
+        ("amountOutMin", "uint256"),
+        ("path", "address[]"),
+        ("payerIsUser", "bool"),
+    ];
+    const V2_SWAP_OUT: &[(&str, &str)] = &[
+        ("recipient", "address"),
+        ("amountOut", "uint256"),
+        ("amountInMax", "uint256"),
+        ("path", "address[]"),
+        ("payerIsUser", "bool"),
+    ];
+    const TOKEN_MOVE: &[(&str, &str)] = &[
+        ("token", "address"),
+        ("recipient", "address"),
+        ("amount", "uint256"),
+    ];
+    const PERMIT2_TRANSFER: &[(&str, &str)] = &[
+        ("token", "address"),
+        ("recipient", "address"),
+        ("amount", "uint160"),
+    ];
+    const WRAP: &[(&str, &str)] = &[("recipient", "address"), ("amountMin", "uint256")];
+
+    Some(match command & 0x3f {
+        0x00 => ("V3_SWAP_EXACT_IN", SWAP_IN),
+        0x01 => ("V3_SWAP_EXACT_OUT", SWAP_OUT),
+        0x02 => ("PERMIT2_TRANSFER_FROM", PERMIT2_TRANSFER),
+        0x04 => ("SWEEP", TOKEN_MOVE),
+        0x05 => ("TRANSFER", TOKEN_MOVE),
+        0x06 => ("PAY_PORTION", TOKEN_MOVE),
+        0x08 => ("V2_SWAP_EXACT_IN", V2_SWAP_IN),
+        0x09 => ("V2_SWAP_EXACT_OUT", V2_SWAP_OUT),
+        0x0b => ("WRAP_ETH", WRAP),
+        0x0c => ("UNWRAP_WETH", WRAP),
+        _ => return None,
+    })
+}
+
+fn router_commands(commands: &[u8], inputs: &[DynSolValue]) -> Vec<NestedCall> {
+    commands
+        .iter()
+        .zip(inputs)
+        .map(|(command, input)| {
+            let DynSolValue::Bytes(input) = input else {
+                return unknown_command(*command);
+            };
+            let Some((name, params)) = router_command(*command) else {
+                return unknown_command(*command);
+            };
+            let inputs: Vec<ParamSpec> = params
+                .iter()
+                .map(|(name, kind)| ParamSpec {
+                    name: name.to_string(),
+                    kind: kind.to_string(),
+                })
+                .collect();
+            let signature = format!(
+                "{}({})",
+                name,
+                params.iter().map(|(_, kind)| *kind).collect::<Vec<_>>().join(",")
+            );
+            let mut call = NestedCall {
+                target: None,
+                selector: None,
+                name: name.to_string(),
+                signature: Some(signature),
+                value: None,
+                args: Vec::new(),
+                error: None,
+            };
+            match decode_params(&inputs, input) {
+                Ok(values) => {
+                    let nested = vec![Vec::new(); values.len()];
+                    call.args = build_args(&inputs, &values, nested);
+                }
+                Err(err) => call.error = Some(err),
+            }
+            call
+        })
+        .collect()
+}
+
+fn unknown_command(command: u8) -> NestedCall {
+    NestedCall {
+        target: None,
+        selector: None,
+        name: format!("command 0x{:02x}", command),
+        signature: None,
+        value: None,
+        args: Vec::new(),
+        error: None,
+    }
+}
+
+fn decode_params(params: &[ParamSpec], data: &[u8]) -> Result<Vec<DynSolValue>, String> {
+    let types = params
+        .iter()
+        .map(|param| {
+            DynSolType::parse(&param.kind)
+                .map_err(|e| format!("Failed to parse type '{}': {}", param.kind, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if types.is_empty() {
+        return Ok(Vec::new());
+    }
+    match DynSolType::Tuple(types)
+        .abi_decode_params(data)
+        .map_err(|e| format!("Failed to decode calldata: {}", e))?
+    {
+        DynSolValue::Tuple(values) => Ok(values),
+        other => Ok(vec![other]),
+    }
+}
+
+fn build_args(
+    params: &[ParamSpec],
+    values: &[DynSolValue],
+    nested: Vec<Vec<NestedCall>>,
+) -> Vec<NestedArg> {
+    params
+        .iter()
+        .zip(values)
+        .zip(nested)
+        .enumerate()
+        .map(|(idx, ((param, value), calls))| NestedArg {
+            name: if param.name.trim().is_empty() {
+                format!("arg{}", idx)
+            } else {
+                param.name.clone()
+            },
+            kind: param.kind.clone(),
+            value: format_dyn_sol_value(value),
+            calls,
+        })
+        .collect()
+}
+
+fn address_of(value: &DynSolValue) -> Option<String> {
+    match value {
+        DynSolValue::Address(addr) => Some(format!("{:?}", addr)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, U256};
+
+    fn lookup(selector: &str) -> Option<FunctionSignature> {
+        let signature = match selector {
+            "0xa9059cbb" => "transfer(address,uint256)",
+            "0xac9650d8" => "multicall(bytes[])",
+            "0x82ad56cb" => "aggregate3((address,bool,bytes)[])",
+            "0x8d80ff0a" => "multiSend(bytes)",
+            _ => return None,
+        };
+        function_from_signature(signature)
+    }
+
+    fn transfer_calldata(to: Address, amount: u64) -> Vec<u8> {
+        let mut data = vec![0xa9, 0x05, 0x9c, 0xbb];
+        data.extend(
+            DynSolValue::Tuple(vec![
+                DynSolValue::Address(to),
+                DynSolValue::Uint(U256::from(amount), 256),
+            ])
+            .abi_encode_params(),
+        );
+        data
+    }
+
+    #[test]
+    fn test_function_from_signature() {
+        let function = function_from_signature("aggregate3((address,bool,bytes)[])").unwrap();
+        assert_eq!(function.selector, [0x82, 0xad, 0x56, 0xcb]);
+        assert_eq!(function.inputs.len(), 1);
+        assert_eq!(function.inputs[0].kind, "(address,bool,bytes)[]");
+    }
+
+    #[test]
+    fn test_multicall_bytes_array() {
+        let inner = transfer_calldata(Address::repeat_byte(0x11), 42);
+        let mut data = vec![0xac, 0x96, 0x50, 0xd8];
+        data.extend(
+            DynSolValue::Tuple(vec![DynSolValue::Array(vec![
+                DynSolValue::Bytes(inner.clone()),
+                DynSolValue::Bytes(inner),
+            ])])
+            .abi_encode_params(),
+        );
+
+        let call = decode_nested_call(&data, &lookup).unwrap();
+        assert_eq!(call.name, "multicall");
+        let nested = &call.args[0].calls;
+        assert_eq!(nested.len(), 2);
+        assert_eq!(nested[0].name, "transfer");
+        assert_eq!(nested[0].args[1].value, "42");
+    }
+
+    #[test]
+    fn test_aggregate3_targets() {
+        let target = Address::repeat_byte(0x22);
+        let mut data = vec![0x82, 0xad, 0x56, 0xcb];
+        data.extend(
+            DynSolValue::Tuple(vec![DynSolValue::Array(vec![DynSolValue::Tuple(vec![
+                DynSolValue::Address(target),
+                DynSolValue::Bool(true),
+                DynSolValue::Bytes(transfer_calldata(Address::repeat_byte(0x33), 7)),
+            ])])])
+            .abi_encode_params(),
+        );
+
+        let call = decode_nested_call(&data, &lookup).unwrap();
+        let nested = &call.args[0].calls;
+        assert_eq!(nested.len(), 1);
+        assert_eq!(nested[0].target.as_deref(), Some(format!("{:?}", target).as_str()));
+    }
+
+    #[test]
+    fn test_multisend_packed() {
+        let to = Address::repeat_byte(0x44);
+        let inner = transfer_calldata(Address::repeat_byte(0x55), 9);
+        let mut packed = vec![0u8];
+        packed.extend_from_slice(to.as_slice());
+        packed.extend_from_slice(&U256::ZERO.to_be_bytes::<32>());
+        packed.extend_from_slice(&U256::from(inner.len()).to_be_bytes::<32>());
+        packed.extend_from_slice(&inner);
+        // Plain ETH transfer with empty data
+        packed.push(0);
+        packed.extend_from_slice(to.as_slice());
+        packed.extend_from_slice(&U256::from(1000).to_be_bytes::<32>());
+        packed.extend_from_slice(&U256::ZERO.to_be_bytes::<32>());
+
+        let mut data = vec![0x8d, 0x80, 0xff, 0x0a];
+        data.extend(DynSolValue::Tuple(vec![DynSolValue::Bytes(packed)]).abi_encode_params());
+
+        let call = decode_nested_call(&data, &lookup).unwrap();
+        let nested = &call.args[0].calls;
+        assert_eq!(nested.len(), 2);
+        assert_eq!(nested[0].name, "transfer");
+        assert_eq!(nested[1].name, "transfer");
+        assert_eq!(nested[1].value.as_deref(), Some("1000"));
+    }
+
+    #[test]
+    fn test_unknown_bytes_not_decoded() {
+        let function = function_from_signature("foo(bytes)").unwrap();
+        let values = vec![DynSolValue::Bytes(vec![0xde, 0xad, 0xbe, 0xef, 0x00])];
+        let nested = nested_calls_for_args(&function, &values, &lookup);
+        assert!(nested[0].is_empty());
+    }
+}
@@ -4,9 +4,11 @@
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
 
+use crate::store::SignatureStore;
+
 /// Resolved function signature from 4byte database
 #[derive(Debug, Clone)]
 pub struct ResolvedSignature {
@@ -62,6 +64,10 @@ pub struct AbiResolver {
     abi_cache: Arc<RwLock<HashMap<(u64, String), Option<ResolvedAbi>>>>,
     /// Pending lookups to avoid duplicate requests
     pending_selectors: Arc<RwLock<HashMap<String, bool>>>,
+    /// Local signature database consulted before any remote lookup
+    signature_store: Option<Arc<Mutex<SignatureStore>>>,
+    /// Never touch the network when set
+    offline: bool,
 }
 
 impl AbiResolver {
@@ -74,9 +80,46 @@ impl AbiResolver {
             selector_cache: Arc::new(RwLock::new(HashMap::new())),
             abi_cache: Arc::new(RwLock::new(HashMap::new())),
             pending_selectors: Arc::new(RwLock::new(HashMap::new())),
+            signature_store: None,
+            offline: false,
         }
     }
 
+    /// Resolve selectors from a local signature database first
+    pub fn with_signature_store(mut self, store: SignatureStore) -> Self {
+        self.signature_store = Some(Arc::new(Mutex::new(store)));
+        self
+    }
+
+    /// Disable remote lookups; only the local signature database is used
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    fn lookup_local(&self, selector_hex: &str, selector: [u8; 4]) -> Vec<ResolvedSignature> {
+        let Some(store) = self.signature_store.as_ref() else {
+            return Vec::new();
+        };
+        let Ok(store) = store.lock() else {
+            return Vec::new();
+        };
+        store
+            .lookup(selector_hex)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|signature| ResolvedSignature {
+                selector,
+                name: signature.split('(').next().unwrap_or(&signature).to_string(),
+                signature,
+            })
+            .collect()
+    }
+
     /// Lookup function signature by 4-byte selector using OpenChain API
     /// Returns the most likely signature (first result)
     pub async fn lookup_selector(&self, selector: [u8; 4]) -> Result<Option<ResolvedSignature>> {
@@ -90,6 +133,18 @@ impl AbiResolver {
             }
         }
 
+        // Then the local signature database
+        let local = self.lookup_local(&selector_hex, selector);
+        if !local.is_empty() {
+            let first = local.first().cloned();
+            self.selector_cache.write().await.insert(selector_hex, local);
+            return Ok(first);
+        }
+
+        if self.offline {
+            return Ok(None);
+        }
+
         // Check if already pending
         {
             let pending = self.pending_selectors.read().await;
@@ -271,6 +326,10 @@ impl AbiResolver {
             }
         }
 
+        if self.offline {
+            return Ok(None);
+        }
+
         // Query Sourcify API
         let url = format!(
             "https://sourcify.dev/server/v2/contract/{}/{}?fields=abi,name",
@@ -337,6 +396,21 @@ impl Default for AbiResolver {
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_offline_uses_local_store() {
+        let mut store = SignatureStore::open_in_memory().unwrap();
+        store.insert_signatures(["deposit()"]).unwrap();
+        let resolver = AbiResolver::new().with_signature_store(store).offline(true);
+
+        let sig = resolver.lookup_selector([0xd0, 0xe3, 0x0d, 0xb0]).await.unwrap();
+        assert_eq!(sig.map(|s| s.name), Some("deposit".to_string()));
+
+        // Unknown selector must not hit the network
+        let missing = resolver.lookup_selector([0xde, 0xad, 0xbe, 0xef]).await.unwrap();
+        assert!(missing.is_none());
+        assert!(resolver.lookup_abi(1, "0x0000000000000000000000000000000000000001").await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_lookup_deposit_selector() {
         let resolver = AbiResolver::new();
@@ -8,24 +8,64 @@ use alloy_json_abi::JsonAbi;
 use alloy_primitives::keccak256;
 use walkdir::WalkDir;
 
-use crate::domain::abi::{AbiRegistry, FunctionSignature, ParamSpec};
+use crate::domain::abi::{AbiLayout, AbiRegistry, ContractDeployment, FunctionSignature, ParamSpec};
+
+/// Functions and (optionally) a deployment parsed from one artifact file
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ParsedArtifact {
+    pub functions: Vec<FunctionSignature>,
+    pub deployment: Option<ContractDeployment>,
+}
 
 /// ABI file scanner
 pub struct AbiScanner;
 
 impl AbiScanner {
-    /// Scan a single root directory for ABI files
-    pub fn scan(root: impl AsRef<Path>) -> AbiRegistry {
+    /// Scan a single root directory, only considering the given layouts
+    pub fn scan_with_layouts(root: impl AsRef<Path>, layouts: &[AbiLayout]) -> AbiRegistry {
         let started = Instant::now();
-        let root = root.as_ref();
         let mut registry = AbiRegistry::new();
-        let mut scanned_files = 0;
+        let (files, mut errors) = Self::candidate_files(root.as_ref(), layouts);
+        let scanned_files = files.len();
+
+        for (path, _, layout) in files {
+            match Self::parse_abi_file(&path, layout) {
+                Ok(artifact) => {
+                    for function in artifact.functions {
+                        registry.insert(function);
+                    }
+                    registry.deployments.extend(artifact.deployment);
+                }
+                Err(err) => errors.push(format!("{}: {}", path.display(), err)),
+            }
+        }
+
+        registry.scanned_files = scanned_files;
+        registry.loaded_functions = registry.len();
+        registry.errors = errors;
+        registry.scan_ms = started.elapsed().as_millis();
+
+        registry
+    }
+
+    /// Walk a root and list artifact files that may contain ABIs, with their
+    /// metadata and detected layout. Returns (files, walk_errors).
+    pub(crate) fn candidate_files(
+        root: &Path,
+        layouts: &[AbiLayout],
+    ) -> (Vec<(PathBuf, fs::Metadata, AbiLayout)>, Vec<String>) {
+        let mut files = Vec::new();
         let mut errors = Vec::new();
+        let truffle = layouts.contains(&AbiLayout::Truffle);
 
         for entry in WalkDir::new(root)
             .follow_links(false)
             .into_iter()
-            .filter_entry(|e| !Self::is_ignored_dir(e.path()))
+            .filter_entry(|e| {
+                // Truffle keeps its artifacts under build/contracts
+                !Self::is_ignored_dir(e.path())
+                    || (truffle && e.file_name().to_str() == Some("build"))
+            })
         {
             let entry = match entry {
                 Ok(entry) => entry,
@@ -40,14 +80,9 @@ impl AbiScanner {
             }
 
             let path = entry.path();
-
-            // Only process JSON files in out/ or artifacts/ directories
-            if path.extension().and_then(|s| s.to_str()) != Some("json") {
-                continue;
-            }
-            if !Self::path_contains_any(path, &["out", "artifacts"]) {
+            let Some(layout) = Self::detect_layout(path, layouts) else {
                 continue;
-            }
+            };
 
             // Skip files larger than 5MB
             let metadata = match entry.metadata() {
@@ -61,28 +96,24 @@ impl AbiScanner {
                 continue;
             }
 
-            scanned_files += 1;
-
-            if let Err(err) = Self::load_abi_file(path, &mut registry) {
-                errors.push(format!("{}: {}", path.display(), err));
-            }
+            files.push((path.to_path_buf(), metadata, layout));
         }
 
-        registry.scanned_files = scanned_files;
-        registry.loaded_functions = registry.len();
-        registry.errors = errors;
-        registry.scan_ms = started.elapsed().as_millis();
-
-        registry
+        (files, errors)
     }
 
-    /// Scan multiple root directories
+    /// Scan multiple root directories in any known layout
     pub fn scan_roots(roots: &[PathBuf]) -> AbiRegistry {
+        Self::scan_roots_with_layouts(roots, &AbiLayout::ALL)
+    }
+
+    /// Scan multiple root directories, only considering the given layouts
+    pub fn scan_roots_with_layouts(roots: &[PathBuf], layouts: &[AbiLayout]) -> AbiRegistry {
         let started = Instant::now();
         let mut registry = AbiRegistry::new();
 
         for root in roots {
-            registry.merge(Self::scan(root));
+            registry.merge(Self::scan_with_layouts(root, layouts));
         }
 
         registry.loaded_functions = registry.len();
@@ -91,24 +122,98 @@ impl AbiScanner {
         registry
     }
 
-    /// Load a single ABI file
-    fn load_abi_file(path: &Path, registry: &mut AbiRegistry) -> anyhow::Result<()> {
+    /// Work out which enabled layout (if any) a file belongs to
+    fn detect_layout(path: &Path, layouts: &[AbiLayout]) -> Option<AbiLayout> {
+        let file_name = path.file_name()?.to_str()?;
+        let enabled = |layout: AbiLayout| layouts.contains(&layout).then_some(layout);
+
+        match path.extension().and_then(|s| s.to_str()) {
+            Some("abi") => return enabled(AbiLayout::RawAbi),
+            Some("json") => {}
+            _ => return None,
+        }
+
+        // Hardhat debug files and solc build info never carry a usable ABI
+        if file_name.ends_with(".dbg.json")
+            || Self::path_contains_any(path, &["build-info", "solcInputs"])
+        {
+            return None;
+        }
+
+        let parent = path.parent().and_then(|p| p.file_name()).and_then(|n| n.to_str());
+        let grandparent = path
+            .parent()
+            .and_then(|p| p.parent())
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str());
+
+        if grandparent == Some("deployments") {
+            if let Some(layout) = enabled(AbiLayout::HardhatDeploy) {
+                return Some(layout);
+            }
+        }
+        if parent == Some("contracts") && grandparent == Some("build") {
+            if let Some(layout) = enabled(AbiLayout::Truffle) {
+                return Some(layout);
+            }
+        }
+        if file_name.ends_with(".abi.json") || path.with_extension("vy").is_file() {
+            if let Some(layout) = enabled(AbiLayout::Vyper) {
+                return Some(layout);
+            }
+        }
+        if Self::path_contains_any(path, &["out"]) {
+            if let Some(layout) = enabled(AbiLayout::Foundry) {
+                return Some(layout);
+            }
+        }
+        if Self::path_contains_any(path, &["artifacts"]) {
+            if let Some(layout) = enabled(AbiLayout::Hardhat) {
+                return Some(layout);
+            }
+        }
+        None
+    }
+
+    /// Parse the function signatures (and deployment address, if any) out of a single ABI file
+    pub(crate) fn parse_abi_file(path: &Path, layout: AbiLayout) -> anyhow::Result<ParsedArtifact> {
         let content = fs::read_to_string(path)?;
         let value: serde_json::Value = serde_json::from_str(&content)?;
 
         // Try to extract ABI - either raw array or nested in "abi" field
         let abi_value = if value.is_array() {
-            value
+            value.clone()
         } else if let Some(abi) = value.get("abi") {
             abi.clone()
         } else {
-            return Ok(()); // No ABI found, skip silently
+            return Ok(ParsedArtifact::default()); // No ABI found, skip silently
         };
 
         // Parse as JsonAbi
         let abi: JsonAbi = serde_json::from_value(abi_value)?;
 
-        // Extract functions
+        let functions = Self::functions_from_abi(&abi, path);
+
+        let deployment = if layout == AbiLayout::HardhatDeploy {
+            Self::deployment_from(path, &value)
+        } else {
+            None
+        };
+
+        Ok(ParsedArtifact {
+            functions,
+            deployment,
+        })
+    }
+
+    /// Parse the function signatures of an ABI JSON array, e.g. one fetched from Sourcify
+    pub fn parse_abi_json(abi_json: &str, source: &Path) -> anyhow::Result<Vec<FunctionSignature>> {
+        let abi: JsonAbi = serde_json::from_str(abi_json)?;
+        Ok(Self::functions_from_abi(&abi, source))
+    }
+
+    fn functions_from_abi(abi: &JsonAbi, source: &Path) -> Vec<FunctionSignature> {
+        let mut functions = Vec::new();
         for function in abi.functions() {
             let signature = function.signature();
             let selector = Self::compute_selector(&signature);
@@ -121,19 +226,48 @@ impl AbiScanner {
                     kind: input.ty.to_string(),
                 })
                 .collect();
+            let outputs: Vec<ParamSpec> = function
+                .outputs
+                .iter()
+                .map(|output| ParamSpec {
+                    name: output.name.clone(),
+                    kind: output.selector_type().into_owned(),
+                })
+                .collect();
 
             let func_sig = FunctionSignature {
                 selector,
                 name: function.name.clone(),
                 signature,
                 inputs,
-                source: path.to_path_buf(),
+                outputs,
+                source: source.to_path_buf(),
             };
 
-            registry.insert(func_sig);
+            functions.push(func_sig);
         }
+        functions
+    }
 
-        Ok(())
+    /// Extract the deployed address from a hardhat-deploy artifact
+    fn deployment_from(path: &Path, value: &serde_json::Value) -> Option<ContractDeployment> {
+        let address = value.get("address")?.as_str()?.trim();
+        let payload = address.strip_prefix("0x")?;
+        if payload.len() != 40 || !payload.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let name = path.file_stem()?.to_str()?.to_string();
+        let network = path
+            .parent()
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+            .map(str::to_string);
+        Some(ContractDeployment {
+            address: format!("0x{}", payload.to_lowercase()),
+            name,
+            network,
+            source: path.to_path_buf(),
+        })
     }
 
     /// Compute the 4-byte function selector from a signature
@@ -206,4 +340,69 @@ mod tests {
             &["out", "artifacts"]
         ));
     }
+
+    #[test]
+    fn test_detect_layout() {
+        let all = AbiLayout::ALL;
+        let detect = |p: &str| AbiScanner::detect_layout(Path::new(p), &all);
+
+        assert_eq!(detect("/p/out/Token.sol/Token.json"), Some(AbiLayout::Foundry));
+        assert_eq!(detect("/p/artifacts/contracts/Token.sol/Token.json"), Some(AbiLayout::Hardhat));
+        assert_eq!(detect("/p/artifacts/contracts/Token.sol/Token.dbg.json"), None);
+        assert_eq!(detect("/p/artifacts/build-info/abc.json"), None);
+        assert_eq!(detect("/p/deployments/mainnet/Token.json"), Some(AbiLayout::HardhatDeploy));
+        assert_eq!(detect("/p/deployments/mainnet/solcInputs/abc.json"), None);
+        assert_eq!(detect("/p/build/contracts/Token.json"), Some(AbiLayout::Truffle));
+        assert_eq!(detect("/p/abis/Vault.abi.json"), Some(AbiLayout::Vyper));
+        assert_eq!(detect("/p/abis/Vault.abi"), Some(AbiLayout::RawAbi));
+        assert_eq!(detect("/p/src/config.json"), None);
+
+        // Disabled layouts are skipped
+        assert_eq!(
+            AbiScanner::detect_layout(Path::new("/p/build/contracts/Token.json"), &[AbiLayout::Foundry]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_scan_hardhat_deploy_address() {
+        let root = std::env::temp_dir().join(format!("poke-abi-layouts-{}", std::process::id()));
+        let dir = root.join("deployments").join("sepolia");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("Vault.json"),
+            r#"{"address":"0xAbCdEf0000000000000000000000000000000001","abi":[{"type":"function","name":"deposit","inputs":[],"outputs":[],"stateMutability":"payable"}]}"#,
+        )
+        .unwrap();
+
+        let registry = AbiScanner::scan_with_layouts(&root, &AbiLayout::ALL);
+        assert!(registry.lookup_hex("0xd0e30db0").is_some());
+        assert_eq!(registry.deployments.len(), 1);
+        let deployment = &registry.deployments[0];
+        assert_eq!(deployment.address, "0xabcdef0000000000000000000000000000000001");
+        assert_eq!(deployment.name, "Vault");
+        assert_eq!(deployment.network.as_deref(), Some("sepolia"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_parse_function_outputs() {
+        let root = std::env::temp_dir().join(format!("poke-abi-outputs-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join("Pair.abi");
+        fs::write(
+            &path,
+            r#"[{"type":"function","name":"getReserves","inputs":[],"outputs":[{"name":"reserve0","type":"uint112"},{"name":"reserve1","type":"uint112"},{"name":"","type":"uint32"}],"stateMutability":"view"}]"#,
+        )
+        .unwrap();
+
+        let artifact = AbiScanner::parse_abi_file(&path, AbiLayout::RawAbi).unwrap();
+        let outputs = &artifact.functions[0].outputs;
+        assert_eq!(outputs.len(), 3);
+        assert_eq!(outputs[0].name, "reserve0");
+        assert_eq!(outputs[2].kind, "uint32");
+
+        let _ = fs::remove_dir_all(&root);
+    }
 }
@@ -0,0 +1,159 @@
+//! ABI artifact watcher - polls scan roots and rescans only changed files
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::time::{Instant, SystemTime};
+
+use super::scanner::AbiScanner;
+use crate::domain::abi::{AbiLayout, AbiRegistry, ContractDeployment, FunctionSignature};
+
+/// Per-file state remembered between polls
+#[derive(Debug)]
+struct WatchedFile {
+    modified: Option<SystemTime>,
+    len: u64,
+    functions: Vec<FunctionSignature>,
+    deployment: Option<ContractDeployment>,
+    error: Option<String>,
+}
+
+/// Incremental ABI scanner.
+///
+/// Each `poll` stats every candidate artifact under the roots, reparses
+/// only files whose size or mtime changed, and rebuilds the registry from
+/// the cached per-file functions.
+#[derive(Debug, Default)]
+pub struct AbiWatcher {
+    roots: Vec<PathBuf>,
+    layouts: Vec<AbiLayout>,
+    files: BTreeMap<PathBuf, WatchedFile>,
+    initialized: bool,
+}
+
+impl AbiWatcher {
+    pub fn new(roots: Vec<PathBuf>, layouts: Vec<AbiLayout>) -> Self {
+        Self {
+            roots,
+            layouts,
+            files: BTreeMap::new(),
+            initialized: false,
+        }
+    }
+
+    /// Replace the watched roots and layouts and force a full rescan on the next poll
+    pub fn set_roots(&mut self, roots: Vec<PathBuf>, layouts: Vec<AbiLayout>) {
+        self.roots = roots;
+        self.layouts = layouts;
+        self.files.clear();
+        self.initialized = false;
+    }
+
+    /// Check the roots for changes. Returns a fresh registry when any
+    /// artifact was added, modified or removed (always on the first poll).
+    pub fn poll(&mut self) -> Option<AbiRegistry> {
+        let started = Instant::now();
+        let mut walk_errors = Vec::new();
+        let mut seen = BTreeSet::new();
+        let mut rescanned = 0;
+
+        for root in &self.roots {
+            let (files, errors) = AbiScanner::candidate_files(root, &self.layouts);
+            walk_errors.extend(errors);
+
+            for (path, metadata, layout) in files {
+                seen.insert(path.clone());
+                let modified = metadata.modified().ok();
+                let len = metadata.len();
+                let unchanged = self
+                    .files
+                    .get(&path)
+                    .map(|file| file.modified == modified && file.len == len)
+                    .unwrap_or(false);
+                if unchanged {
+                    continue;
+                }
+
+                rescanned += 1;
+                let (artifact, error) = match AbiScanner::parse_abi_file(&path, layout) {
+                    Ok(artifact) => (artifact, None),
+                    Err(err) => (Default::default(), Some(format!("{}: {}", path.display(), err))),
+                };
+                self.files.insert(
+                    path,
+                    WatchedFile {
+                        modified,
+                        len,
+                        functions: artifact.functions,
+                        deployment: artifact.deployment,
+                        error,
+                    },
+                );
+            }
+        }
+
+        let before = self.files.len();
+        self.files.retain(|path, _| seen.contains(path));
+        let removed = before - self.files.len();
+
+        if self.initialized && rescanned == 0 && removed == 0 {
+            return None;
+        }
+        self.initialized = true;
+
+        let mut registry = AbiRegistry::new();
+        for file in self.files.values() {
+            for function in &file.functions {
+                registry.insert(function.clone());
+            }
+            registry.deployments.extend(file.deployment.clone());
+        }
+        registry.scanned_files = rescanned;
+        registry.loaded_functions = registry.len();
+        registry.errors = walk_errors;
+        registry
+            .errors
+            .extend(self.files.values().filter_map(|file| file.error.clone()));
+        registry.scan_ms = started.elapsed().as_millis();
+
+        Some(registry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const ERC20_ABI: &str = r#"{"abi":[{"type":"function","name":"transfer","inputs":[{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],"outputs":[{"name":"","type":"bool"}],"stateMutability":"nonpayable"}]}"#;
+    const DEPOSIT_ABI: &str = r#"{"abi":[{"type":"function","name":"deposit","inputs":[],"outputs":[],"stateMutability":"payable"}]}"#;
+
+    #[test]
+    fn test_poll_picks_up_changes() {
+        let root = std::env::temp_dir().join(format!("poke-abi-watch-{}", std::process::id()));
+        let out = root.join("out").join("Token.sol");
+        fs::create_dir_all(&out).unwrap();
+        let artifact = out.join("Token.json");
+        fs::write(&artifact, ERC20_ABI).unwrap();
+
+        let mut watcher = AbiWatcher::new(vec![root.clone()], AbiLayout::ALL.to_vec());
+        let registry = watcher.poll().expect("first poll yields a registry");
+        assert!(registry.lookup_hex("0xa9059cbb").is_some());
+
+        // Nothing changed
+        assert!(watcher.poll().is_none());
+
+        // Modified artifact (different length, so mtime granularity doesn't matter)
+        fs::write(&artifact, DEPOSIT_ABI).unwrap();
+        let registry = watcher.poll().expect("change detected");
+        assert_eq!(registry.scanned_files, 1);
+        assert!(registry.lookup_hex("0xd0e30db0").is_some());
+        assert!(registry.lookup_hex("0xa9059cbb").is_none());
+
+        // Removed artifact
+        fs::remove_file(&artifact).unwrap();
+        let registry = watcher.poll().expect("removal detected");
+        assert!(registry.is_empty());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
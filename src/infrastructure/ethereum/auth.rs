@@ -0,0 +1,126 @@
+//! Credentials for endpoints behind authenticating proxies - extra headers,
+//! basic auth and HS256 JWT bearer tokens as used by the engine API
+
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use alloy::transports::Authorization;
+use anyhow::{bail, Context, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use sha2::Sha256;
+
+/// How requests to one endpoint authenticate
+#[derive(Clone, Default, PartialEq)]
+pub struct EndpointAuth {
+    /// Sent with every HTTP request; not supported on WebSocket endpoints
+    pub headers: Vec<(String, String)>,
+    /// Username and password
+    pub basic: Option<(String, String)>,
+    /// HS256 key; every HTTP request carries a freshly signed token. Not
+    /// supported on WebSocket endpoints, whose handshake is signed only once.
+    pub jwt_secret: Option<Vec<u8>>,
+}
+
+impl fmt::Debug for EndpointAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Header values and secrets stay out of logs and error messages
+        f.debug_struct("EndpointAuth")
+            .field("headers", &self.headers.iter().map(|(name, _)| name).collect::<Vec<_>>())
+            .field("basic", &self.basic.as_ref().map(|(user, _)| user))
+            .field("jwt", &self.jwt_secret.is_some())
+            .finish()
+    }
+}
+
+impl EndpointAuth {
+    /// The `Authorization` value to send now, if any
+    pub fn authorization(&self) -> Option<Authorization> {
+        if let Some(secret) = &self.jwt_secret {
+            return Some(Authorization::bearer(jwt_token(secret, now_secs())));
+        }
+        self.basic
+            .as_ref()
+            .map(|(user, password)| Authorization::basic(user, password))
+    }
+
+    /// Headers for one HTTP request
+    pub fn header_map(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .with_context(|| format!("invalid header name {name:?}"))?;
+            let mut value =
+                HeaderValue::from_str(value).with_context(|| format!("invalid value for header {name}"))?;
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+        if let Some(auth) = self.authorization() {
+            let mut value = HeaderValue::from_str(&auth.to_string()).context("invalid credentials")?;
+            value.set_sensitive(true);
+            headers.insert(AUTHORIZATION, value);
+        }
+        Ok(headers)
+    }
+}
+
+/// Parse a hex JWT secret, as in a node's jwt.hex; engine API secrets are 32 bytes
+pub fn parse_jwt_secret(value: &str) -> Result<Vec<u8>> {
+    let value = value.trim();
+    let value = value.strip_prefix("0x").unwrap_or(value);
+    let secret = hex::decode(value).context("JWT secret must be hex")?;
+    if secret.len() < 32 {
+        bail!("JWT secret must be at least 32 bytes, got {}", secret.len());
+    }
+    Ok(secret)
+}
+
+/// HS256 token with only the `iat` claim, which is all the engine API checks
+pub fn jwt_token(secret: &[u8], issued_at: u64) -> String {
+    let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"HS256","typ":"JWT"}"#);
+    let claims = URL_SAFE_NO_PAD.encode(format!(r#"{{"iat":{issued_at}}}"#));
+    let signing_input = format!("{header}.{claims}");
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(signing_input.as_bytes());
+    let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+    format!("{signing_input}.{signature}")
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jwt_token() {
+        let secret = parse_jwt_secret(&format!("0x{}", "11".repeat(32))).unwrap();
+        assert_eq!(
+            jwt_token(&secret, 1_700_000_000),
+            "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJpYXQiOjE3MDAwMDAwMDB9.\
+             Hb09lNHg9IiC1UttHzXRW1voUqjM97m4oEuKWkSux5g"
+        );
+        assert!(parse_jwt_secret("abcd").is_err());
+        assert!(parse_jwt_secret("zz").is_err());
+    }
+
+    #[test]
+    fn test_header_map() {
+        let auth = EndpointAuth {
+            headers: vec![("X-Api-Key".to_string(), "secret".to_string())],
+            basic: Some(("poke".to_string(), "hunter2".to_string())),
+            jwt_secret: None,
+        };
+        let headers = auth.header_map().unwrap();
+        assert_eq!(headers["x-api-key"], "secret");
+        assert_eq!(headers[AUTHORIZATION], "Basic cG9rZTpodW50ZXIy");
+        assert!(!format!("{auth:?}").contains("hunter2"));
+    }
+}
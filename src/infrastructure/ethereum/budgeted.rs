@@ -0,0 +1,294 @@
+//! Provider wrapper that paces calls to an endpoint's rate limits and backs off on 429
+//!
+//! Calls only ever wait briefly for the budget; one that would wait longer fails
+//! with a rate-limit error and the worker's polling backs off on the pressure.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use alloy::network::AnyTransactionReceipt;
+use alloy::primitives::{Address, Bytes, B256, U256};
+use alloy::rpc::types::{Block, TransactionRequest};
+use anyhow::Result;
+use tokio::sync::mpsc;
+
+use super::http::{rate_limited, RateLimited};
+use super::provider::{create_provider, EthereumProvider, ProviderConfig, RawBlock};
+use crate::domain::budget::{BudgetConfig, BudgetStatus, Pressure, RequestBudget};
+use crate::infrastructure::runtime::TraceFrame;
+
+/// Longest a call waits for the budget, or for Retry-After before its one
+/// retry; past this it fails as rate limited
+const MAX_PACE_WAIT: Duration = Duration::from_secs(1);
+
+/// Request budget of one endpoint, shared between its connections and the worker
+#[derive(Debug, Clone)]
+pub struct SharedBudget {
+    inner: Arc<Mutex<RequestBudget>>,
+}
+
+impl SharedBudget {
+    pub fn new(config: BudgetConfig) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(RequestBudget::new(config, Instant::now()))),
+        }
+    }
+
+    fn reserve(&self, method: &str) -> Result<Duration, Duration> {
+        self.inner
+            .lock()
+            .map(|mut budget| budget.reserve(method, Instant::now(), MAX_PACE_WAIT))
+            .unwrap_or(Ok(Duration::ZERO))
+    }
+
+    fn record_success(&self) {
+        if let Ok(mut budget) = self.inner.lock() {
+            budget.record_success();
+        }
+    }
+
+    fn throttle(&self, retry_after: Option<Duration>) -> Duration {
+        self.inner
+            .lock()
+            .map(|mut budget| budget.throttle(retry_after, Instant::now()))
+            .unwrap_or_default()
+    }
+
+    pub fn pressure(&self) -> Pressure {
+        self.inner
+            .lock()
+            .map(|budget| budget.pressure(Instant::now()))
+            .unwrap_or_default()
+    }
+
+    pub fn status(&self) -> Option<BudgetStatus> {
+        self.inner.lock().ok().map(|budget| budget.status(Instant::now()))
+    }
+}
+
+/// Wraps a provider so every call waits for the endpoint's budget
+pub struct BudgetedProvider {
+    inner: Box<dyn EthereumProvider>,
+    budget: SharedBudget,
+}
+
+impl BudgetedProvider {
+    pub fn new(inner: Box<dyn EthereumProvider>, budget: SharedBudget) -> Self {
+        Self { inner, budget }
+    }
+
+    /// Connect to `config`, charging every call to `budget`
+    pub async fn connect(config: ProviderConfig, budget: &SharedBudget) -> Result<Box<dyn EthereumProvider>> {
+        Ok(Box::new(Self::new(create_provider(config).await?, budget.clone())))
+    }
+
+    async fn paced<T, F, Fut>(&self, method: &'static str, call: F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut retried = false;
+        loop {
+            let wait = self.budget.reserve(method).map_err(|wait| RateLimited {
+                retry_after: Some(wait),
+            })?;
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+            let result = call().await;
+            match &result {
+                Ok(_) => self.budget.record_success(),
+                Err(err) => {
+                    if let Some(limited) = rate_limited(err) {
+                        let delay = self.budget.throttle(limited.retry_after);
+                        if !retried && delay <= MAX_PACE_WAIT {
+                            retried = true;
+                            continue;
+                        }
+                    }
+                }
+            }
+            return result;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumProvider for BudgetedProvider {
+    async fn block_number(&self) -> Result<u64> {
+        self.paced("eth_blockNumber", || self.inner.block_number()).await
+    }
+
+    async fn chain_id(&self) -> Result<u64> {
+        self.paced("eth_chainId", || self.inner.chain_id()).await
+    }
+
+    async fn client_version(&self) -> Result<String> {
+        self.paced("web3_clientVersion", || self.inner.client_version()).await
+    }
+
+    async fn accounts(&self) -> Result<Vec<Address>> {
+        self.paced("eth_accounts", || self.inner.accounts()).await
+    }
+
+    async fn peer_count(&self) -> Result<u64> {
+        self.paced("net_peerCount", || self.inner.peer_count()).await
+    }
+
+    async fn sync_status(&self) -> Result<Option<(u64, u64)>> {
+        self.paced("eth_syncing", || self.inner.sync_status()).await
+    }
+
+    async fn get_block(&self, number: u64) -> Result<Option<RawBlock>> {
+        self.paced("eth_getBlockByNumber", || self.inner.get_block(number)).await
+    }
+
+    async fn get_block_header(&self, number: u64) -> Result<Option<RawBlock>> {
+        self.paced("eth_getBlockByNumber", || self.inner.get_block_header(number)).await
+    }
+
+    async fn get_tagged_block(&self, tag: &'static str) -> Result<Option<RawBlock>> {
+        self.paced("eth_getBlockByNumber", || self.inner.get_tagged_block(tag)).await
+    }
+
+    async fn get_receipt(&self, hash: B256) -> Result<Option<AnyTransactionReceipt>> {
+        self.paced("eth_getTransactionReceipt", || self.inner.get_receipt(hash)).await
+    }
+
+    async fn get_balance(&self, address: Address, block: Option<u64>) -> Result<U256> {
+        self.paced("eth_getBalance", || self.inner.get_balance(address, block)).await
+    }
+
+    async fn get_code(&self, address: Address) -> Result<Bytes> {
+        self.paced("eth_getCode", || self.inner.get_code(address)).await
+    }
+
+    async fn call(&self, request: TransactionRequest, block: Option<u64>) -> Result<Bytes> {
+        self.paced("eth_call", || self.inner.call(request.clone(), block)).await
+    }
+
+    async fn get_storage_at(&self, address: Address, slot: U256, block: Option<u64>) -> Result<B256> {
+        self.paced("eth_getStorageAt", || self.inner.get_storage_at(address, slot, block))
+            .await
+    }
+
+    async fn debug_trace_transaction(&self, hash: B256) -> Result<Vec<TraceFrame>> {
+        self.paced("debug_traceTransaction", || self.inner.debug_trace_transaction(hash))
+            .await
+    }
+
+    async fn subscribe_blocks(&self) -> Result<mpsc::Receiver<Block>> {
+        self.paced("eth_subscribe", || self.inner.subscribe_blocks()).await
+    }
+
+    fn supports_subscriptions(&self) -> bool {
+        self.inner.supports_subscriptions()
+    }
+
+    fn endpoint_name(&self) -> String {
+        self.inner.endpoint_name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::ethereum::EndpointAuth;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Read one HTTP request and return its JSON-RPC id
+    async fn read_request_id(stream: &mut TcpStream) -> serde_json::Value {
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 4096];
+        loop {
+            let n = stream.read(&mut chunk).await.unwrap();
+            buf.extend_from_slice(&chunk[..n]);
+            let text = String::from_utf8_lossy(&buf);
+            if let Some(end) = text.find("\r\n\r\n") {
+                let length = text[..end]
+                    .lines()
+                    .find_map(|line| line.to_lowercase().strip_prefix("content-length:").map(str::to_string))
+                    .and_then(|value| value.trim().parse::<usize>().ok())
+                    .unwrap_or(0);
+                if buf.len() >= end + 4 + length {
+                    let body: serde_json::Value = serde_json::from_slice(&buf[end + 4..]).unwrap();
+                    return body["id"].clone();
+                }
+            }
+            if n == 0 {
+                return serde_json::Value::Null;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_after_429() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            for attempt in 0.. {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let id = read_request_id(&mut stream).await;
+                let response = if attempt == 0 {
+                    "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        .to_string()
+                } else {
+                    let body = format!(r#"{{"jsonrpc":"2.0","id":{id},"result":"0x10"}}"#);
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                };
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let budget = SharedBudget::new(BudgetConfig::default());
+        let provider = BudgetedProvider::new(
+            create_provider(ProviderConfig::Http(url, EndpointAuth::default()))
+                .await
+                .unwrap(),
+            budget.clone(),
+        );
+        assert_eq!(provider.block_number().await.unwrap(), 16);
+
+        let status = budget.status().unwrap();
+        assert_eq!(status.throttles, 1);
+        assert_eq!(status.used_cu, 20);
+        assert_eq!(status.pressure, Pressure::Constrained);
+    }
+
+    #[tokio::test]
+    async fn test_long_retry_after_fails_fast() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                read_request_id(&mut stream).await;
+                let response =
+                    "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 600\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let budget = SharedBudget::new(BudgetConfig::default());
+        let provider = BudgetedProvider::connect(ProviderConfig::Http(url, EndpointAuth::default()), &budget)
+            .await
+            .unwrap();
+        let started = Instant::now();
+        let err = provider.block_number().await.unwrap_err();
+        assert!(rate_limited(&err).is_some());
+        // Blocked by the first answer, the next call does not reach the endpoint
+        let err = provider.block_number().await.unwrap_err();
+        assert!(rate_limited(&err).is_some_and(|limited| limited.retry_after.is_some()));
+        assert!(started.elapsed() < MAX_PACE_WAIT);
+
+        let status = budget.status().unwrap();
+        assert_eq!(status.throttles, 1);
+        assert_eq!(status.pressure, Pressure::Exhausted);
+    }
+}
@@ -0,0 +1,137 @@
+//! HTTP JSON-RPC transport that keeps the Retry-After header of 429 responses
+
+use std::fmt;
+use std::task;
+use std::time::Duration;
+
+use alloy::rpc::client::RpcClient;
+use alloy::rpc::json_rpc::{RequestPacket, ResponsePacket};
+use alloy::transports::utils::guess_local_url;
+use alloy::transports::{RpcError, TransportError, TransportErrorKind, TransportFut, TransportResult};
+use reqwest::{StatusCode, Url};
+use tower::Service;
+
+use super::auth::EndpointAuth;
+use crate::domain::budget::parse_retry_after;
+
+/// The endpoint refused a request for exceeding its rate limit
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimited {
+    /// Parsed Retry-After, when the endpoint sent one in seconds
+    pub retry_after: Option<Duration>,
+}
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.retry_after {
+            Some(after) => write!(f, "rate limited (429), retry after {:.1}s", after.as_secs_f64()),
+            None => write!(f, "rate limited (429)"),
+        }
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// Whether `err` is a rate-limit answer: a 429 from this transport, a
+/// JSON-RPC error hosted providers use for throttling, or a call the budget
+/// refused to send
+pub fn rate_limited(err: &anyhow::Error) -> Option<RateLimited> {
+    if let Some(limited) = err.downcast_ref::<RateLimited>() {
+        return Some(limited.clone());
+    }
+    match err.downcast_ref::<TransportError>()? {
+        RpcError::Transport(TransportErrorKind::Custom(inner)) => {
+            inner.downcast_ref::<RateLimited>().cloned()
+        }
+        RpcError::Transport(TransportErrorKind::HttpError(http)) if http.is_rate_limit_err() => {
+            Some(RateLimited { retry_after: None })
+        }
+        RpcError::ErrorResp(payload) if payload.is_retry_err() => {
+            Some(RateLimited { retry_after: None })
+        }
+        _ => None,
+    }
+}
+
+/// Whether the node answered `err` with a JSON-RPC error such as a revert or an
+/// unsupported method, as opposed to the request failing or being throttled
+pub fn rpc_error_response(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<TransportError>(),
+        Some(RpcError::ErrorResp(payload)) if !payload.is_retry_err()
+    )
+}
+
+/// reqwest-backed transport, otherwise the same as alloy's `Http<Client>`
+#[derive(Debug, Clone)]
+pub struct HttpTransport {
+    client: reqwest::Client,
+    url: Url,
+    auth: EndpointAuth,
+}
+
+impl HttpTransport {
+    pub fn new(url: Url, auth: EndpointAuth) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+            auth,
+        }
+    }
+
+    /// An RPC client sending every request through this transport
+    pub fn into_client(self) -> RpcClient {
+        let is_local = guess_local_url(self.url.as_str());
+        RpcClient::new(self, is_local)
+    }
+
+    async fn send(self, req: RequestPacket) -> TransportResult<ResponsePacket> {
+        // Built per request so JWTs carry a current `iat`
+        let auth_headers = self
+            .auth
+            .header_map()
+            .map_err(|err| TransportErrorKind::custom_str(&format!("{err:#}")))?;
+        let resp = self
+            .client
+            .post(self.url)
+            .json(&req)
+            .headers(req.headers())
+            .headers(auth_headers)
+            .send()
+            .await
+            .map_err(TransportErrorKind::custom)?;
+        let status = resp.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = resp
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after);
+            return Err(TransportErrorKind::custom(RateLimited { retry_after }));
+        }
+
+        let body = resp.bytes().await.map_err(TransportErrorKind::custom)?;
+        if !status.is_success() {
+            return Err(TransportErrorKind::http_error(
+                status.as_u16(),
+                String::from_utf8_lossy(&body).into_owned(),
+            ));
+        }
+        serde_json::from_slice(&body)
+            .map_err(|err| TransportError::deser_err(err, String::from_utf8_lossy(&body)))
+    }
+}
+
+impl Service<RequestPacket> for HttpTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut task::Context<'_>) -> task::Poll<Result<(), Self::Error>> {
+        task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        Box::pin(self.clone().send(req))
+    }
+}
@@ -0,0 +1,203 @@
+//! Provider wrapper that times every RPC call by method
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use alloy::network::AnyTransactionReceipt;
+use alloy::primitives::{Address, Bytes, B256, U256};
+use alloy::rpc::types::{Block, TransactionRequest};
+use anyhow::Result;
+use tokio::sync::mpsc;
+
+use super::provider::{EthereumProvider, RawBlock};
+use crate::domain::rpc_stats::{MethodStats, RpcStatRow};
+use crate::infrastructure::runtime::TraceFrame;
+
+#[derive(Debug, Default)]
+struct Timings {
+    /// Total latency and calls per (endpoint, method) since the last drain
+    recent: BTreeMap<(String, &'static str), (Duration, u32)>,
+    /// Running counters per (endpoint, method), never reset
+    stats: BTreeMap<(String, &'static str), MethodStats>,
+}
+
+/// Call latencies per JSON-RPC method, shared by every instrumented provider
+#[derive(Debug, Clone, Default)]
+pub struct RpcTimings {
+    inner: Arc<Mutex<Timings>>,
+}
+
+impl RpcTimings {
+    pub fn record(&self, endpoint: &str, method: &'static str, elapsed: Duration, ok: bool) {
+        if let Ok(mut timings) = self.inner.lock() {
+            let entry = timings.recent.entry((endpoint.to_string(), method)).or_default();
+            entry.0 += elapsed;
+            entry.1 += 1;
+            timings
+                .stats
+                .entry((endpoint.to_string(), method))
+                .or_default()
+                .record(elapsed.as_secs_f64() * 1000.0, ok);
+        }
+    }
+
+    /// Average latency in ms per method of `endpoint` since the last drain,
+    /// resetting the counters of every endpoint
+    pub fn drain(&self, endpoint: &str) -> Vec<(&'static str, f64)> {
+        let Ok(mut timings) = self.inner.lock() else {
+            return Vec::new();
+        };
+        std::mem::take(&mut timings.recent)
+            .into_iter()
+            .filter(|((name, _), _)| name == endpoint)
+            .map(|((_, method), (total, count))| {
+                (method, total.as_secs_f64() * 1000.0 / count.max(1) as f64)
+            })
+            .collect()
+    }
+
+    /// Counts and latency percentiles per endpoint and method
+    pub fn rows(&self) -> Vec<RpcStatRow> {
+        let Ok(timings) = self.inner.lock() else {
+            return Vec::new();
+        };
+        timings
+            .stats
+            .iter()
+            .map(|((endpoint, method), stats)| stats.row(endpoint, method))
+            .collect()
+    }
+}
+
+/// Wraps a provider and records how long each call takes
+pub struct InstrumentedProvider {
+    inner: Box<dyn EthereumProvider>,
+    endpoint: String,
+    timings: RpcTimings,
+}
+
+impl InstrumentedProvider {
+    pub fn new(inner: Box<dyn EthereumProvider>, timings: RpcTimings) -> Self {
+        Self {
+            endpoint: inner.endpoint_name(),
+            inner,
+            timings,
+        }
+    }
+
+    async fn timed<T>(
+        &self,
+        method: &'static str,
+        call: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let started = Instant::now();
+        let result = call.await;
+        self.timings
+            .record(&self.endpoint, method, started.elapsed(), result.is_ok());
+        result
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumProvider for InstrumentedProvider {
+    async fn block_number(&self) -> Result<u64> {
+        self.timed("eth_blockNumber", self.inner.block_number()).await
+    }
+
+    async fn chain_id(&self) -> Result<u64> {
+        self.timed("eth_chainId", self.inner.chain_id()).await
+    }
+
+    async fn client_version(&self) -> Result<String> {
+        self.timed("web3_clientVersion", self.inner.client_version()).await
+    }
+
+    async fn accounts(&self) -> Result<Vec<Address>> {
+        self.timed("eth_accounts", self.inner.accounts()).await
+    }
+
+    async fn peer_count(&self) -> Result<u64> {
+        self.timed("net_peerCount", self.inner.peer_count()).await
+    }
+
+    async fn sync_status(&self) -> Result<Option<(u64, u64)>> {
+        self.timed("eth_syncing", self.inner.sync_status()).await
+    }
+
+    async fn get_block(&self, number: u64) -> Result<Option<RawBlock>> {
+        self.timed("eth_getBlockByNumber", self.inner.get_block(number)).await
+    }
+
+    async fn get_block_header(&self, number: u64) -> Result<Option<RawBlock>> {
+        self.timed("eth_getBlockByNumber", self.inner.get_block_header(number)).await
+    }
+
+    async fn get_tagged_block(&self, tag: &'static str) -> Result<Option<RawBlock>> {
+        self.timed("eth_getBlockByNumber", self.inner.get_tagged_block(tag)).await
+    }
+
+    async fn get_receipt(&self, hash: B256) -> Result<Option<AnyTransactionReceipt>> {
+        self.timed("eth_getTransactionReceipt", self.inner.get_receipt(hash)).await
+    }
+
+    async fn get_balance(&self, address: Address, block: Option<u64>) -> Result<U256> {
+        self.timed("eth_getBalance", self.inner.get_balance(address, block)).await
+    }
+
+    async fn get_code(&self, address: Address) -> Result<Bytes> {
+        self.timed("eth_getCode", self.inner.get_code(address)).await
+    }
+
+    async fn call(&self, request: TransactionRequest, block: Option<u64>) -> Result<Bytes> {
+        self.timed("eth_call", self.inner.call(request, block)).await
+    }
+
+    async fn get_storage_at(&self, address: Address, slot: U256, block: Option<u64>) -> Result<B256> {
+        self.timed("eth_getStorageAt", self.inner.get_storage_at(address, slot, block))
+            .await
+    }
+
+    async fn debug_trace_transaction(&self, hash: B256) -> Result<Vec<TraceFrame>> {
+        self.timed("debug_traceTransaction", self.inner.debug_trace_transaction(hash))
+            .await
+    }
+
+    async fn subscribe_blocks(&self) -> Result<mpsc::Receiver<Block>> {
+        self.timed("eth_subscribe", self.inner.subscribe_blocks()).await
+    }
+
+    fn supports_subscriptions(&self) -> bool {
+        self.inner.supports_subscriptions()
+    }
+
+    fn endpoint_name(&self) -> String {
+        self.inner.endpoint_name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timings_drain() {
+        let timings = RpcTimings::default();
+        timings.record("a", "eth_call", Duration::from_millis(10), true);
+        timings.record("b", "eth_call", Duration::from_millis(30), false);
+        timings.record("a", "eth_blockNumber", Duration::from_millis(5), true);
+
+        // Only the connected endpoint's calls feed the latency chart
+        let drained = timings.drain("a");
+        assert_eq!(drained, vec![("eth_blockNumber", 5.0), ("eth_call", 10.0)]);
+        assert!(timings.drain("b").is_empty());
+
+        // Per-endpoint counters survive the drain
+        let rows = timings.rows();
+        assert_eq!(rows.len(), 3);
+        assert_eq!((rows[1].endpoint.as_str(), rows[1].method.as_str()), ("a", "eth_call"));
+        assert_eq!(rows[1].p50, Some(10.0));
+        assert_eq!((rows[2].calls, rows[2].errors, rows[2].p99), (1, 1, None));
+    }
+}
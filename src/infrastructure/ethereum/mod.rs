@@ -1,6 +1,20 @@
 //! Ethereum infrastructure - Alloy provider implementations
 
+mod auth;
+mod budgeted;
+mod http;
+mod instrumented;
 mod provider;
+mod quorum;
+#[cfg(test)]
+pub(crate) mod stub;
 pub(crate) mod types;
 
-pub use provider::{create_provider, EthereumProvider, ProviderConfig, RawBlock, RawTransaction};
+pub use auth::{parse_jwt_secret, EndpointAuth};
+pub use budgeted::{BudgetedProvider, SharedBudget};
+pub use http::{rate_limited, rpc_error_response};
+pub use instrumented::{InstrumentedProvider, RpcTimings};
+pub use quorum::{QuorumLog, QuorumProvider};
+pub use provider::{
+    create_provider, receipt_l1_fee, EthereumProvider, ProviderConfig, RawBlock, RawTransaction,
+};
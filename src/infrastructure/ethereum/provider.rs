@@ -6,18 +6,25 @@
 
 use std::path::PathBuf;
 
-use alloy::network::Ethereum;
+use alloy::eips::BlockId;
+use alloy::network::{AnyTransactionReceipt, Ethereum};
 use alloy::primitives::{Address, Bytes, B256, U256};
 use alloy::providers::{
     fillers::{BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller},
-    Identity, Provider, ProviderBuilder, RootProvider,
+    Identity, Provider, ProviderBuilder, RootProvider, WsConnect,
 };
 use alloy::rpc::types::trace::geth::{GethDebugTracingOptions, GethTrace};
-use alloy::rpc::types::{Block, TransactionReceipt, TransactionRequest};
+use alloy::rpc::types::{Block, TransactionRequest};
+use alloy::transports::Authorization;
 use anyhow::{Context, Result};
 use futures::StreamExt;
+use op_alloy::rpc_types::L1BlockInfo;
 use tokio::sync::mpsc;
 
+use crate::domain::delegation;
+use crate::domain::optimism::{DepositInfo, L1Fee, DEPOSIT_TX_TYPE};
+use crate::infrastructure::ethereum::auth::EndpointAuth;
+use crate::infrastructure::ethereum::http::HttpTransport;
 use crate::infrastructure::ethereum::types::convert_trace_frames;
 use crate::infrastructure::runtime::TraceFrame;
 
@@ -31,6 +38,9 @@ pub struct RawBlock {
     pub gas_used: u64,
     pub gas_limit: u64,
     pub base_fee_per_gas: Option<u64>,
+    /// EIP-4844 header fields, absent before Cancun and on most L2s
+    pub blob_gas_used: Option<u64>,
+    pub excess_blob_gas: Option<u64>,
     pub miner: String,
     pub transactions: Vec<RawTransaction>,
 }
@@ -46,15 +56,22 @@ pub struct RawTransaction {
     pub gas: u64,
     pub nonce: u64,
     pub tx_type: Option<u8>,
+    /// Blob commitments of a type 3 transaction
+    pub blob_versioned_hashes: Vec<String>,
+    pub max_fee_per_blob_gas: Option<u128>,
+    /// EIP-7702 authorizations of a type 4 transaction
+    pub authorization_list: Vec<delegation::Authorization>,
+    /// OP Stack deposit fields of a type 0x7e transaction
+    pub deposit: Option<DepositInfo>,
 }
 
 /// Provider configuration
 #[derive(Debug, Clone)]
 pub enum ProviderConfig {
     /// HTTP JSON-RPC endpoint
-    Http(String),
+    Http(String, EndpointAuth),
     /// WebSocket endpoint
-    WebSocket(String),
+    WebSocket(String, EndpointAuth),
     /// IPC socket path (Unix only)
     #[cfg(unix)]
     Ipc(PathBuf),
@@ -64,8 +81,8 @@ impl ProviderConfig {
     /// Get display name for this endpoint
     pub fn display(&self) -> String {
         match self {
-            ProviderConfig::Http(url) => url.clone(),
-            ProviderConfig::WebSocket(url) => url.clone(),
+            ProviderConfig::Http(url, _) => url.clone(),
+            ProviderConfig::WebSocket(url, _) => url.clone(),
             #[cfg(unix)]
             ProviderConfig::Ipc(path) => path.display().to_string(),
         }
@@ -73,7 +90,7 @@ impl ProviderConfig {
 
     /// Check if this is a WebSocket endpoint
     pub fn is_websocket(&self) -> bool {
-        matches!(self, ProviderConfig::WebSocket(_))
+        matches!(self, ProviderConfig::WebSocket(..))
     }
 }
 
@@ -86,6 +103,9 @@ pub trait EthereumProvider: Send + Sync + 'static {
     /// Get the current block number
     async fn block_number(&self) -> Result<u64>;
 
+    /// Get the chain id (eth_chainId)
+    async fn chain_id(&self) -> Result<u64>;
+
     /// Get client version (for node detection)
     async fn client_version(&self) -> Result<String>;
 
@@ -95,23 +115,32 @@ pub trait EthereumProvider: Send + Sync + 'static {
     /// Get peer count
     async fn peer_count(&self) -> Result<u64>;
 
-    /// Get sync status (returns None if not syncing, Some(progress) if syncing)
-    async fn sync_progress(&self) -> Result<Option<f64>>;
+    /// Get sync status (returns None if not syncing, Some((current, highest)) if syncing)
+    async fn sync_status(&self) -> Result<Option<(u64, u64)>>;
 
     /// Get a block by number with full transactions (chain-agnostic raw format)
     async fn get_block(&self, number: u64) -> Result<Option<RawBlock>>;
 
-    /// Get transaction receipt
-    async fn get_receipt(&self, hash: B256) -> Result<Option<TransactionReceipt>>;
+    /// Get a block by number without transactions, for when only the header is needed
+    async fn get_block_header(&self, number: u64) -> Result<Option<RawBlock>>;
+
+    /// Get the block at a tag such as "safe" or "finalized", without transactions
+    async fn get_tagged_block(&self, tag: &'static str) -> Result<Option<RawBlock>>;
+
+    /// Get transaction receipt, keeping chain-specific fields such as OP Stack L1 fees
+    async fn get_receipt(&self, hash: B256) -> Result<Option<AnyTransactionReceipt>>;
 
-    /// Get account balance
-    async fn get_balance(&self, address: Address) -> Result<U256>;
+    /// Get account balance at `block`, or at the latest block
+    async fn get_balance(&self, address: Address, block: Option<u64>) -> Result<U256>;
 
-    /// Execute a call (eth_call)
-    async fn call(&self, request: TransactionRequest) -> Result<Bytes>;
+    /// Get the code deployed at an address
+    async fn get_code(&self, address: Address) -> Result<Bytes>;
 
-    /// Get storage at a specific slot
-    async fn get_storage_at(&self, address: Address, slot: U256) -> Result<B256>;
+    /// Execute a call (eth_call) against `block`, or the latest block
+    async fn call(&self, request: TransactionRequest, block: Option<u64>) -> Result<Bytes>;
+
+    /// Get storage at a specific slot at `block`, or at the latest block
+    async fn get_storage_at(&self, address: Address, slot: U256, block: Option<u64>) -> Result<B256>;
 
     /// Debug trace transaction (for trace view)
     async fn debug_trace_transaction(&self, hash: B256) -> Result<Vec<TraceFrame>>;
@@ -176,17 +205,30 @@ pub enum AlloyProvider {
 /// Create a provider from configuration
 pub async fn create_provider(config: ProviderConfig) -> Result<Box<dyn EthereumProvider>> {
     match config {
-        ProviderConfig::Http(url) => {
+        ProviderConfig::Http(url, auth) => {
             let rpc_url = url.parse().context("Invalid HTTP URL")?;
-            let provider = ProviderBuilder::new().connect_http(rpc_url);
+            // Reject malformed headers up front rather than on every request
+            auth.header_map()?;
+            let provider =
+                ProviderBuilder::new().connect_client(HttpTransport::new(rpc_url, auth).into_client());
             Ok(Box::new(AlloyProvider::Http {
                 provider,
                 endpoint: url,
             }))
         }
-        ProviderConfig::WebSocket(url) => {
+        ProviderConfig::WebSocket(url, auth) => {
+            // The handshake is signed once and replayed on reconnect
+            if auth.jwt_secret.is_some() || !auth.headers.is_empty() {
+                anyhow::bail!("WebSocket endpoints support basic auth only, not JWT secrets or headers");
+            }
+            // Credentials in the URL still work when none are configured
+            let authorization = auth.authorization().or_else(|| {
+                url.parse()
+                    .ok()
+                    .and_then(|url| Authorization::extract_from_url(&url))
+            });
             let provider = ProviderBuilder::new()
-                .connect(&url)
+                .connect_ws(WsConnect::new(url.clone()).with_auth_opt(authorization))
                 .await
                 .context("Failed to create WebSocket provider")?;
             Ok(Box::new(AlloyProvider::WebSocket {
@@ -230,6 +272,10 @@ impl EthereumProvider for AlloyProvider {
         Ok(impl_provider_method!(self, get_block_number)?)
     }
 
+    async fn chain_id(&self) -> Result<u64> {
+        Ok(impl_provider_method!(self, get_chain_id)?)
+    }
+
     async fn client_version(&self) -> Result<String> {
         Ok(impl_provider_method!(self, get_client_version)?)
     }
@@ -257,19 +303,14 @@ impl EthereumProvider for AlloyProvider {
         Ok(result.map(|v| v.to::<u64>()).unwrap_or(0))
     }
 
-    async fn sync_progress(&self) -> Result<Option<f64>> {
+    async fn sync_status(&self) -> Result<Option<(u64, u64)>> {
         let syncing = impl_provider_method!(self, syncing)?;
         match syncing {
             alloy::rpc::types::SyncStatus::None => Ok(None),
-            alloy::rpc::types::SyncStatus::Info(info) => {
-                let current = info.current_block.to::<u64>();
-                let highest = info.highest_block.to::<u64>();
-                if highest == 0 {
-                    Ok(Some(0.0))
-                } else {
-                    Ok(Some((current as f64 / highest as f64).clamp(0.0, 1.0)))
-                }
-            }
+            alloy::rpc::types::SyncStatus::Info(info) => Ok(Some((
+                info.current_block.to::<u64>(),
+                info.highest_block.to::<u64>(),
+            ))),
         }
     }
 
@@ -302,25 +343,112 @@ impl EthereumProvider for AlloyProvider {
         Ok(Some(parse_raw_block(&json)?))
     }
 
-    async fn get_receipt(&self, hash: B256) -> Result<Option<TransactionReceipt>> {
-        Ok(impl_provider_method!(self, get_transaction_receipt, hash)?)
+    async fn get_block_header(&self, number: u64) -> Result<Option<RawBlock>> {
+        let block_num_hex = format!("0x{:x}", number);
+        let json: serde_json::Value = match self {
+            AlloyProvider::Http { provider, .. } => {
+                provider
+                    .raw_request("eth_getBlockByNumber".into(), (&block_num_hex, false))
+                    .await?
+            }
+            AlloyProvider::WebSocket { provider, .. } => {
+                provider
+                    .raw_request("eth_getBlockByNumber".into(), (&block_num_hex, false))
+                    .await?
+            }
+            #[cfg(unix)]
+            AlloyProvider::Ipc { provider, .. } => {
+                provider
+                    .raw_request("eth_getBlockByNumber".into(), (&block_num_hex, false))
+                    .await?
+            }
+        };
+
+        if json.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(parse_raw_block(&json)?))
     }
 
-    async fn get_balance(&self, address: Address) -> Result<U256> {
-        Ok(impl_provider_method!(self, get_balance, address)?)
+    async fn get_tagged_block(&self, tag: &'static str) -> Result<Option<RawBlock>> {
+        // Header only; transaction hashes are skipped by the raw parser
+        let json: serde_json::Value = match self {
+            AlloyProvider::Http { provider, .. } => {
+                provider.raw_request("eth_getBlockByNumber".into(), (tag, false)).await?
+            }
+            AlloyProvider::WebSocket { provider, .. } => {
+                provider.raw_request("eth_getBlockByNumber".into(), (tag, false)).await?
+            }
+            #[cfg(unix)]
+            AlloyProvider::Ipc { provider, .. } => {
+                provider.raw_request("eth_getBlockByNumber".into(), (tag, false)).await?
+            }
+        };
+
+        if json.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(parse_raw_block(&json)?))
     }
 
-    async fn call(&self, request: TransactionRequest) -> Result<Bytes> {
+    async fn get_receipt(&self, hash: B256) -> Result<Option<AnyTransactionReceipt>> {
+        // Raw request, as typed Ethereum receipts reject L2 types like OP deposits
+        let json: serde_json::Value = match self {
+            AlloyProvider::Http { provider, .. } => {
+                provider.raw_request("eth_getTransactionReceipt".into(), (hash,)).await?
+            }
+            AlloyProvider::WebSocket { provider, .. } => {
+                provider.raw_request("eth_getTransactionReceipt".into(), (hash,)).await?
+            }
+            #[cfg(unix)]
+            AlloyProvider::Ipc { provider, .. } => {
+                provider.raw_request("eth_getTransactionReceipt".into(), (hash,)).await?
+            }
+        };
+
+        if json.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(serde_json::from_value(json).context("Failed to parse receipt")?))
+    }
+
+    async fn get_balance(&self, address: Address, block: Option<u64>) -> Result<U256> {
+        let block = block_id(block);
+        Ok(match self {
+            AlloyProvider::Http { provider, .. } => provider.get_balance(address).block_id(block).await?,
+            AlloyProvider::WebSocket { provider, .. } => provider.get_balance(address).block_id(block).await?,
+            #[cfg(unix)]
+            AlloyProvider::Ipc { provider, .. } => provider.get_balance(address).block_id(block).await?,
+        })
+    }
+
+    async fn get_code(&self, address: Address) -> Result<Bytes> {
+        Ok(impl_provider_method!(self, get_code_at, address)?)
+    }
+
+    async fn call(&self, request: TransactionRequest, block: Option<u64>) -> Result<Bytes> {
+        let block = block_id(block);
         match self {
-            AlloyProvider::Http { provider, .. } => Ok(provider.call(request.clone()).await?),
-            AlloyProvider::WebSocket { provider, .. } => Ok(provider.call(request.clone()).await?),
+            AlloyProvider::Http { provider, .. } => Ok(provider.call(request).block(block).await?),
+            AlloyProvider::WebSocket { provider, .. } => Ok(provider.call(request).block(block).await?),
             #[cfg(unix)]
-            AlloyProvider::Ipc { provider, .. } => Ok(provider.call(request).await?),
+            AlloyProvider::Ipc { provider, .. } => Ok(provider.call(request).block(block).await?),
         }
     }
 
-    async fn get_storage_at(&self, address: Address, slot: U256) -> Result<B256> {
-        let value = impl_provider_method!(self, get_storage_at, address, slot)?;
+    async fn get_storage_at(&self, address: Address, slot: U256, block: Option<u64>) -> Result<B256> {
+        let block = block_id(block);
+        let value = match self {
+            AlloyProvider::Http { provider, .. } => provider.get_storage_at(address, slot).block_id(block).await?,
+            AlloyProvider::WebSocket { provider, .. } => {
+                provider.get_storage_at(address, slot).block_id(block).await?
+            }
+            #[cfg(unix)]
+            AlloyProvider::Ipc { provider, .. } => provider.get_storage_at(address, slot).block_id(block).await?,
+        };
         Ok(B256::from(value))
     }
 
@@ -451,6 +579,14 @@ fn parse_raw_block(json: &serde_json::Value) -> Result<RawBlock> {
         .get("baseFeePerGas")
         .and_then(|v| v.as_str())
         .map(|s| parse_hex_u64(s).unwrap_or(0));
+    let blob_gas_used = json
+        .get("blobGasUsed")
+        .and_then(|v| v.as_str())
+        .and_then(|s| parse_hex_u64(s).ok());
+    let excess_blob_gas = json
+        .get("excessBlobGas")
+        .and_then(|v| v.as_str())
+        .and_then(|s| parse_hex_u64(s).ok());
     let miner = json
         .get("miner")
         .and_then(|v| v.as_str())
@@ -474,6 +610,8 @@ fn parse_raw_block(json: &serde_json::Value) -> Result<RawBlock> {
         gas_used,
         gas_limit,
         base_fee_per_gas,
+        blob_gas_used,
+        excess_blob_gas,
         miner,
         transactions,
     })
@@ -501,6 +639,43 @@ fn parse_raw_transaction(json: &serde_json::Value) -> Option<RawTransaction> {
         .and_then(|s| parse_hex_u64(s).ok())
         .map(|n| n as u8);
 
+    let blob_versioned_hashes = json
+        .get("blobVersionedHashes")
+        .and_then(|v| v.as_array())
+        .map(|hashes| {
+            hashes
+                .iter()
+                .filter_map(|h| h.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let max_fee_per_blob_gas = json
+        .get("maxFeePerBlobGas")
+        .and_then(|v| v.as_str())
+        .and_then(|s| parse_hex_u256(s).ok())
+        .map(|fee| fee.saturating_to::<u128>());
+    let authorization_list = json
+        .get("authorizationList")
+        .and_then(|v| v.as_array())
+        .map(|list| list.iter().filter_map(parse_authorization).collect())
+        .unwrap_or_default();
+    let deposit = (tx_type == Some(DEPOSIT_TX_TYPE)).then(|| DepositInfo {
+        source_hash: json
+            .get("sourceHash")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        mint: json
+            .get("mint")
+            .and_then(|v| v.as_str())
+            .and_then(|s| parse_hex_u256(s).ok())
+            .map_or(0, |mint| mint.saturating_to::<u128>()),
+        is_system_tx: json
+            .get("isSystemTx")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_default(),
+    });
+
     Some(RawTransaction {
         hash,
         from,
@@ -510,9 +685,45 @@ fn parse_raw_transaction(json: &serde_json::Value) -> Option<RawTransaction> {
         gas,
         nonce,
         tx_type,
+        blob_versioned_hashes,
+        max_fee_per_blob_gas,
+        authorization_list,
+        deposit,
+    })
+}
+
+/// Parse one authorization list entry and recover who signed it
+fn parse_authorization(json: &serde_json::Value) -> Option<delegation::Authorization> {
+    use alloy::eips::eip7702::SignedAuthorization;
+
+    let signed: SignedAuthorization = serde_json::from_value(json.clone()).ok()?;
+    Some(delegation::Authorization {
+        chain_id: signed.chain_id().saturating_to(),
+        delegate: format!("{:#x}", signed.address()),
+        nonce: signed.nonce(),
+        authority: signed
+            .recover_authority()
+            .ok()
+            .map(|authority| format!("{authority:#x}")),
+    })
+}
+
+/// L1 data fee of an OP Stack receipt; None on chains without one
+pub fn receipt_l1_fee(receipt: &AnyTransactionReceipt) -> Option<L1Fee> {
+    let info: L1BlockInfo = receipt.other.deserialize_as().ok()?;
+    Some(L1Fee {
+        fee: info.l1_fee?,
+        gas_used: info.l1_gas_used,
+        base_fee_scalar: info.l1_base_fee_scalar,
+        blob_base_fee_scalar: info.l1_blob_base_fee_scalar,
     })
 }
 
+/// A block number, or "latest" when none is given
+fn block_id(block: Option<u64>) -> BlockId {
+    block.map(BlockId::number).unwrap_or_else(BlockId::latest)
+}
+
 /// Parse hex string to u64
 fn parse_hex_u64(s: &str) -> Result<u64> {
     let s = s.strip_prefix("0x").unwrap_or(s);
@@ -530,3 +741,131 @@ fn parse_hex_u256(s: &str) -> Result<U256> {
     let bytes = hex::decode(&padded).context("Failed to decode hex")?;
     Ok(U256::from_be_slice(&bytes))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_blob_fields() {
+        let json = serde_json::json!({
+            "number": "0x10",
+            "hash": "0xb1",
+            "parentHash": "0xb0",
+            "timestamp": "0x6800",
+            "gasUsed": "0x5208",
+            "gasLimit": "0x1c9c380",
+            "baseFeePerGas": "0x7",
+            "blobGasUsed": "0xc0000",
+            "excessBlobGas": "0x4b00000",
+            "miner": "0x0000000000000000000000000000000000000001",
+            "transactions": [{
+                "hash": "0x01",
+                "from": "0x0000000000000000000000000000000000000002",
+                "to": "0x0000000000000000000000000000000000000003",
+                "value": "0x0",
+                "input": "0x",
+                "gas": "0x5208",
+                "nonce": "0x1",
+                "type": "0x3",
+                "maxFeePerBlobGas": "0x3b9aca00",
+                "blobVersionedHashes": ["0x01aa", "0x01bb"]
+            }]
+        });
+        let block = parse_raw_block(&json).unwrap();
+        assert_eq!(block.blob_gas_used, Some(786_432));
+        assert_eq!(block.excess_blob_gas, Some(78_643_200));
+        let tx = &block.transactions[0];
+        assert_eq!(tx.tx_type, Some(3));
+        assert_eq!(tx.max_fee_per_blob_gas, Some(1_000_000_000));
+        assert_eq!(tx.blob_versioned_hashes, vec!["0x01aa", "0x01bb"]);
+    }
+
+    #[test]
+    fn test_parse_deposit_and_l1_fee() {
+        let json = serde_json::json!({
+            "hash": "0x7e",
+            "from": "0xdeaddeaddeaddeaddeaddeaddeaddeaddead0001",
+            "to": "0x4200000000000000000000000000000000000015",
+            "type": "0x7e",
+            "sourceHash": "0x5b8e",
+            "mint": "0xde0b6b3a7640000",
+            "isSystemTx": false
+        });
+        let deposit = parse_raw_transaction(&json).unwrap().deposit.unwrap();
+        assert_eq!(deposit.source_hash, "0x5b8e");
+        assert_eq!(deposit.mint, 1_000_000_000_000_000_000);
+        assert!(!deposit.is_system_tx);
+
+        // Receipt shape as returned by op-geth after Ecotone
+        let mut json = serde_json::json!({
+            "type": "0x2",
+            "status": "0x1",
+            "cumulativeGasUsed": "0x5208",
+            "logs": [],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "transactionHash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "transactionIndex": "0x1",
+            "blockHash": "0x0000000000000000000000000000000000000000000000000000000000000002",
+            "blockNumber": "0x10",
+            "from": "0x0000000000000000000000000000000000000002",
+            "to": "0x0000000000000000000000000000000000000003",
+            "gasUsed": "0x5208",
+            "effectiveGasPrice": "0x3e8",
+            "contractAddress": null,
+            "l1GasPrice": "0x3b9aca00",
+            "l1GasUsed": "0x640",
+            "l1Fee": "0x2540be400",
+            "l1BaseFeeScalar": "0x558",
+            "l1BlobBaseFee": "0x1",
+            "l1BlobBaseFeeScalar": "0xc5fc5"
+        });
+        let receipt: AnyTransactionReceipt = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(
+            receipt_l1_fee(&receipt),
+            Some(L1Fee {
+                fee: 10_000_000_000,
+                gas_used: Some(1_600),
+                base_fee_scalar: Some(1_368),
+                blob_base_fee_scalar: Some(810_949),
+            })
+        );
+
+        // Deposits pay no L1 fee, and their receipt type is unknown to Ethereum receipts
+        let fields = json.as_object_mut().unwrap();
+        fields.retain(|key, _| !key.starts_with("l1"));
+        fields.insert("type".into(), "0x7e".into());
+        fields.insert("depositNonce".into(), "0x2a".into());
+        let receipt: AnyTransactionReceipt = serde_json::from_value(json).unwrap();
+        assert_eq!(receipt_l1_fee(&receipt), None);
+    }
+
+    #[test]
+    fn test_parse_authorization_list() {
+        let json = serde_json::json!({
+            "hash": "0x04",
+            "from": "0x0000000000000000000000000000000000000002",
+            "to": "0x0000000000000000000000000000000000000002",
+            "type": "0x4",
+            "authorizationList": [
+                {
+                    "chainId": "0x2105",
+                    "address": "0x000000004F43C49e93C970E84001853a70923B03",
+                    "nonce": "0x0",
+                    "r": "0xb3fdb76993ec6787313ab8b54129200032dfb9ce683fa9f7693129421e6a3185",
+                    "s": "0x210b3350107a5687b532a346a90e7cc9a799b995743e2b79698bedba7bd779ae",
+                    "v": "0x1b",
+                    "yParity": "0x0"
+                },
+                { "chainId": "0x1", "address": "0x00" }
+            ]
+        });
+        let tx = parse_raw_transaction(&json).unwrap();
+        assert_eq!(tx.authorization_list.len(), 1);
+        let auth = &tx.authorization_list[0];
+        assert_eq!(auth.chain_id, 8453);
+        assert_eq!(auth.delegate, "0x000000004f43c49e93c970e84001853a70923b03");
+        assert_eq!(auth.nonce, 0);
+        assert_eq!(auth.authority.as_deref(), Some("0xb187803a4ac9c5a498e470aab82de203f5870ab8"));
+    }
+}
@@ -0,0 +1,338 @@
+//! Provider that sends reads to several endpoints and compares the answers
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use alloy::network::{AnyTransactionReceipt, ReceiptResponse};
+use alloy::primitives::{Address, Bytes, B256, U256};
+use alloy::rpc::types::{Block, TransactionRequest};
+use anyhow::{bail, Result};
+use futures::future::join_all;
+use tokio::sync::mpsc;
+
+use super::budgeted::{BudgetedProvider, SharedBudget};
+use super::provider::{EthereumProvider, ProviderConfig, RawBlock};
+use crate::domain::quorum::{QuorumReport, QuorumResponse};
+use crate::infrastructure::runtime::TraceFrame;
+
+type ReadFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// Reports of quorum reads since the last drain
+#[derive(Debug, Clone, Default)]
+pub struct QuorumLog {
+    inner: Arc<Mutex<Vec<QuorumReport>>>,
+}
+
+impl QuorumLog {
+    fn record(&self, report: QuorumReport) {
+        if let Ok(mut reports) = self.inner.lock() {
+            reports.push(report);
+        }
+    }
+
+    pub fn drain(&self) -> Vec<QuorumReport> {
+        self.inner
+            .lock()
+            .map(|mut reports| std::mem::take(&mut *reports))
+            .unwrap_or_default()
+    }
+}
+
+/// Balance, storage, call and receipt reads go to every member and return the
+/// majority answer; everything else is served by the first member. State reads
+/// are pinned to the newest block every member has, so members at different
+/// heads are not counted as disagreeing.
+pub struct QuorumProvider {
+    members: Vec<(String, Box<dyn EthereumProvider>)>,
+    /// Endpoints that could not be connected, with the error
+    offline: Vec<(String, String)>,
+    log: QuorumLog,
+}
+
+impl QuorumProvider {
+    /// Connect to every endpoint, each charging its own budget; at least two
+    /// must be reachable
+    pub async fn connect(configs: Vec<(ProviderConfig, SharedBudget)>, log: QuorumLog) -> Result<Self> {
+        let connecting = configs.into_iter().map(|(config, budget)| async move {
+            let name = config.display();
+            (name, BudgetedProvider::connect(config, &budget).await)
+        });
+        let mut members = Vec::new();
+        let mut offline = Vec::new();
+        for (name, result) in join_all(connecting).await {
+            match result {
+                Ok(provider) => members.push((name, provider)),
+                Err(err) => offline.push((name, format!("{err:#}"))),
+            }
+        }
+        if members.len() < 2 {
+            bail!(
+                "quorum reads need two reachable endpoints, {} connected",
+                members.len()
+            );
+        }
+        Ok(Self {
+            members,
+            offline,
+            log,
+        })
+    }
+
+    /// Every member, reachable or not
+    pub fn endpoints(&self) -> Vec<String> {
+        self.members
+            .iter()
+            .map(|(name, _)| name.clone())
+            .chain(self.offline.iter().map(|(name, _)| name.clone()))
+            .collect()
+    }
+
+    fn primary(&self) -> &dyn EthereumProvider {
+        self.members[0].1.as_ref()
+    }
+
+    /// `block` if given, otherwise the lowest head among the members that answer
+    async fn pin(&self, block: Option<u64>) -> Result<u64> {
+        if let Some(block) = block {
+            return Ok(block);
+        }
+        let heads = join_all(self.members.iter().map(|(_, provider)| provider.block_number())).await;
+        let lowest = heads.iter().filter_map(|head| head.as_ref().ok()).min().copied();
+        match lowest {
+            Some(block) => Ok(block),
+            None => heads.into_iter().next().unwrap_or_else(|| bail!("no quorum members")),
+        }
+    }
+
+    /// Ask every member, record the comparison and return the majority answer,
+    /// or the first member's when there is none
+    async fn read<T, K, F>(&self, method: &str, params: String, key: K, call: F) -> Result<T>
+    where
+        K: Fn(&T) -> String,
+        F: for<'a> Fn(&'a dyn EthereumProvider) -> ReadFuture<'a, T>,
+    {
+        let results = join_all(self.members.iter().map(|(_, provider)| call(provider.as_ref()))).await;
+
+        let responses = self
+            .members
+            .iter()
+            .zip(&results)
+            .map(|((name, _), result)| QuorumResponse {
+                endpoint: name.clone(),
+                value: result.as_ref().map(&key).map_err(|err| format!("{err:#}")),
+            })
+            .chain(self.offline.iter().map(|(name, err)| QuorumResponse {
+                endpoint: name.clone(),
+                value: Err(err.clone()),
+            }))
+            .collect();
+        let report = QuorumReport::new(method, params, responses, now_secs());
+        let majority = report.majority.clone();
+        self.log.record(report);
+
+        let mut results = results;
+        let chosen = majority
+            .and_then(|value| {
+                results
+                    .iter()
+                    .position(|result| result.as_ref().is_ok_and(|answer| key(answer) == value))
+            })
+            .unwrap_or(0);
+        results.swap_remove(chosen)
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumProvider for QuorumProvider {
+    async fn block_number(&self) -> Result<u64> {
+        self.primary().block_number().await
+    }
+
+    async fn chain_id(&self) -> Result<u64> {
+        self.primary().chain_id().await
+    }
+
+    async fn client_version(&self) -> Result<String> {
+        self.primary().client_version().await
+    }
+
+    async fn accounts(&self) -> Result<Vec<Address>> {
+        self.primary().accounts().await
+    }
+
+    async fn peer_count(&self) -> Result<u64> {
+        self.primary().peer_count().await
+    }
+
+    async fn sync_status(&self) -> Result<Option<(u64, u64)>> {
+        self.primary().sync_status().await
+    }
+
+    async fn get_block(&self, number: u64) -> Result<Option<RawBlock>> {
+        self.primary().get_block(number).await
+    }
+
+    async fn get_block_header(&self, number: u64) -> Result<Option<RawBlock>> {
+        self.primary().get_block_header(number).await
+    }
+
+    async fn get_tagged_block(&self, tag: &'static str) -> Result<Option<RawBlock>> {
+        self.primary().get_tagged_block(tag).await
+    }
+
+    async fn get_receipt(&self, hash: B256) -> Result<Option<AnyTransactionReceipt>> {
+        self.read(
+            "eth_getTransactionReceipt",
+            format!("{hash:#x}"),
+            |receipt: &Option<AnyTransactionReceipt>| receipt.as_ref().map(receipt_key).unwrap_or_default(),
+            |provider| provider.get_receipt(hash),
+        )
+        .await
+    }
+
+    async fn get_balance(&self, address: Address, block: Option<u64>) -> Result<U256> {
+        let block = self.pin(block).await?;
+        self.read(
+            "eth_getBalance",
+            format!("{address:#x} @{block}"),
+            |balance: &U256| balance.to_string(),
+            |provider| provider.get_balance(address, Some(block)),
+        )
+        .await
+    }
+
+    async fn get_code(&self, address: Address) -> Result<Bytes> {
+        self.primary().get_code(address).await
+    }
+
+    async fn call(&self, request: TransactionRequest, block: Option<u64>) -> Result<Bytes> {
+        let block = self.pin(block).await?;
+        let to = request
+            .to
+            .and_then(|to| to.to().copied())
+            .map(|to| format!("{to:#x}"))
+            .unwrap_or_default();
+        let data = request.input.input().cloned().unwrap_or_default();
+        let selector = data.get(..4).map(hex::encode).unwrap_or_default();
+        self.read(
+            "eth_call",
+            format!("{to} 0x{selector} @{block}"),
+            |output: &Bytes| format!("{output}"),
+            |provider| provider.call(request.clone(), Some(block)),
+        )
+        .await
+    }
+
+    async fn get_storage_at(&self, address: Address, slot: U256, block: Option<u64>) -> Result<B256> {
+        let block = self.pin(block).await?;
+        self.read(
+            "eth_getStorageAt",
+            format!("{address:#x} {slot:#x} @{block}"),
+            |value: &B256| format!("{value:#x}"),
+            |provider| provider.get_storage_at(address, slot, Some(block)),
+        )
+        .await
+    }
+
+    async fn debug_trace_transaction(&self, hash: B256) -> Result<Vec<TraceFrame>> {
+        self.primary().debug_trace_transaction(hash).await
+    }
+
+    async fn subscribe_blocks(&self) -> Result<mpsc::Receiver<Block>> {
+        self.primary().subscribe_blocks().await
+    }
+
+    fn supports_subscriptions(&self) -> bool {
+        self.primary().supports_subscriptions()
+    }
+
+    fn endpoint_name(&self) -> String {
+        format!("quorum({})", self.members.len() + self.offline.len())
+    }
+}
+
+/// What members must agree on for a receipt: the outcome, not how each client
+/// serializes the rest of the object
+fn receipt_key(receipt: &AnyTransactionReceipt) -> String {
+    let logs: Vec<String> = receipt
+        .inner
+        .logs()
+        .iter()
+        .map(|log| format!("{:#x} {:?} {}", log.address(), log.topics(), log.data().data))
+        .collect();
+    format!("{} {} [{}]", receipt.inner.status(), receipt.inner.gas_used, logs.join(", "))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::ethereum::stub::StubProvider;
+
+    fn member(name: &str, head: u64, balances: &[(u64, u64)]) -> (String, Box<dyn EthereumProvider>) {
+        let stub = StubProvider {
+            head: Some(head),
+            balances: balances.iter().map(|(block, value)| (*block, U256::from(*value))).collect(),
+            ..StubProvider::named(name)
+        };
+        (name.to_string(), Box::new(stub))
+    }
+
+    fn quorum(members: Vec<(String, Box<dyn EthereumProvider>)>) -> QuorumProvider {
+        QuorumProvider {
+            members,
+            offline: vec![("d".to_string(), "connection refused".to_string())],
+            log: QuorumLog::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reads_pinned_to_common_block() {
+        // "a" is one block ahead; at the block everyone has, all three agree
+        let provider = quorum(vec![
+            member("a", 101, &[(100, 5), (101, 6)]),
+            member("b", 100, &[(100, 5)]),
+            member("c", 100, &[(100, 5)]),
+        ]);
+        let balance = provider.get_balance(Address::ZERO, None).await.unwrap();
+        assert_eq!(balance, U256::from(5));
+
+        let report = provider.log.drain().remove(0);
+        assert!(report.params.ends_with("@100"));
+        assert_eq!(report.majority.as_deref(), Some("5"));
+        let divergent: Vec<&str> = report.divergent().iter().map(|r| r.endpoint.as_str()).collect();
+        assert_eq!(divergent, vec!["d"]);
+    }
+
+    #[tokio::test]
+    async fn test_read_returns_majority_and_reports_divergence() {
+        let provider = quorum(vec![
+            member("a", 100, &[(100, 7)]),
+            member("b", 100, &[(100, 5)]),
+            member("c", 100, &[(100, 5)]),
+        ]);
+        // 2 of 4 members is not a majority: the first member's answer is returned
+        assert_eq!(provider.get_balance(Address::ZERO, Some(100)).await.unwrap(), U256::from(7));
+        let report = provider.log.drain().remove(0);
+        assert_eq!(report.majority, None);
+        assert_eq!(report.divergent().len(), 4);
+
+        let provider = QuorumProvider {
+            offline: Vec::new(),
+            ..provider
+        };
+        assert_eq!(provider.get_balance(Address::ZERO, Some(100)).await.unwrap(), U256::from(5));
+        let report = provider.log.drain().remove(0);
+        assert_eq!(report.majority.as_deref(), Some("5"));
+        let divergent: Vec<&str> = report.divergent().iter().map(|r| r.endpoint.as_str()).collect();
+        assert_eq!(divergent, vec!["a"]);
+    }
+}
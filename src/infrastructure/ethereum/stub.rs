@@ -0,0 +1,147 @@
+//! Scriptable in-memory provider for unit tests
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use alloy::network::AnyTransactionReceipt;
+use alloy::primitives::{Address, Bytes, B256, U256};
+use alloy::rpc::json_rpc::ErrorPayload;
+use serde_json::value::RawValue;
+use alloy::rpc::types::{Block, TransactionRequest};
+use alloy::transports::TransportError;
+use anyhow::{anyhow, Result};
+use tokio::sync::mpsc;
+
+use crate::infrastructure::ethereum::{EthereumProvider, RawBlock};
+use crate::infrastructure::runtime::TraceFrame;
+
+type CallFn = Box<dyn Fn(&TransactionRequest) -> Result<Bytes> + Send + Sync>;
+
+/// Answers from fixed values; anything not set fails like an unsupported method
+#[derive(Default)]
+pub struct StubProvider {
+    pub name: String,
+    pub head: Option<u64>,
+    pub chain_id: Option<u64>,
+    pub balance: Option<U256>,
+    /// Balances at given blocks, answered instead of `balance` for reads pinned to them
+    pub balances: HashMap<u64, U256>,
+    pub blocks: HashMap<u64, RawBlock>,
+    pub receipts: HashMap<B256, AnyTransactionReceipt>,
+    pub on_call: Option<CallFn>,
+    /// Number of eth_call requests served
+    pub calls: AtomicUsize,
+}
+
+impl StubProvider {
+    pub fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_call(
+        mut self,
+        on_call: impl Fn(&TransactionRequest) -> Result<Bytes> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_call = Some(Box::new(on_call));
+        self
+    }
+}
+
+/// A JSON-RPC error answer, as nodes send for reverts and unsupported methods
+pub fn error_response(code: i64, message: &'static str) -> anyhow::Error {
+    TransportError::ErrorResp(ErrorPayload::<Box<RawValue>> {
+        code,
+        message: message.into(),
+        data: None,
+    })
+    .into()
+}
+
+fn unsupported<T>(method: &str) -> Result<T> {
+    Err(anyhow!("{method} not stubbed"))
+}
+
+#[async_trait::async_trait]
+impl EthereumProvider for StubProvider {
+    async fn block_number(&self) -> Result<u64> {
+        self.head.ok_or_else(|| anyhow!("eth_blockNumber not stubbed"))
+    }
+
+    async fn chain_id(&self) -> Result<u64> {
+        self.chain_id.ok_or_else(|| anyhow!("eth_chainId not stubbed"))
+    }
+
+    async fn client_version(&self) -> Result<String> {
+        Ok(format!("stub/{}", self.name))
+    }
+
+    async fn accounts(&self) -> Result<Vec<Address>> {
+        Ok(Vec::new())
+    }
+
+    async fn peer_count(&self) -> Result<u64> {
+        Ok(0)
+    }
+
+    async fn sync_status(&self) -> Result<Option<(u64, u64)>> {
+        Ok(None)
+    }
+
+    async fn get_block(&self, number: u64) -> Result<Option<RawBlock>> {
+        Ok(self.blocks.get(&number).cloned())
+    }
+
+    async fn get_block_header(&self, number: u64) -> Result<Option<RawBlock>> {
+        Ok(self.blocks.get(&number).cloned())
+    }
+
+    async fn get_tagged_block(&self, _tag: &'static str) -> Result<Option<RawBlock>> {
+        unsupported("eth_getBlockByNumber(tag)")
+    }
+
+    async fn get_receipt(&self, hash: B256) -> Result<Option<AnyTransactionReceipt>> {
+        Ok(self.receipts.get(&hash).cloned())
+    }
+
+    async fn get_balance(&self, _address: Address, block: Option<u64>) -> Result<U256> {
+        block
+            .and_then(|block| self.balances.get(&block).copied())
+            .or(self.balance)
+            .ok_or_else(|| anyhow!("eth_getBalance not stubbed"))
+    }
+
+    async fn get_code(&self, _address: Address) -> Result<Bytes> {
+        Ok(Bytes::new())
+    }
+
+    async fn call(&self, request: TransactionRequest, _block: Option<u64>) -> Result<Bytes> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        match &self.on_call {
+            Some(on_call) => on_call(&request),
+            None => unsupported("eth_call"),
+        }
+    }
+
+    async fn get_storage_at(&self, _address: Address, _slot: U256, _block: Option<u64>) -> Result<B256> {
+        unsupported("eth_getStorageAt")
+    }
+
+    async fn debug_trace_transaction(&self, _hash: B256) -> Result<Vec<TraceFrame>> {
+        unsupported("debug_traceTransaction")
+    }
+
+    async fn subscribe_blocks(&self) -> Result<mpsc::Receiver<Block>> {
+        unsupported("eth_subscribe")
+    }
+
+    fn supports_subscriptions(&self) -> bool {
+        false
+    }
+
+    fn endpoint_name(&self) -> String {
+        self.name.clone()
+    }
+}
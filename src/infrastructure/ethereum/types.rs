@@ -30,7 +30,8 @@ fn flatten_call_frame(
     let call_type = frame.typ.to_string();
     let from = format!("{:?}", frame.from);
     let to = format!("{:?}", frame.to.unwrap_or(Address::ZERO));
-    let value = wei_to_eth(frame.value.unwrap_or(U256::ZERO));
+    let value_wei = frame.value.unwrap_or(U256::ZERO);
+    let value = wei_to_eth(value_wei);
     let gas_used = frame.gas_used.to::<u64>();
 
     let (status, note) = if let Some(error) = &frame.error {
@@ -52,11 +53,16 @@ fn flatten_call_frame(
         from,
         to,
         value,
+        value_wei,
         gas_used,
         status,
         note,
         collapsed: false,
         input: Some(format!("0x{}", hex::encode(&input))),
+        output: frame
+            .output
+            .as_ref()
+            .map(|output| format!("0x{}", hex::encode(output))),
         selector,
         method: None,
         signature: None,
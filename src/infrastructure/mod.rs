@@ -10,7 +10,4 @@ pub mod ethereum;
 pub mod runtime;
 
 // Re-export types used by main.rs
-pub use abi::AbiScanner;
-pub use runtime::{
-    CallStatus, TxStatus,
-};
+pub use abi::AbiWatcher;
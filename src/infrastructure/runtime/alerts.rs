@@ -0,0 +1,332 @@
+//! Alert monitor - evaluates alert rules in the worker and runs their hooks
+
+use std::collections::BTreeMap;
+use std::sync::mpsc::Sender;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::json;
+
+use crate::domain::alerts::{AlertEngine, AlertEvent, AlertRule, BlockRef, TxSample};
+use crate::infrastructure::ethereum::{EthereumProvider, RawBlock};
+use crate::infrastructure::runtime::bridge::{BlockInfo, RuntimeEvent, TxInfo, TxStatus};
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A rule's shell command still running after this is killed
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Alert engine plus the hooks configured on its rules
+#[derive(Debug, Default)]
+pub struct AlertMonitor {
+    engine: AlertEngine,
+    http: reqwest::Client,
+}
+
+impl AlertMonitor {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        Self {
+            engine: AlertEngine::new(rules),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn set_watched(&mut self, addresses: Vec<String>) {
+        self.engine.set_watched(addresses);
+    }
+
+    pub fn reset_chain(&mut self) {
+        self.engine.reset_chain();
+    }
+
+    pub fn observe_status(
+        &mut self,
+        peer_count: Option<u32>,
+        rpc_latency_ms: Option<u64>,
+        head_lag: Option<u64>,
+    ) {
+        self.engine.observe_status(peer_count, rpc_latency_ms, head_lag);
+    }
+
+    /// Release held activity up to the `finalized` block, checking each held
+    /// block against the canonical chain. Parent hashes link contiguous held
+    /// blocks to the finalized one; only heights off that chain are fetched.
+    pub async fn observe_finalized(&mut self, provider: &dyn EthereumProvider, finalized: &RawBlock) {
+        if self.engine.finalized_head().is_some_and(|head| finalized.number <= head) {
+            return;
+        }
+        let mut canonical = BTreeMap::new();
+        // Latest block known to be canonical, with its parent
+        let mut known = Some((finalized.number, finalized.hash.clone(), finalized.parent_hash.clone()));
+        for held in self.engine.held_until(finalized.number) {
+            let hash = match &known {
+                Some((number, hash, _)) if *number == held.number => Some(hash.clone()),
+                Some((number, _, parent)) if *number == held.number + 1 => Some(parent.clone()),
+                _ => None,
+            };
+            let hash = match hash {
+                Some(hash) => hash,
+                None => match provider.get_block(held.number).await {
+                    Ok(Some(block)) => block.hash,
+                    // Left held, to be checked again at the next finalized step
+                    _ => {
+                        known = None;
+                        continue;
+                    }
+                },
+            };
+            known = hash
+                .eq_ignore_ascii_case(&held.hash)
+                .then(|| (held.number, held.hash.clone(), held.parent_hash.clone()));
+            canonical.insert(held.number, hash);
+        }
+        self.engine.observe_finalized(finalized.number, &canonical);
+    }
+
+    pub fn observe_block(&mut self, block: &BlockInfo, txs: &[TxInfo]) {
+        let samples: Vec<TxSample> = txs
+            .iter()
+            .map(|tx| TxSample {
+                from: tx.from.clone(),
+                to: tx.to.clone(),
+                reverted: match tx.status {
+                    TxStatus::Success => Some(false),
+                    TxStatus::Revert => Some(true),
+                    TxStatus::Unknown => None,
+                },
+            })
+            .collect();
+        let block_ref = BlockRef {
+            number: block.number,
+            hash: block.hash.clone(),
+            parent_hash: block.parent_hash.clone(),
+        };
+        self.engine.observe_block(block_ref, block.base_fee, &samples, now_secs());
+    }
+
+    /// Evaluate every rule, fire hooks for transitions and return them.
+    /// Hook failures and held activity dropped unfinalized are reported on
+    /// `evt_tx` as errors.
+    pub fn evaluate(&mut self, evt_tx: &Sender<RuntimeEvent>) -> Vec<AlertEvent> {
+        let dropped = self.engine.take_dropped();
+        if dropped > 0 {
+            let _ = evt_tx.send(RuntimeEvent::Error {
+                message: format!(
+                    "Watched activity of {dropped} blocks dropped before finality: too many blocks held"
+                ),
+            });
+        }
+        let events = self.engine.evaluate(now_secs());
+        for event in &events {
+            let Some(rule) = self.engine.rule(&event.rule) else {
+                continue;
+            };
+            if let Some(command) = rule.command.clone() {
+                let event = event.clone();
+                let evt_tx = evt_tx.clone();
+                tokio::spawn(async move {
+                    let result = run_command(&command, &event).await.and_then(|status| {
+                        if status.success() {
+                            Ok(())
+                        } else {
+                            Err(anyhow!("alert command for {} exited with {status}", event.rule))
+                        }
+                    });
+                    report_hook_error(&evt_tx, result);
+                });
+            }
+            if let Some(url) = rule.webhook.clone() {
+                let http = self.http.clone();
+                let event = event.clone();
+                let evt_tx = evt_tx.clone();
+                tokio::spawn(async move {
+                    report_hook_error(&evt_tx, post_webhook(&http, &url, &event).await);
+                });
+            }
+        }
+        events
+    }
+}
+
+fn report_hook_error(evt_tx: &Sender<RuntimeEvent>, result: Result<()>) {
+    if let Err(err) = result {
+        let _ = evt_tx.send(RuntimeEvent::Error {
+            message: format!("{err:#}"),
+        });
+    }
+}
+
+/// Run a rule's shell command with the event in POKE_ALERT_* variables,
+/// killing it after `COMMAND_TIMEOUT`
+async fn run_command(command: &str, event: &AlertEvent) -> Result<std::process::ExitStatus> {
+    let mut child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("POKE_ALERT", &event.rule)
+        .env("POKE_ALERT_STATE", event.state.as_str())
+        .env("POKE_ALERT_MESSAGE", &event.message)
+        .env("POKE_ALERT_DETAIL", &event.detail)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("run alert command for {}", event.rule))?;
+    match tokio::time::timeout(COMMAND_TIMEOUT, child.wait()).await {
+        Ok(status) => status.with_context(|| format!("run alert command for {}", event.rule)),
+        Err(_) => {
+            let _ = child.kill().await;
+            Err(anyhow!(
+                "alert command for {} timed out after {}s",
+                event.rule,
+                COMMAND_TIMEOUT.as_secs()
+            ))
+        }
+    }
+}
+
+async fn post_webhook(http: &reqwest::Client, url: &str, event: &AlertEvent) -> Result<()> {
+    let payload = json!({
+        "rule": event.rule,
+        "state": event.state.as_str(),
+        "message": event.message,
+        "detail": event.detail,
+        "at": event.at,
+    });
+    http.post(url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .json(&payload)
+        .send()
+        .await
+        .with_context(|| format!("post alert webhook {url}"))?
+        .error_for_status()
+        .with_context(|| format!("post alert webhook {url}"))?;
+    Ok(())
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::alerts::{AlertCondition, AlertState};
+    use crate::infrastructure::ethereum::stub::StubProvider;
+    use crate::infrastructure::runtime::block_info;
+    use crate::infrastructure::runtime::http_stub::serve;
+
+    fn event() -> AlertEvent {
+        AlertEvent {
+            rule: "peer_count_low".to_string(),
+            state: AlertState::Fired,
+            message: "Peer count critically low".to_string(),
+            detail: "peers=1".to_string(),
+            at: 1_700_000_000,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_posts_event() {
+        let (base, requests) = serve(|_| ("200 OK", String::new()));
+        let url = format!("{base}/hook");
+
+        post_webhook(&reqwest::Client::new(), &url, &event())
+            .await
+            .unwrap();
+        let request = requests.recv().unwrap();
+        assert_eq!((request.method.as_str(), request.path.as_str()), ("POST", "/hook"));
+        let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(body["rule"], "peer_count_low");
+        assert_eq!(body["state"], "fired");
+        assert_eq!(body["detail"], "peers=1");
+    }
+
+    #[tokio::test]
+    async fn test_webhook_failure_is_reported() {
+        let (url, _requests) = serve(|_| ("500 Internal Server Error", String::new()));
+        let (evt_tx, evt_rx) = std::sync::mpsc::channel();
+
+        report_hook_error(
+            &evt_tx,
+            post_webhook(&reqwest::Client::new(), &url, &event()).await,
+        );
+        match evt_rx.try_recv() {
+            Ok(RuntimeEvent::Error { message }) => assert!(message.contains("500"), "{message}"),
+            other => panic!("expected an error event, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_command_gets_event_env() {
+        let out = std::env::temp_dir().join(format!("poke-alert-{}.txt", std::process::id()));
+        let command = format!(
+            "printf '%s %s' \"$POKE_ALERT\" \"$POKE_ALERT_STATE\" > '{}'",
+            out.display()
+        );
+        assert!(run_command(&command, &event()).await.unwrap().success());
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            "peer_count_low fired"
+        );
+        let _ = std::fs::remove_file(out);
+    }
+
+    fn raw_block(number: u64, hash: &str, parent_hash: &str) -> RawBlock {
+        RawBlock {
+            number,
+            hash: hash.to_string(),
+            parent_hash: parent_hash.to_string(),
+            timestamp: 0,
+            gas_used: 0,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(1),
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            miner: String::new(),
+            transactions: Vec::new(),
+        }
+    }
+
+    fn tx_to(to: &str, block_number: u64) -> TxInfo {
+        TxInfo {
+            from: "0x111".to_string(),
+            to: to.to_string(),
+            block_number,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_polled_orphan_is_not_released() {
+        let mut rule = AlertRule::new(
+            "treasury",
+            AlertCondition::WatchedActivity(vec!["0xabc".to_string()]),
+        );
+        rule.finalized = true;
+        let mut monitor = AlertMonitor::new(vec![rule]);
+        let (evt_tx, _evt_rx) = std::sync::mpsc::channel();
+
+        // Polling saw 10, then an 11 that was later replaced, then 12 built on
+        // the replacement; the new 11 itself was never fetched
+        let block_10 = raw_block(10, "0x0a", "0x09");
+        let orphan_11 = raw_block(11, "0xdead", "0x0a");
+        let block_12 = raw_block(12, "0x0c", "0x0b");
+        monitor.observe_block(&block_info(&block_10, 1), &[tx_to("0xabc", 10)]);
+        monitor.observe_block(&block_info(&orphan_11, 1), &[tx_to("0xabc", 11)]);
+        monitor.observe_block(&block_info(&block_12, 1), &[]);
+        assert!(monitor.evaluate(&evt_tx).is_empty());
+
+        // 12 links to the canonical 11; 10 is off that link and read from the node
+        let mut stub = StubProvider::named("stub");
+        stub.blocks.insert(10, block_10);
+        monitor.observe_finalized(&stub, &block_12).await;
+        let events = monitor.evaluate(&evt_tx);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].state, AlertState::Fired);
+        assert_eq!(events[0].detail, "0xabc in block #10 (finalized)");
+    }
+}
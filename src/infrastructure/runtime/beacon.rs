@@ -0,0 +1,131 @@
+//! Beacon node polling over the standard Beacon REST API
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::domain::beacon::BeaconStatus;
+use crate::infrastructure::ethereum::EndpointAuth;
+
+const BEACON_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Read sync state, head, finality and peers; only sync state and head are required.
+/// Requests carry the credentials configured for the beacon API.
+pub async fn fetch_status(http: &reqwest::Client, base_url: &str, auth: &EndpointAuth) -> Result<BeaconStatus> {
+    let base_url = base_url.trim_end_matches('/');
+    let (syncing, headers, finality, peers, block) = tokio::join!(
+        get(http, base_url, auth, "/eth/v1/node/syncing"),
+        get(http, base_url, auth, "/eth/v1/beacon/headers"),
+        get(http, base_url, auth, "/eth/v1/beacon/states/head/finality_checkpoints"),
+        get(http, base_url, auth, "/eth/v1/node/peer_count"),
+        get(http, base_url, auth, "/eth/v1/beacon/blinded_blocks/head"),
+    );
+    let syncing = syncing?;
+    let syncing = &syncing["data"];
+    let head_slot = number(&headers?["data"][0]["header"]["message"]["slot"])
+        .context("beacon headers: missing head slot")?;
+
+    let finality = finality.ok();
+    let checkpoint = |name: &str| {
+        finality
+            .as_ref()
+            .and_then(|body| number(&body["data"][name]["epoch"]))
+    };
+    Ok(BeaconStatus {
+        head_slot,
+        sync_distance: number(&syncing["sync_distance"]).unwrap_or_default(),
+        is_syncing: syncing["is_syncing"].as_bool().unwrap_or_default(),
+        is_optimistic: syncing["is_optimistic"].as_bool().unwrap_or_default(),
+        el_offline: syncing["el_offline"].as_bool().unwrap_or_default(),
+        finalized_epoch: checkpoint("finalized"),
+        justified_epoch: checkpoint("current_justified"),
+        peers: peers
+            .ok()
+            .and_then(|body| number(&body["data"]["connected"])),
+        execution_head: block.ok().and_then(|body| {
+            number(&body["data"]["message"]["body"]["execution_payload_header"]["block_number"])
+        }),
+    })
+}
+
+async fn get(http: &reqwest::Client, base_url: &str, auth: &EndpointAuth, path: &str) -> Result<Value> {
+    http.get(format!("{base_url}{path}"))
+        .headers(auth.header_map()?)
+        .timeout(BEACON_TIMEOUT)
+        .send()
+        .await
+        .with_context(|| format!("beacon {path}"))?
+        .error_for_status()
+        .with_context(|| format!("beacon {path}"))?
+        .json()
+        .await
+        .with_context(|| format!("beacon {path}: invalid JSON"))
+}
+
+/// The Beacon API quotes integers as strings
+fn number(value: &Value) -> Option<u64> {
+    match value {
+        Value::String(text) => text.parse().ok(),
+        other => other.as_u64(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::runtime::http_stub::serve;
+
+    fn respond(path: &str) -> (&'static str, String) {
+        let body = match path {
+            "/eth/v1/node/syncing" => r#"{"data":{"head_slot":"9600010","sync_distance":"0","is_syncing":false,"is_optimistic":false,"el_offline":false}}"#,
+            "/eth/v1/beacon/headers" => r#"{"data":[{"root":"0x01","canonical":true,"header":{"message":{"slot":"9600010","proposer_index":"7"}}}]}"#,
+            "/eth/v1/beacon/states/head/finality_checkpoints" => r#"{"data":{"previous_justified":{"epoch":"299998"},"current_justified":{"epoch":"299999"},"finalized":{"epoch":"299998"}}}"#,
+            "/eth/v1/node/peer_count" => r#"{"data":{"disconnected":"3","connecting":"0","connected":"64","disconnecting":"0"}}"#,
+            "/eth/v1/beacon/blinded_blocks/head" => r#"{"version":"deneb","data":{"message":{"slot":"9600010","body":{"execution_payload_header":{"block_number":"20000105","block_hash":"0x02"}}}}}"#,
+            _ => return ("404 Not Found", String::new()),
+        };
+        ("200 OK", body.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_status_from_stub() {
+        let (url, _requests) = serve(|request| respond(&request.path));
+
+        let status = fetch_status(&reqwest::Client::new(), &url, &EndpointAuth::default())
+            .await
+            .unwrap();
+        assert_eq!(status.head_slot, 9_600_010);
+        assert_eq!(status.finalized_epoch, Some(299_998));
+        assert_eq!(status.justified_epoch, Some(299_999));
+        assert_eq!(status.peers, Some(64));
+        assert_eq!(status.execution_head, Some(20_000_105));
+        assert_eq!(status.el_lag(Some(20_000_100)), Some(5));
+        assert_eq!(status.warnings(Some(20_000_100)), vec!["execution head 5 blocks behind"]);
+        assert!(status.warnings(Some(20_000_105)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_status_sends_beacon_auth() {
+        // Behind a proxy that rejects requests without the beacon credentials
+        let (url, _requests) = serve(|request| {
+            let basic = request.header("authorization").is_some_and(|value| value.starts_with("Basic "));
+            if basic && request.header("x-api-key") == Some("key") {
+                respond(&request.path)
+            } else {
+                ("401 Unauthorized", String::new())
+            }
+        });
+        let auth = EndpointAuth {
+            headers: vec![("X-Api-Key".to_string(), "key".to_string())],
+            basic: Some(("poke".to_string(), "secret".to_string())),
+            jwt_secret: None,
+        };
+
+        assert!(fetch_status(&reqwest::Client::new(), &url, &EndpointAuth::default())
+            .await
+            .is_err());
+        let status = fetch_status(&reqwest::Client::new(), &url, &auth).await.unwrap();
+        assert_eq!(status.head_slot, 9_600_010);
+    }
+}
@@ -8,10 +8,25 @@ use std::path::PathBuf;
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::thread;
 
+use alloy::primitives::U256;
 use tokio::runtime::Runtime;
 
 use crate::domain::abi::AbiRegistry;
-use crate::infrastructure::ethereum::ProviderConfig;
+use crate::domain::alerts::AlertEvent;
+use crate::domain::beacon::BeaconStatus;
+use crate::domain::metrics::{MetricsWindow, SeriesView};
+use crate::domain::nodes::NodeComparison;
+use crate::domain::optimism::{DepositInfo, L1Fee, L1Origin};
+use crate::domain::budget::{BudgetConfig, BudgetStatus};
+use crate::domain::delegation::Authorization;
+use crate::domain::finality::FinalityHeads;
+use crate::domain::quorum::QuorumReport;
+use crate::domain::rpc_stats::RpcStatRow;
+use crate::domain::transfers::TransferSummary;
+use crate::infrastructure::abi::AbiResolver;
+use crate::infrastructure::ethereum::{EndpointAuth, ProviderConfig};
+use crate::infrastructure::runtime::alerts::AlertMonitor;
+use crate::infrastructure::runtime::tokens::TokenCatalog;
 use crate::infrastructure::runtime::worker::run_async_worker;
 
 /// Commands sent from the TUI to the async worker
@@ -21,6 +36,11 @@ pub enum RuntimeCommand {
     SwitchEndpoint { index: usize },
     /// Fetch transaction trace
     FetchTrace { tx_hash: String },
+    /// Fetch token transfers and native movements of a transaction
+    FetchTransfers {
+        tx_hash: String,
+        tokens: Vec<TokenConfig>,
+    },
     /// Fetch account balance
     FetchBalance { address: String },
     /// Fetch token balances
@@ -28,8 +48,8 @@ pub enum RuntimeCommand {
         address: String,
         tokens: Vec<TokenConfig>,
     },
-    /// Fetch storage slot
-    FetchStorage { address: String, slot: String },
+    /// Fetch storage slots (read concurrently)
+    FetchStorage { address: String, slots: Vec<String> },
     /// Resolve function selector via 4byte API
     ResolveSelector { selector: String },
     /// Resolve contract ABI via Sourcify
@@ -38,10 +58,44 @@ pub enum RuntimeCommand {
     Refresh,
     /// Reload ABI from specified roots
     ReloadAbi { roots: Vec<PathBuf> },
+    /// Addresses watched in the UI, for watched-activity alert rules
+    SetWatchedAddresses { addresses: Vec<String> },
+    /// Time range of the metrics charts
+    SetMetricsWindow { window: MetricsWindow },
+    /// Send balance/storage/call/receipt reads to every endpoint and compare them
+    SetQuorum { enabled: bool },
+    /// Start or stop polling every endpoint for the node comparison view
+    SetNodeComparison { enabled: bool },
     /// Shutdown the worker
     Shutdown,
 }
 
+/// An endpoint the worker can connect to, plus its side channels
+#[derive(Debug, Clone)]
+pub struct EndpointSpec {
+    pub provider: ProviderConfig,
+    /// Prometheus text endpoint scraped for node metrics
+    pub metrics_url: Option<String>,
+    /// Beacon REST API of the paired consensus client
+    pub beacon_url: Option<String>,
+    /// Credentials for the beacon API, separate from the RPC endpoint's
+    pub beacon_auth: EndpointAuth,
+    /// Rate limits and compute-unit budget of the provider
+    pub budget: BudgetConfig,
+}
+
+impl From<ProviderConfig> for EndpointSpec {
+    fn from(provider: ProviderConfig) -> Self {
+        Self {
+            provider,
+            metrics_url: None,
+            beacon_url: None,
+            beacon_auth: EndpointAuth::default(),
+            budget: BudgetConfig::default(),
+        }
+    }
+}
+
 /// Token configuration for balance fetching
 #[derive(Debug, Clone)]
 pub struct TokenConfig {
@@ -57,6 +111,7 @@ pub enum RuntimeEvent {
     Connected {
         endpoint: String,
         node_kind: String,
+        chain_id: Option<u64>,
         accounts: Vec<String>,
     },
     /// Node status update
@@ -75,8 +130,15 @@ pub enum RuntimeEvent {
         tx_hash: String,
         frames: Vec<TraceFrame>,
     },
+    /// Asset movements of a transaction ready
+    TransfersReady { summary: TransferSummary },
     /// Balance ready
     BalanceReady { address: String, balance: f64 },
+    /// EIP-7702 delegate of an inspected address; None when it isn't a delegated EOA
+    Delegation {
+        address: String,
+        delegate: Option<String>,
+    },
     /// Token balances ready
     TokenBalancesReady {
         address: String,
@@ -103,6 +165,36 @@ pub enum RuntimeEvent {
         abi_json: String,
         contract_name: Option<String>,
     },
+    /// Alert rule fired or resolved
+    Alert { event: AlertEvent },
+    /// Metrics series bucketed over the selected window
+    Metrics {
+        window: MetricsWindow,
+        series: Vec<SeriesView>,
+    },
+    /// Request budget of the connected endpoint; sent when pressure changes and
+    /// with each status update while the endpoint has limits
+    Budget { endpoint: String, status: BudgetStatus },
+    /// Call counts and latency percentiles per endpoint and method
+    RpcStats { rows: Vec<RpcStatRow> },
+    /// Quorum members after SetQuorum; empty once quorum reads are off
+    Quorum { endpoints: Vec<String> },
+    /// How the quorum members answered one read
+    QuorumReport { report: QuorumReport },
+    /// The worker moved to another endpoint on its own
+    Failover {
+        from: String,
+        to: String,
+        reason: String,
+    },
+    /// Safe and finalized heads, refreshed with each status update
+    Finality { heads: FinalityHeads },
+    /// Consensus client status of the connected endpoint; None while its beacon node is unreachable
+    Beacon { status: Option<BeaconStatus> },
+    /// L1 origin from the L1Block predeploy on OP Stack chains; None elsewhere
+    L1Origin { origin: Option<L1Origin> },
+    /// One side-by-side round across all configured endpoints
+    NodeComparison { comparisons: Vec<NodeComparison> },
     /// Error occurred
     Error { message: String },
 }
@@ -111,22 +203,28 @@ pub enum RuntimeEvent {
 #[derive(Debug, Clone)]
 pub struct BlockInfo {
     pub number: u64,
+    pub hash: String,
+    pub parent_hash: String,
     pub tx_count: u32,
     pub gas_used: u64,
     pub base_fee: u64,
     pub miner: String,
+    pub blob_count: u32,
+    /// Blob base fee in wei; None before Cancun
+    pub blob_base_fee: Option<u128>,
 }
 
 /// Transaction status
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum TxStatus {
     Success,
     Revert,
+    #[default]
     Unknown,
 }
 
 /// Transaction information compatible with app.rs
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct TxInfo {
     pub hash: String,
     pub from: String,
@@ -141,6 +239,21 @@ pub struct TxInfo {
     pub decoded_args: Option<Vec<DecodedArg>>,
     pub decode_error: Option<String>,
     pub block_number: u64,
+    /// Versioned hashes of the blobs a type 3 transaction carries
+    pub blob_hashes: Vec<String>,
+    pub max_fee_per_blob_gas: Option<u128>,
+    /// Wei paid for blob gas, from the receipt
+    pub blob_fee: Option<u128>,
+    /// EIP-7702 delegations set by a type 4 transaction
+    pub authorizations: Vec<Authorization>,
+    pub deposit: Option<DepositInfo>,
+    /// Wei paid for L2 execution (gas used times effective gas price), from the receipt
+    pub execution_fee: Option<u128>,
+    /// L1 data fee charged on OP Stack chains
+    pub l1_fee: Option<L1Fee>,
+    /// Lowercase addresses of the receipt's log emitters and of its indexed log
+    /// topics, such as the parties of token transfers
+    pub log_addresses: Vec<String>,
 }
 
 /// Decoded argument
@@ -166,11 +279,14 @@ pub struct TraceFrame {
     pub from: String,
     pub to: String,
     pub value: f64,
+    /// Exact transferred value, used for native balance changes
+    pub value_wei: U256,
     pub gas_used: u64,
     pub status: CallStatus,
     pub note: String,
     pub collapsed: bool,
     pub input: Option<String>,
+    pub output: Option<String>,
     pub selector: Option<String>,
     pub method: Option<String>,
     pub signature: Option<String>,
@@ -196,6 +312,22 @@ pub struct RuntimeBridge {
 impl RuntimeBridge {
     /// Create a new runtime bridge with the given endpoint configurations
     pub fn new(endpoints: Vec<ProviderConfig>) -> anyhow::Result<Self> {
+        Self::with_services(
+            endpoints.into_iter().map(EndpointSpec::from).collect(),
+            AbiResolver::new(),
+            TokenCatalog::default(),
+            AlertMonitor::default(),
+        )
+    }
+
+    /// Create a runtime bridge using a preconfigured ABI resolver, token catalog
+    /// and alert rules
+    pub fn with_services(
+        endpoints: Vec<EndpointSpec>,
+        resolver: AbiResolver,
+        tokens: TokenCatalog,
+        alerts: AlertMonitor,
+    ) -> anyhow::Result<Self> {
         let (cmd_tx, cmd_rx) = mpsc::channel::<RuntimeCommand>();
         let (evt_tx, evt_rx) = mpsc::channel::<RuntimeEvent>();
 
@@ -203,7 +335,7 @@ impl RuntimeBridge {
         thread::spawn(move || {
             let rt = Runtime::new().expect("Failed to create Tokio runtime");
             rt.block_on(async {
-                if let Err(err) = run_async_worker(endpoints, cmd_rx, evt_tx.clone()).await {
+                if let Err(err) = run_async_worker(endpoints, resolver, tokens, alerts, cmd_rx, evt_tx.clone()).await {
                     let _ = evt_tx.send(RuntimeEvent::Error {
                         message: format!("Worker exited: {:#}", err),
                     });
@@ -230,6 +362,14 @@ impl RuntimeBridge {
         events
     }
 
+    /// Wait up to `timeout` for the next event
+    pub fn recv_timeout(
+        &self,
+        timeout: std::time::Duration,
+    ) -> Result<RuntimeEvent, mpsc::RecvTimeoutError> {
+        self.evt_rx.recv_timeout(timeout)
+    }
+
     /// Try to receive a single event (non-blocking)
     pub fn try_recv(&self) -> Option<RuntimeEvent> {
         self.evt_rx.try_recv().ok()
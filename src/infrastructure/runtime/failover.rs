@@ -0,0 +1,86 @@
+//! Background health probes for endpoints the worker isn't connected to
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+
+use crate::infrastructure::ethereum::{BudgetedProvider, EthereumProvider, ProviderConfig, SharedBudget};
+
+/// A probe slower than this counts as a failure
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// What one probe saw
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProbeResult {
+    pub chain_id: u64,
+    pub head: u64,
+    /// Latency of eth_blockNumber in ms
+    pub latency_ms: f64,
+}
+
+/// A probe connection and the chain id it reported on connect
+type ProbeConnection = (Arc<dyn EthereumProvider>, u64);
+
+/// Probe connections, kept per endpoint index between probes so each probe
+/// is one request rather than a new client (and a ws/ipc handshake)
+#[derive(Clone, Default)]
+pub struct ProbePool {
+    connections: Arc<Mutex<HashMap<usize, ProbeConnection>>>,
+}
+
+impl ProbePool {
+    /// Read the head of endpoint `index`, connecting first if needed. Calls are
+    /// charged to the endpoint's budget. A failed probe drops the connection so
+    /// the next one starts fresh.
+    pub async fn probe(&self, index: usize, config: ProviderConfig, budget: SharedBudget) -> Result<ProbeResult> {
+        let result = tokio::time::timeout(PROBE_TIMEOUT, self.probe_inner(index, config, budget))
+            .await
+            .unwrap_or_else(|_| Err(anyhow!("probe timed out after {}s", PROBE_TIMEOUT.as_secs())));
+        if result.is_err() {
+            self.forget(index);
+        }
+        result
+    }
+
+    async fn probe_inner(&self, index: usize, config: ProviderConfig, budget: SharedBudget) -> Result<ProbeResult> {
+        let (provider, chain_id) = match self.cached(index) {
+            Some(cached) => cached,
+            None => {
+                let provider: Arc<dyn EthereumProvider> = Arc::from(BudgetedProvider::connect(config, &budget).await?);
+                let chain_id = provider.chain_id().await?;
+                if let Ok(mut connections) = self.connections.lock() {
+                    connections.insert(index, (provider.clone(), chain_id));
+                }
+                (provider, chain_id)
+            }
+        };
+        let started = Instant::now();
+        let head = provider.block_number().await?;
+        Ok(ProbeResult {
+            chain_id,
+            head,
+            latency_ms: started.elapsed().as_secs_f64() * 1000.0,
+        })
+    }
+
+    fn cached(&self, index: usize) -> Option<ProbeConnection> {
+        self.connections.lock().ok()?.get(&index).cloned()
+    }
+
+    /// Drop the connection to `index`, e.g. once the worker connects to it directly
+    pub fn forget(&self, index: usize) {
+        if let Ok(mut connections) = self.connections.lock() {
+            connections.remove(&index);
+        }
+    }
+}
+
+/// 0..1, to spread retries of endpoints that failed together
+pub fn jitter() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as f64 / 1e9)
+        .unwrap_or(0.5)
+}
@@ -1,8 +1,22 @@
 //! Runtime infrastructure - Tokio runtime bridge for async operations
 
+mod alerts;
+mod beacon;
 mod bridge;
+mod failover;
+#[cfg(test)]
+mod http_stub;
+mod multicall;
+mod nodes;
+mod optimism;
+mod prometheus;
+mod tokens;
 mod worker;
 
 pub use bridge::{
-    CallStatus, RuntimeBridge, RuntimeCommand, RuntimeEvent, TokenConfig, TraceFrame, TxStatus,
+    BlockInfo, CallStatus, EndpointSpec, RuntimeBridge, RuntimeCommand, RuntimeEvent, TokenConfig,
+    TraceFrame, TxInfo, TxStatus,
 };
+pub use alerts::AlertMonitor;
+pub use tokens::TokenCatalog;
+pub use worker::{block_info, convert_raw_tx};
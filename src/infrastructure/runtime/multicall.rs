@@ -0,0 +1,300 @@
+//! Multicall3 aggregation - batches read-only contract calls into one eth_call
+
+use alloy::primitives::{address, Address, Bytes};
+use alloy::rpc::types::TransactionRequest;
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+
+use crate::infrastructure::ethereum::{rpc_error_response, EthereumProvider};
+
+/// Canonical Multicall3 deployment (same address on nearly every chain)
+pub const MULTICALL3_ADDRESS: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+/// aggregate3((address,bool,bytes)[])
+const AGGREGATE3_SELECTOR: [u8; 4] = [0x82, 0xad, 0x56, 0xcb];
+
+/// Calls per aggregate3 request, keeps responses well under typical RPC limits
+const MAX_BATCH: usize = 200;
+
+/// A read-only contract call
+#[derive(Debug, Clone)]
+pub struct ContractCall {
+    pub target: Address,
+    pub data: Vec<u8>,
+}
+
+impl ContractCall {
+    pub fn new(target: Address, data: Vec<u8>) -> Self {
+        Self { target, data }
+    }
+}
+
+/// Outcome of one call
+#[derive(Debug, Clone, PartialEq)]
+pub enum CallResult {
+    /// The call ran and returned this data
+    Returned(Bytes),
+    /// The call ran and reverted
+    Reverted,
+    /// No answer (transport error, rate limit), so nothing is known about the contract
+    Failed,
+}
+
+impl CallResult {
+    /// Return data of a call that ran to completion
+    pub fn data(&self) -> Option<&Bytes> {
+        match self {
+            Self::Returned(data) => Some(data),
+            _ => None,
+        }
+    }
+}
+
+/// Batches calls through Multicall3, falling back to sequential `eth_call`s
+/// when the contract isn't deployed on the connected chain (e.g. fresh Anvil).
+#[derive(Debug, Default)]
+pub struct Multicall {
+    /// None until the first aggregate3 attempt on the current endpoint
+    available: Option<bool>,
+}
+
+impl Multicall {
+    /// Forget what we learned about the previous endpoint
+    pub fn reset(&mut self) {
+        self.available = None;
+    }
+
+    /// Run every call and return its outcome, in order
+    pub async fn call_all(
+        &mut self,
+        provider: &dyn EthereumProvider,
+        calls: &[ContractCall],
+    ) -> Vec<CallResult> {
+        if calls.len() < 2 || self.available == Some(false) {
+            return call_sequential(provider, calls).await;
+        }
+
+        let mut results = Vec::with_capacity(calls.len());
+        for chunk in calls.chunks(MAX_BATCH) {
+            let request = TransactionRequest::default()
+                .to(MULTICALL3_ADDRESS)
+                .input(encode_aggregate3(chunk).into());
+            let response = match provider.call(request, None).await {
+                Ok(data) => data,
+                // A failed request (timeout, rate limit) says nothing about the contract
+                Err(_) => {
+                    results.extend(call_sequential(provider, chunk).await);
+                    continue;
+                }
+            };
+            match decode_aggregate3(&response, chunk.len()) {
+                Some(chunk_results) => {
+                    self.available = Some(true);
+                    results.extend(chunk_results);
+                }
+                None => {
+                    // Only an endpoint that never answered aggregate3 is marked unsupported
+                    if self.available.is_none() {
+                        self.available = Some(false);
+                    }
+                    results.extend(call_sequential(provider, chunk).await);
+                }
+            }
+        }
+        results
+    }
+}
+
+async fn call_sequential(provider: &dyn EthereumProvider, calls: &[ContractCall]) -> Vec<CallResult> {
+    let mut results = Vec::with_capacity(calls.len());
+    for call in calls {
+        let request = TransactionRequest::default()
+            .to(call.target)
+            .input(call.data.clone().into());
+        results.push(match provider.call(request, None).await {
+            Ok(data) => CallResult::Returned(data),
+            Err(err) if rpc_error_response(&err) => CallResult::Reverted,
+            Err(_) => CallResult::Failed,
+        });
+    }
+    results
+}
+
+fn encode_aggregate3(calls: &[ContractCall]) -> Vec<u8> {
+    let items = calls
+        .iter()
+        .map(|call| {
+            DynSolValue::Tuple(vec![
+                DynSolValue::Address(call.target),
+                DynSolValue::Bool(true),
+                DynSolValue::Bytes(call.data.clone()),
+            ])
+        })
+        .collect();
+    let mut data = AGGREGATE3_SELECTOR.to_vec();
+    data.extend(DynSolValue::Tuple(vec![DynSolValue::Array(items)]).abi_encode_params());
+    data
+}
+
+/// Decode `(bool success, bytes returnData)[]`; `None` if the response isn't one
+fn decode_aggregate3(data: &[u8], expected: usize) -> Option<Vec<CallResult>> {
+    let ty = DynSolType::Tuple(vec![DynSolType::Array(Box::new(DynSolType::Tuple(vec![
+        DynSolType::Bool,
+        DynSolType::Bytes,
+    ])))]);
+    let DynSolValue::Tuple(mut outer) = ty.abi_decode_params(data).ok()? else {
+        return None;
+    };
+    let DynSolValue::Array(items) = outer.pop()? else {
+        return None;
+    };
+    if items.len() != expected {
+        return None;
+    }
+
+    items
+        .into_iter()
+        .map(|item| match item {
+            DynSolValue::Tuple(fields) => match fields.as_slice() {
+                [DynSolValue::Bool(true), DynSolValue::Bytes(ret)] => {
+                    Some(CallResult::Returned(Bytes::from(ret.clone())))
+                }
+                [DynSolValue::Bool(false), DynSolValue::Bytes(_)] => Some(CallResult::Reverted),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::keccak256;
+
+    #[test]
+    fn test_aggregate3_selector() {
+        assert_eq!(
+            AGGREGATE3_SELECTOR,
+            keccak256("aggregate3((address,bool,bytes)[])")[..4]
+        );
+    }
+
+    #[test]
+    fn test_decode_aggregate3() {
+        let response = DynSolValue::Tuple(vec![DynSolValue::Array(vec![
+            DynSolValue::Tuple(vec![DynSolValue::Bool(true), DynSolValue::Bytes(vec![0xaa; 32])]),
+            DynSolValue::Tuple(vec![DynSolValue::Bool(false), DynSolValue::Bytes(vec![])]),
+        ])])
+        .abi_encode_params();
+
+        let results = decode_aggregate3(&response, 2).unwrap();
+        assert_eq!(results[0], CallResult::Returned(Bytes::from(vec![0xaa; 32])));
+        assert_eq!(results[1], CallResult::Reverted);
+
+        // Length mismatch or an empty response (no code at the address) is not a batch result
+        assert!(decode_aggregate3(&response, 3).is_none());
+        assert!(decode_aggregate3(&[], 2).is_none());
+    }
+
+    fn aggregate3_response(results: &[&[u8]]) -> Bytes {
+        let items = results
+            .iter()
+            .map(|data| {
+                DynSolValue::Tuple(vec![DynSolValue::Bool(true), DynSolValue::Bytes(data.to_vec())])
+            })
+            .collect();
+        DynSolValue::Tuple(vec![DynSolValue::Array(items)])
+            .abi_encode_params()
+            .into()
+    }
+
+    #[tokio::test]
+    async fn test_transient_error_does_not_latch_unsupported() {
+        use crate::infrastructure::ethereum::stub::StubProvider;
+        use alloy::primitives::TxKind;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let failed_once = AtomicBool::new(false);
+        let provider = StubProvider::named("stub").with_call(move |request| {
+            if request.to != Some(TxKind::Call(MULTICALL3_ADDRESS)) {
+                return Ok(Bytes::from(vec![1]));
+            }
+            if !failed_once.swap(true, Ordering::SeqCst) {
+                anyhow::bail!("429 Too Many Requests");
+            }
+            Ok(aggregate3_response(&[&[2], &[3]]))
+        });
+        let calls = vec![
+            ContractCall::new(Address::ZERO, vec![0xaa]),
+            ContractCall::new(Address::ZERO, vec![0xbb]),
+        ];
+
+        let mut multicall = Multicall::default();
+        let first = multicall.call_all(&provider, &calls).await;
+        assert_eq!(first, vec![CallResult::Returned(Bytes::from(vec![1])); 2]);
+        assert_eq!(multicall.available, None);
+
+        let second = multicall.call_all(&provider, &calls).await;
+        assert_eq!(
+            second,
+            vec![CallResult::Returned(Bytes::from(vec![2])), CallResult::Returned(Bytes::from(vec![3]))]
+        );
+        assert_eq!(multicall.available, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_reverts_told_apart_from_failures() {
+        use crate::infrastructure::ethereum::stub::{error_response, StubProvider};
+
+        let provider = StubProvider::named("stub").with_call(|request| {
+            match request.input.input().map(|input| input[0]) {
+                Some(0xaa) => Ok(Bytes::from(vec![1])),
+                Some(0xbb) => Err(error_response(3, "execution reverted")),
+                _ => anyhow::bail!("connection reset"),
+            }
+        });
+        let calls = vec![
+            ContractCall::new(Address::ZERO, vec![0xaa]),
+            ContractCall::new(Address::ZERO, vec![0xbb]),
+            ContractCall::new(Address::ZERO, vec![0xcc]),
+        ];
+
+        let results = Multicall::default().call_all(&provider, &calls).await;
+        assert_eq!(
+            results,
+            vec![CallResult::Returned(Bytes::from(vec![1])), CallResult::Reverted, CallResult::Failed]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_response_latches_unsupported() {
+        use crate::infrastructure::ethereum::stub::StubProvider;
+
+        // No code at the Multicall3 address: the call succeeds with empty data
+        let provider = StubProvider::named("anvil").with_call(|_| Ok(Bytes::new()));
+        let calls = vec![
+            ContractCall::new(Address::ZERO, vec![0xaa]),
+            ContractCall::new(Address::ZERO, vec![0xbb]),
+        ];
+
+        let mut multicall = Multicall::default();
+        multicall.call_all(&provider, &calls).await;
+        assert_eq!(multicall.available, Some(false));
+    }
+
+    #[test]
+    fn test_encode_aggregate3_roundtrip() {
+        let calls = vec![ContractCall::new(MULTICALL3_ADDRESS, vec![1, 2, 3, 4])];
+        let encoded = encode_aggregate3(&calls);
+        assert_eq!(encoded[..4], AGGREGATE3_SELECTOR);
+
+        let ty = DynSolType::parse("((address,bool,bytes)[])").unwrap();
+        let DynSolValue::Tuple(outer) = ty.abi_decode_params(&encoded[4..]).unwrap() else {
+            panic!("expected tuple");
+        };
+        let DynSolValue::Array(items) = &outer[0] else {
+            panic!("expected array");
+        };
+        assert_eq!(items.len(), 1);
+    }
+}
@@ -0,0 +1,205 @@
+//! Node comparison polling - probes every configured endpoint side by side
+
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use futures::future::join_all;
+
+use crate::domain::nodes::{common_height, compare_by_chain, NodeComparison, NodeProbe};
+use crate::infrastructure::ethereum::{
+    create_provider, BudgetedProvider, EthereumProvider, InstrumentedProvider, ProviderConfig, RpcTimings,
+    SharedBudget,
+};
+use crate::infrastructure::runtime::bridge::RuntimeEvent;
+
+/// Time between comparison rounds
+const COMPARE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A node slower than this counts as down for the round
+const PROBE_TIMEOUT: Duration = Duration::from_secs(4);
+
+/// Keeps one connection per endpoint, separate from the worker's main provider
+/// but charged to the same budget and timed into the same RPC stats
+pub struct NodeComparer {
+    endpoints: Vec<(ProviderConfig, SharedBudget)>,
+    timings: RpcTimings,
+    providers: Vec<Option<Box<dyn EthereumProvider>>>,
+    clients: Vec<Option<String>>,
+    chain_ids: Vec<Option<u64>>,
+}
+
+impl NodeComparer {
+    pub fn new(endpoints: Vec<(ProviderConfig, SharedBudget)>, timings: RpcTimings) -> Self {
+        let count = endpoints.len();
+        Self {
+            endpoints,
+            timings,
+            providers: (0..count).map(|_| None).collect(),
+            clients: vec![None; count],
+            chain_ids: vec![None; count],
+        }
+    }
+
+    /// Compare every endpoint until the task is aborted
+    pub async fn run(mut self, evt_tx: Sender<RuntimeEvent>) {
+        let mut ticker = tokio::time::interval(COMPARE_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let comparisons = self.round().await;
+            if evt_tx
+                .send(RuntimeEvent::NodeComparison { comparisons })
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+
+    /// Probe all endpoints concurrently, then compare hashes at each chain's
+    /// common height
+    pub async fn round(&mut self) -> Vec<NodeComparison> {
+        let timings = &self.timings;
+        let probes = self
+            .endpoints
+            .iter()
+            .zip(self.providers.iter_mut())
+            .zip(self.clients.iter_mut().zip(self.chain_ids.iter_mut()))
+            .map(|(((config, budget), provider), (client, chain_id))| {
+                probe_endpoint(config, budget, timings, provider, client, chain_id)
+            });
+        let mut probes = join_all(probes).await;
+
+        let heights: Vec<Option<u64>> = probes
+            .iter()
+            .map(|probe| common_height(&probes, probe.chain_id))
+            .collect();
+        let hashes = self
+            .providers
+            .iter()
+            .zip(&probes)
+            .zip(heights)
+            .map(|((provider, probe), height)| async move {
+                match (provider, height) {
+                    (Some(provider), Some(height)) if probe.error.is_none() => {
+                        tokio::time::timeout(PROBE_TIMEOUT, provider.get_block_header(height))
+                            .await
+                            .ok()
+                            .and_then(|block| block.ok().flatten())
+                            .map(|block| block.hash)
+                    }
+                    _ => None,
+                }
+            });
+        let hashes = join_all(hashes).await;
+        for (probe, hash) in probes.iter_mut().zip(hashes) {
+            probe.check_hash = hash;
+        }
+
+        compare_by_chain(probes, now_secs())
+    }
+}
+
+/// Head, RTT, peers, sync state, client and chain of one endpoint, (re)connecting as needed
+async fn probe_endpoint(
+    config: &ProviderConfig,
+    budget: &SharedBudget,
+    timings: &RpcTimings,
+    provider: &mut Option<Box<dyn EthereumProvider>>,
+    client: &mut Option<String>,
+    chain_id: &mut Option<u64>,
+) -> NodeProbe {
+    let mut probe = NodeProbe {
+        endpoint: config.display(),
+        ..Default::default()
+    };
+    let result = tokio::time::timeout(PROBE_TIMEOUT, async {
+        let p = match provider {
+            Some(p) => p,
+            None => {
+                // Timings measure the node, not the time spent waiting on the budget
+                let connected = BudgetedProvider::new(
+                    Box::new(InstrumentedProvider::new(
+                        create_provider(config.clone()).await?,
+                        timings.clone(),
+                    )),
+                    budget.clone(),
+                );
+                let connected = provider.insert(Box::new(connected));
+                // Re-read on every connect: the endpoint may now serve another chain
+                *chain_id = Some(connected.chain_id().await?);
+                connected
+            }
+        };
+        if client.is_none() {
+            *client = p.client_version().await.ok();
+        }
+
+        // eth_blockNumber alone, so the RTT is one round trip
+        let started = Instant::now();
+        let head = p.block_number().await?;
+        probe.rtt_ms = Some(started.elapsed().as_millis() as u64);
+        probe.head = Some(head);
+
+        let block = p
+            .get_block_header(head)
+            .await?
+            .with_context(|| format!("head block {head} not found"))?;
+        probe.head_hash = Some(block.hash);
+        probe.peers = p.peer_count().await.ok().map(|count| count as u32);
+        probe.sync = p.sync_status().await.ok().flatten();
+        Ok::<_, anyhow::Error>(())
+    })
+    .await
+    .unwrap_or_else(|_| Err(anyhow::anyhow!("timed out after {}s", PROBE_TIMEOUT.as_secs())));
+
+    if let Err(err) = result {
+        probe.error = Some(format!("{err:#}"));
+        // Reconnect on the next round
+        *provider = None;
+    }
+    probe.client = client.clone();
+    probe.chain_id = *chain_id;
+    probe
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::budget::BudgetConfig;
+    use crate::domain::nodes::NodeVerdict;
+    use crate::infrastructure::ethereum::EndpointAuth;
+
+    #[tokio::test]
+    async fn test_unreachable_nodes_are_down() {
+        let mut comparer = NodeComparer::new(
+            [1, 2]
+                .map(|port| {
+                    (
+                        ProviderConfig::Http(format!("http://127.0.0.1:{port}"), EndpointAuth::default()),
+                        SharedBudget::new(BudgetConfig::default()),
+                    )
+                })
+                .into(),
+            RpcTimings::default(),
+        );
+        let comparisons = comparer.round().await;
+        assert_eq!(comparisons.len(), 1);
+        let comparison = &comparisons[0];
+        assert_eq!(comparison.chain_id, None);
+        assert_eq!(comparison.height, None);
+        assert_eq!(comparison.unhealthy(), 2);
+        for node in &comparison.nodes {
+            assert_eq!(node.verdict, NodeVerdict::Down);
+            assert!(node.probe.error.is_some());
+        }
+        assert!(comparer.providers.iter().all(Option::is_none));
+    }
+}
@@ -0,0 +1,124 @@
+//! OP Stack L1Block predeploy reads
+
+use alloy::primitives::{keccak256, Address, Bytes, B256, U256};
+use anyhow::{anyhow, Context, Result};
+
+use crate::domain::optimism::{L1Origin, L1_BLOCK_ADDRESS};
+use crate::infrastructure::ethereum::EthereumProvider;
+use crate::infrastructure::runtime::multicall::{CallResult, ContractCall, Multicall};
+
+/// L1Block getters, in the order `fetch_l1_origin` reads their results
+const GETTERS: [&str; 9] = [
+    "number()",
+    "timestamp()",
+    "hash()",
+    "basefee()",
+    "blobBaseFee()",
+    "baseFeeScalar()",
+    "blobBaseFeeScalar()",
+    "sequenceNumber()",
+    "batcherHash()",
+];
+
+/// Read the L1 origin. None when the chain has no predeploy; an error when the
+/// reads failed and say nothing about the chain.
+pub async fn fetch_l1_origin(
+    provider: &dyn EthereumProvider,
+    multicall: &mut Multicall,
+) -> Result<Option<L1Origin>> {
+    let target: Address = L1_BLOCK_ADDRESS.parse()?;
+    let calls: Vec<ContractCall> = GETTERS
+        .iter()
+        .map(|getter| ContractCall::new(target, keccak256(getter.as_bytes())[..4].to_vec()))
+        .collect();
+    let results = multicall.call_all(provider, &calls).await;
+    let read = |index: usize| results.get(index).and_then(|result| word(result.data()?));
+
+    // An address without code answers with empty data, so number tells OP chains apart
+    match results.first() {
+        Some(CallResult::Returned(data)) if data.is_empty() => return Ok(None),
+        Some(CallResult::Returned(_)) => {}
+        _ => return Err(anyhow!("L1Block: number() call failed")),
+    }
+    let number = read(0).context("L1Block: malformed number")?;
+    let hash = read(2).context("L1Block: no hash")?;
+    Ok(Some(L1Origin {
+        number: number.saturating_to(),
+        timestamp: read(1).map_or(0, |value| value.saturating_to()),
+        hash: format!("{:#x}", B256::from(hash)),
+        base_fee: read(3).map_or(0, |value| value.saturating_to()),
+        blob_base_fee: read(4).map(|value| value.saturating_to()),
+        base_fee_scalar: read(5).map(|value| value.saturating_to()),
+        blob_base_fee_scalar: read(6).map(|value| value.saturating_to()),
+        sequence_number: read(7).map_or(0, |value| value.saturating_to()),
+        batcher_hash: read(8).map_or_else(String::new, |value| format!("{:#x}", B256::from(value))),
+    }))
+}
+
+/// A single static return value
+fn word(data: &Bytes) -> Option<U256> {
+    (data.len() == 32).then(|| U256::from_be_slice(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::ethereum::stub::StubProvider;
+    use alloy::primitives::TxKind;
+
+    /// L1Block getters by their published selectors
+    fn l1_block(request: &alloy::rpc::types::TransactionRequest) -> Result<Bytes> {
+        let target: Address = L1_BLOCK_ADDRESS.parse()?;
+        if request.to != Some(TxKind::Call(target)) {
+            anyhow::bail!("execution reverted");
+        }
+        let input = request.input.input().context("no input")?;
+        let value = match hex::encode(&input[..4]).as_str() {
+            "8381f58a" => U256::from(20_000_100u64),
+            "b80777ea" => U256::from(1_750_000_000u64),
+            "09bd5a60" => U256::from_be_bytes([0xab; 32]),
+            "5cf24969" => U256::from(3_000_000_000u64),
+            "f8206140" => U256::from(1u64),
+            // Not a single word, so the field is left unset
+            "c5985918" => return Ok(Bytes::from(vec![0; 31])),
+            "68d5dca6" => U256::from(810_949u64),
+            "64ca23ef" => U256::from(4u64),
+            "e81b2c6d" => U256::from(0xffu64),
+            other => anyhow::bail!("unexpected selector {other}"),
+        };
+        Ok(Bytes::from(value.to_be_bytes::<32>().to_vec()))
+    }
+
+    #[tokio::test]
+    async fn test_fetch_l1_origin() {
+        let provider = StubProvider::named("op").with_call(l1_block);
+        let origin = fetch_l1_origin(&provider, &mut Multicall::default())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(origin.number, 20_000_100);
+        assert_eq!(origin.timestamp, 1_750_000_000);
+        assert_eq!(origin.hash, format!("0x{}", "ab".repeat(32)));
+        assert_eq!(origin.base_fee, 3_000_000_000);
+        assert_eq!(origin.blob_base_fee, Some(1));
+        assert_eq!(origin.base_fee_scalar, None);
+        assert_eq!(origin.blob_base_fee_scalar, Some(810_949));
+        assert_eq!(origin.sequence_number, 4);
+        assert_eq!(origin.batcher_hash, format!("{:#x}", B256::from(U256::from(0xffu64))));
+        // One aggregate3 attempt, then each getter on its own
+        assert_eq!(provider.calls.load(std::sync::atomic::Ordering::SeqCst), GETTERS.len() + 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_l1_origin_without_predeploy() {
+        // No code at the predeploy: empty return data means not an OP Stack chain
+        let provider = StubProvider::named("l1").with_call(|_| Ok(Bytes::new()));
+        let origin = fetch_l1_origin(&provider, &mut Multicall::default()).await.unwrap();
+        assert_eq!(origin, None);
+
+        // Failed calls say nothing about the chain
+        let provider = StubProvider::named("flaky").with_call(|_| anyhow::bail!("429 Too Many Requests"));
+        assert!(fetch_l1_origin(&provider, &mut Multicall::default()).await.is_err());
+    }
+}
@@ -0,0 +1,358 @@
+//! Prometheus scraping - reads node metrics endpoints (Geth, Reth, Nethermind)
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+const SCRAPE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// One sample from the Prometheus text exposition format
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromSample {
+    pub name: String,
+    pub labels: BTreeMap<String, String>,
+    pub value: f64,
+}
+
+/// Where a key series comes from in one client's metrics
+struct Source {
+    metric: &'static str,
+    label: Option<(&'static str, &'static str)>,
+    /// Multiplier into the series unit
+    scale: f64,
+}
+
+const fn source(metric: &'static str, scale: f64) -> Source {
+    Source {
+        metric,
+        label: None,
+        scale,
+    }
+}
+
+const fn labeled(metric: &'static str, label: (&'static str, &'static str), scale: f64) -> Source {
+    Source {
+        metric,
+        label: Some(label),
+        scale,
+    }
+}
+
+/// A series shown in the Metrics panel, built from whichever client's metrics exist
+struct KeySeries {
+    key: &'static str,
+    label: &'static str,
+    unit: &'static str,
+    /// Counter: chart the per-second rate instead of the raw value
+    rate: bool,
+    /// Add up every matching source (e.g. pending + queued) instead of taking the first
+    sum: bool,
+    sources: &'static [Source],
+}
+
+const KEY_SERIES: &[KeySeries] = &[
+    KeySeries {
+        key: "head",
+        label: "Node chain head",
+        unit: "",
+        rate: false,
+        sum: false,
+        sources: &[
+            source("chain_head_block", 1.0),
+            labeled("reth_sync_checkpoint", ("stage", "Finish"), 1.0),
+            source("nethermind_blocks", 1.0),
+        ],
+    },
+    KeySeries {
+        key: "txpool",
+        label: "Txpool size",
+        unit: "txs",
+        rate: false,
+        sum: true,
+        sources: &[
+            source("txpool_pending", 1.0),
+            source("txpool_queued", 1.0),
+            source("reth_transaction_pool_pending_pool_transactions", 1.0),
+            source("reth_transaction_pool_basefee_pool_transactions", 1.0),
+            source("reth_transaction_pool_queued_pool_transactions", 1.0),
+        ],
+    },
+    KeySeries {
+        key: "db_size",
+        label: "DB size",
+        unit: "GB",
+        rate: false,
+        sum: true,
+        sources: &[
+            source("eth_db_chaindata_disk_size", 1e-9),
+            source("reth_db_table_size", 1e-9),
+        ],
+    },
+    KeySeries {
+        key: "p2p_in",
+        label: "P2P ingress",
+        unit: "KB/s",
+        rate: true,
+        sum: true,
+        sources: &[source("p2p_ingress", 1e-3)],
+    },
+    KeySeries {
+        key: "p2p_out",
+        label: "P2P egress",
+        unit: "KB/s",
+        rate: true,
+        sum: true,
+        sources: &[source("p2p_egress", 1e-3)],
+    },
+    KeySeries {
+        key: "engine_new_payload",
+        label: "Engine newPayload p50",
+        unit: "ms",
+        rate: false,
+        sum: false,
+        sources: &[
+            // Geth reports timer percentiles in nanoseconds, Reth in seconds
+            labeled("rpc_duration_engine_newPayloadV4_success", ("quantile", "0.5"), 1e-6),
+            labeled("rpc_duration_engine_newPayloadV3_success", ("quantile", "0.5"), 1e-6),
+            labeled("reth_engine_rpc_new_payload_v4", ("quantile", "0.5"), 1e3),
+            labeled("reth_engine_rpc_new_payload_v3", ("quantile", "0.5"), 1e3),
+        ],
+    },
+];
+
+/// A key series value ready for the metrics store
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeMetric {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub unit: &'static str,
+    pub value: f64,
+}
+
+/// Turns scrapes into key series, remembering counters to derive rates
+#[derive(Debug, Default)]
+pub struct NodeMetrics {
+    counters: HashMap<&'static str, (u64, f64)>,
+}
+
+impl NodeMetrics {
+    pub fn reset(&mut self) {
+        self.counters.clear();
+    }
+
+    /// Key series found in a scrape taken at `at` (unix seconds)
+    pub fn extract(&mut self, samples: &[PromSample], at: u64) -> Vec<NodeMetric> {
+        let mut out = Vec::new();
+        for series in KEY_SERIES {
+            let mut matched = series.sources.iter().filter_map(|source| {
+                let values: Vec<f64> = samples
+                    .iter()
+                    .filter(|sample| sample.name == source.metric)
+                    .filter(|sample| {
+                        source
+                            .label
+                            .is_none_or(|(k, v)| sample.labels.get(k).map(String::as_str) == Some(v))
+                    })
+                    .map(|sample| sample.value)
+                    .filter(|value| value.is_finite())
+                    .collect();
+                (!values.is_empty()).then(|| values.iter().sum::<f64>() * source.scale)
+            });
+            let value = if series.sum {
+                matched.reduce(|a, b| a + b)
+            } else {
+                matched.next()
+            };
+            let Some(value) = value else {
+                continue;
+            };
+
+            let value = if series.rate {
+                let previous = self.counters.insert(series.key, (at, value));
+                match previous {
+                    Some((prev_at, prev)) if at > prev_at && value >= prev => {
+                        (value - prev) / (at - prev_at) as f64
+                    }
+                    // First scrape or counter reset
+                    _ => continue,
+                }
+            } else {
+                value
+            };
+            out.push(NodeMetric {
+                key: series.key,
+                label: series.label,
+                unit: series.unit,
+                value,
+            });
+        }
+        out
+    }
+}
+
+/// Fetch and parse a metrics endpoint
+pub async fn scrape(http: &reqwest::Client, url: &str) -> Result<Vec<PromSample>> {
+    let body = http
+        .get(url)
+        .timeout(SCRAPE_TIMEOUT)
+        .send()
+        .await
+        .with_context(|| format!("scrape {url}"))?
+        .error_for_status()?
+        .text()
+        .await?;
+    Ok(parse_text(&body))
+}
+
+/// Parse the Prometheus text exposition format, skipping malformed lines
+pub fn parse_text(body: &str) -> Vec<PromSample> {
+    body.lines().filter_map(parse_line).collect()
+}
+
+fn parse_line(line: &str) -> Option<PromSample> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let name_end = line
+        .find(|c: char| c == '{' || c.is_whitespace())
+        .unwrap_or(line.len());
+    let name = &line[..name_end];
+    let mut rest = line[name_end..].trim_start();
+    let mut labels = BTreeMap::new();
+
+    if let Some(body) = rest.strip_prefix('{') {
+        let (parsed, remaining) = parse_labels(body)?;
+        labels = parsed;
+        rest = remaining;
+    }
+
+    // Value, optionally followed by a timestamp
+    let value = rest.split_whitespace().next()?;
+    let value = match value {
+        "+Inf" => f64::INFINITY,
+        "-Inf" => f64::NEG_INFINITY,
+        other => other.parse().ok()?,
+    };
+    Some(PromSample {
+        name: name.to_string(),
+        labels,
+        value,
+    })
+}
+
+/// Parse `k="v",...}` and return the labels and what follows the closing brace
+fn parse_labels(mut body: &str) -> Option<(BTreeMap<String, String>, &str)> {
+    let mut labels = BTreeMap::new();
+    loop {
+        body = body.trim_start_matches([',', ' ']);
+        if let Some(rest) = body.strip_prefix('}') {
+            return Some((labels, rest));
+        }
+        let eq = body.find('=')?;
+        let key = body[..eq].trim().to_string();
+        let quoted = body[eq + 1..].trim_start().strip_prefix('"')?;
+
+        let mut value = String::new();
+        let mut chars = quoted.char_indices();
+        let end = loop {
+            let (idx, c) = chars.next()?;
+            match c {
+                '\\' => match chars.next()?.1 {
+                    'n' => value.push('\n'),
+                    other => value.push(other),
+                },
+                '"' => break idx,
+                other => value.push(other),
+            }
+        };
+        labels.insert(key, value);
+        body = &quoted[end + 1..];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::runtime::http_stub::serve;
+
+    const GETH: &str = r#"# TYPE chain_head_block gauge
+chain_head_block 19000000
+# TYPE txpool_pending gauge
+txpool_pending 120
+txpool_queued 30
+eth_db_chaindata_disk_size 1500000000000
+p2p_ingress 1000000
+p2p_egress 500000
+rpc_duration_engine_newPayloadV3_success_count 42
+rpc_duration_engine_newPayloadV3_success {quantile="0.5"} 85000000
+rpc_duration_engine_newPayloadV3_success {quantile="0.99"} 250000000
+"#;
+
+    fn value(metrics: &[NodeMetric], key: &str) -> Option<f64> {
+        metrics
+            .iter()
+            .find(|metric| metric.key == key)
+            .map(|metric| metric.value)
+    }
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("series missing");
+        assert!((actual - expected).abs() < 1e-6, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let sample =
+            parse_line(r#"reth_sync_checkpoint{stage="Finish",note="a \"q\", b"} 123 1700000000"#)
+                .unwrap();
+        assert_eq!(sample.name, "reth_sync_checkpoint");
+        assert_eq!(sample.labels["stage"], "Finish");
+        assert_eq!(sample.labels["note"], r#"a "q", b"#);
+        assert_eq!(sample.value, 123.0);
+
+        assert_eq!(parse_line("up +Inf").unwrap().value, f64::INFINITY);
+        assert!(parse_line("# HELP up whatever").is_none());
+        assert!(parse_line("broken{label=1} 2").is_none());
+    }
+
+    #[test]
+    fn test_extract_geth() {
+        let samples = parse_text(GETH);
+        let mut node = NodeMetrics::default();
+
+        let first = node.extract(&samples, 100);
+        assert_close(value(&first, "head"), 19_000_000.0);
+        assert_close(value(&first, "txpool"), 150.0);
+        assert_close(value(&first, "db_size"), 1500.0);
+        assert_close(value(&first, "engine_new_payload"), 85.0);
+        // Rates need two scrapes
+        assert!(value(&first, "p2p_in").is_none());
+
+        let later = GETH.replace("p2p_ingress 1000000", "p2p_ingress 1100000");
+        let second = node.extract(&parse_text(&later), 110);
+        assert_close(value(&second, "p2p_in"), 10.0);
+        assert_close(value(&second, "p2p_out"), 0.0);
+    }
+
+    #[test]
+    fn test_extract_reth_stage_label() {
+        let samples = parse_text(
+            "reth_sync_checkpoint{stage=\"Headers\"} 200\nreth_sync_checkpoint{stage=\"Finish\"} 150\n",
+        );
+        let metrics = NodeMetrics::default().extract(&samples, 0);
+        assert_close(value(&metrics, "head"), 150.0);
+    }
+
+    #[tokio::test]
+    async fn test_scrape_static_file() {
+        let (base, _requests) = serve(|_| ("200 OK", GETH.to_string()));
+        let url = format!("{base}/debug/metrics/prometheus");
+
+        let samples = scrape(&reqwest::Client::new(), &url).await.unwrap();
+        assert_eq!(samples.len(), 9);
+        assert_eq!(samples[0].name, "chain_head_block");
+    }
+}
@@ -0,0 +1,346 @@
+//! Token catalog - discovers tokens from Transfer logs and caches their metadata
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+
+use alloy::primitives::{Address, U256};
+
+use crate::domain::transfers::{AssetTransfer, TokenMeta};
+use crate::infrastructure::ethereum::EthereumProvider;
+use crate::infrastructure::runtime::multicall::{CallResult, ContractCall, Multicall};
+use crate::store::TokenStore;
+
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+// ERC-20 metadata selectors
+const NAME_SELECTOR: [u8; 4] = [0x06, 0xfd, 0xde, 0x03];
+const SYMBOL_SELECTOR: [u8; 4] = [0x95, 0xd8, 0x9b, 0x41];
+const DECIMALS_SELECTOR: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+const TOTAL_SUPPLY_SELECTOR: [u8; 4] = [0x18, 0x16, 0x0d, 0xdd];
+
+/// Tokens seen on the current chain and their metadata.
+///
+/// Metadata is cached in memory for the session and mirrored to the optional
+/// SQLite store so it survives restarts. Holder -> token relations go to the
+/// store in one batch per block; in memory they are only kept for addresses
+/// whose balances have been looked up, so a busy chain can't grow them
+/// without bound.
+#[derive(Debug, Default)]
+pub struct TokenCatalog {
+    store: Option<Arc<Mutex<TokenStore>>>,
+    chain_id: u64,
+    metadata: HashMap<(u64, String), TokenMeta>,
+    /// Tokens touched by tracked holders on the current chain
+    holders: HashMap<String, BTreeSet<String>>,
+    /// Holder/token pairs waiting for the next store flush
+    pending: BTreeSet<(String, String)>,
+}
+
+impl TokenCatalog {
+    pub fn new(store: Option<TokenStore>) -> Self {
+        Self {
+            store: store.map(|store| Arc::new(Mutex::new(store))),
+            ..Default::default()
+        }
+    }
+
+    /// Switch to the chain of a newly connected endpoint
+    pub fn set_chain(&mut self, chain_id: u64) {
+        if chain_id != self.chain_id {
+            self.flush_holders();
+            self.holders.clear();
+        }
+        self.chain_id = chain_id;
+    }
+
+    /// Chain the catalog currently records for (0 before the first connect)
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Record the holders of every token moved by these transfers
+    pub fn observe(&mut self, transfers: &[AssetTransfer]) {
+        for transfer in transfers {
+            let Some(token) = transfer.asset.token_address() else {
+                continue;
+            };
+            for holder in [&transfer.from, &transfer.to] {
+                if holder == ZERO_ADDRESS {
+                    continue;
+                }
+                if let Some(tokens) = self.holders.get_mut(holder) {
+                    tokens.insert(token.to_string());
+                }
+                if self.store.is_some() {
+                    self.pending.insert((holder.clone(), token.to_string()));
+                }
+            }
+        }
+    }
+
+    /// Write the holders observed since the last flush to the store, off the
+    /// worker's task
+    pub fn flush_holders(&mut self) {
+        let Some(store) = self.store.clone() else {
+            return;
+        };
+        if self.pending.is_empty() {
+            return;
+        }
+        let pairs: Vec<(String, String)> = std::mem::take(&mut self.pending).into_iter().collect();
+        let chain_id = self.chain_id;
+        tokio::task::spawn_blocking(move || {
+            if let Ok(mut store) = store.lock() {
+                let _ = store.record_holders(chain_id, &pairs);
+            }
+        });
+    }
+
+    /// Every token the holder has sent or received. From now on the holder's
+    /// transfers are also tracked in memory.
+    pub fn touched_tokens(&mut self, holder: &str) -> Vec<String> {
+        let holder = holder.to_lowercase();
+        let mut tokens = self.holders.entry(holder.clone()).or_default().clone();
+        tokens.extend(
+            self.pending
+                .iter()
+                .filter(|(pending, _)| *pending == holder)
+                .map(|(_, token)| token.clone()),
+        );
+        if let Some(store) = &self.store {
+            if let Ok(store) = store.lock() {
+                tokens.extend(
+                    store
+                        .tokens_for_holder(self.chain_id, &holder)
+                        .unwrap_or_default(),
+                );
+            }
+        }
+        tokens.into_iter().collect()
+    }
+
+    /// Cached metadata for each token, fetching the missing ones in one batch.
+    /// Metadata is only cached once every read got an answer; a token whose reads
+    /// failed (transport error, rate limit) is returned as far as it is known and
+    /// fetched again next time.
+    pub async fn metadata_many(
+        &mut self,
+        provider: &dyn EthereumProvider,
+        multicall: &mut Multicall,
+        tokens: &[String],
+    ) -> BTreeMap<String, TokenMeta> {
+        let mut out = BTreeMap::new();
+        let mut missing = Vec::new();
+        for token in tokens {
+            let key = (self.chain_id, token.to_lowercase());
+            if let Some(meta) = self.metadata.get(&key) {
+                out.insert(key.1, meta.clone());
+                continue;
+            }
+            let cached = self.store.as_ref().and_then(|store| {
+                store.lock().ok()?.get(self.chain_id, &key.1).ok().flatten()
+            });
+            match cached {
+                Some(meta) => {
+                    self.metadata.insert(key.clone(), meta.clone());
+                    out.insert(key.1, meta);
+                }
+                None => {
+                    if let Ok(address) = key.1.parse::<Address>() {
+                        missing.push((key.1, address));
+                    }
+                }
+            }
+        }
+
+        let addresses: Vec<Address> = missing.iter().map(|(_, address)| *address).collect();
+        let fetched = fetch_token_metas(provider, multicall, &addresses).await;
+        for ((token, _), (meta, complete)) in missing.into_iter().zip(fetched) {
+            if complete {
+                if let Some(store) = self.store.as_ref().and_then(|store| store.lock().ok()) {
+                    let _ = store.upsert(self.chain_id, &token, &meta);
+                }
+                self.metadata.insert((self.chain_id, token.clone()), meta.clone());
+            }
+            out.insert(token, meta);
+        }
+        out
+    }
+}
+
+/// Read name(), symbol(), decimals() and totalSupply() for every token,
+/// tolerating contracts that lack some of them. Each meta comes with whether
+/// every read got an answer, i.e. whether its missing fields are really missing.
+async fn fetch_token_metas(
+    provider: &dyn EthereumProvider,
+    multicall: &mut Multicall,
+    tokens: &[Address],
+) -> Vec<(TokenMeta, bool)> {
+    const SELECTORS: [[u8; 4]; 4] = [
+        NAME_SELECTOR,
+        SYMBOL_SELECTOR,
+        DECIMALS_SELECTOR,
+        TOTAL_SUPPLY_SELECTOR,
+    ];
+    let calls: Vec<ContractCall> = tokens
+        .iter()
+        .flat_map(|token| {
+            SELECTORS
+                .iter()
+                .map(|selector| ContractCall::new(*token, selector.to_vec()))
+        })
+        .collect();
+    let results = multicall.call_all(provider, &calls).await;
+    let word = |result: &CallResult| {
+        result
+            .data()
+            .filter(|data| data.len() >= 32)
+            .map(|data| U256::from_be_slice(&data[..32]))
+    };
+
+    results
+        .chunks(SELECTORS.len())
+        .map(|fields| {
+            let meta = TokenMeta {
+                name: fields[0].data().and_then(|data| decode_string(data)),
+                symbol: fields[1].data().and_then(|data| decode_string(data)),
+                decimals: word(&fields[2]).and_then(|value| u8::try_from(value).ok()),
+                total_supply: word(&fields[3]),
+            };
+            let complete = !fields.contains(&CallResult::Failed);
+            (meta, complete)
+        })
+        .collect()
+}
+
+/// Decode a string return value, either ABI-encoded or a legacy bytes32
+fn decode_string(data: &[u8]) -> Option<String> {
+    let raw = if data.len() >= 96 {
+        let len = usize::try_from(U256::from_be_slice(&data[32..64])).ok()?;
+        data.get(64..64 + len)?.to_vec()
+    } else if data.len() == 32 {
+        data.iter().copied().take_while(|b| *b != 0).collect()
+    } else {
+        return None;
+    };
+    let value = String::from_utf8(raw).ok()?;
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::keccak256;
+
+    #[test]
+    fn test_selectors() {
+        for (selector, signature) in [
+            (NAME_SELECTOR, "name()"),
+            (SYMBOL_SELECTOR, "symbol()"),
+            (DECIMALS_SELECTOR, "decimals()"),
+            (TOTAL_SUPPLY_SELECTOR, "totalSupply()"),
+        ] {
+            assert_eq!(selector, keccak256(signature)[..4]);
+        }
+    }
+
+    #[test]
+    fn test_decode_string() {
+        // ABI string "USDC"
+        let mut abi = vec![0u8; 96];
+        abi[31] = 0x20;
+        abi[63] = 4;
+        abi[64..68].copy_from_slice(b"USDC");
+        assert_eq!(decode_string(&abi).as_deref(), Some("USDC"));
+
+        // bytes32 "MKR"
+        let mut legacy = vec![0u8; 32];
+        legacy[..3].copy_from_slice(b"MKR");
+        assert_eq!(decode_string(&legacy).as_deref(), Some("MKR"));
+
+        assert_eq!(decode_string(&[]), None);
+    }
+
+    #[test]
+    fn test_holders_only_kept_for_tracked_addresses() {
+        use crate::domain::transfers::{Asset, AssetKind};
+
+        const TOKEN: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+        const ALICE: &str = "0x1111111111111111111111111111111111111111";
+        const BOB: &str = "0x2222222222222222222222222222222222222222";
+        let transfer = AssetTransfer {
+            asset: Asset::Token {
+                kind: AssetKind::Erc20,
+                address: TOKEN.to_string(),
+                token_id: None,
+            },
+            from: ALICE.to_string(),
+            to: BOB.to_string(),
+            amount: U256::from(1),
+        };
+
+        let mut catalog = TokenCatalog::new(None);
+        catalog.set_chain(1);
+        catalog.observe(std::slice::from_ref(&transfer));
+        assert!(catalog.holders.is_empty());
+
+        assert!(catalog.touched_tokens(ALICE).is_empty());
+        catalog.observe(&[transfer]);
+        assert_eq!(catalog.touched_tokens(ALICE), vec![TOKEN.to_string()]);
+        assert!(!catalog.holders.contains_key(BOB));
+
+        catalog.set_chain(10);
+        assert!(catalog.touched_tokens(ALICE).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_metadata_cached_only_when_every_read_answered() {
+        use crate::infrastructure::ethereum::stub::{error_response, StubProvider};
+        use alloy::primitives::Bytes;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        const TOKEN: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+        let throttled = Arc::new(AtomicBool::new(true));
+        let provider = {
+            let throttled = throttled.clone();
+            StubProvider::named("stub").with_call(move |request| {
+                let input = request.input.input().cloned().unwrap_or_default();
+                match input.get(..4) {
+                    Some(selector) if selector == SYMBOL_SELECTOR => {
+                        let mut data = vec![0u8; 32];
+                        data[..4].copy_from_slice(b"USDC");
+                        Ok(Bytes::from(data))
+                    }
+                    Some(selector) if selector == DECIMALS_SELECTOR => {
+                        if throttled.load(Ordering::SeqCst) {
+                            anyhow::bail!("429 Too Many Requests");
+                        }
+                        Ok(Bytes::from(U256::from(6).to_be_bytes::<32>().to_vec()))
+                    }
+                    // No name() or totalSupply(), and no Multicall3
+                    _ => Err(error_response(3, "execution reverted")),
+                }
+            })
+        };
+        let store = TokenStore::open_in_memory().unwrap();
+        let mut catalog = TokenCatalog::new(Some(store));
+        catalog.set_chain(1);
+        let mut multicall = Multicall::default();
+        let tokens = vec![TOKEN.to_string()];
+
+        let first = catalog.metadata_many(&provider, &mut multicall, &tokens).await;
+        assert_eq!(first[TOKEN].symbol.as_deref(), Some("USDC"));
+        assert_eq!(first[TOKEN].decimals, None);
+        assert!(catalog.metadata.is_empty());
+        let stored = catalog.store.as_ref().unwrap().lock().unwrap().get(1, TOKEN).unwrap();
+        assert_eq!(stored, None);
+
+        throttled.store(false, Ordering::SeqCst);
+        let second = catalog.metadata_many(&provider, &mut multicall, &tokens).await;
+        assert_eq!(second[TOKEN].decimals, Some(6));
+        assert_eq!(second[TOKEN].name, None);
+        let stored = catalog.store.as_ref().unwrap().lock().unwrap().get(1, TOKEN).unwrap();
+        assert_eq!(stored.and_then(|meta| meta.decimals), Some(6));
+    }
+}
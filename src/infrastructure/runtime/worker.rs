@@ -1,26 +1,54 @@
 //! Async worker - runs in Tokio runtime and handles RPC operations
 
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::sync::mpsc::{Receiver, Sender};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use alloy::network::{AnyTransactionReceipt, ReceiptResponse};
 use alloy::primitives::{Address, B256, U256};
-use alloy::rpc::types::{Block, TransactionRequest};
+use alloy::rpc::types::Block;
 use anyhow::{Context, Result};
-use tokio::time::interval;
 
+use crate::domain::beacon::BeaconStatus;
+use crate::domain::blobs::{blob_base_fee, blob_count, blob_params};
+use crate::domain::budget::Pressure;
+use crate::domain::delegation::parse_delegation;
+use crate::domain::finality::FinalityHeads;
+use crate::domain::health::HealthTracker;
+use crate::domain::metrics::{MetricsStore, MetricsWindow};
+use crate::domain::transfers::{decode_transfer_log, Asset, AssetTransfer, TransferSummary};
 use crate::infrastructure::abi::{AbiResolver, AbiScanner};
 use crate::infrastructure::ethereum::{
-    create_provider, EthereumProvider, ProviderConfig, RawBlock, RawTransaction,
+    create_provider, rate_limited, receipt_l1_fee, rpc_error_response, BudgetedProvider, EthereumProvider,
+    InstrumentedProvider, ProviderConfig, QuorumLog, QuorumProvider, RawBlock, RawTransaction,
+    RpcTimings, SharedBudget,
 };
+use crate::infrastructure::runtime::alerts::AlertMonitor;
 use crate::infrastructure::runtime::bridge::{
-    BlockInfo, RuntimeCommand, RuntimeEvent, TokenBalance, TokenConfig, TxInfo, TxStatus,
+    BlockInfo, CallStatus, EndpointSpec, RuntimeCommand, RuntimeEvent, TokenBalance, TokenConfig, TraceFrame,
+    TxInfo, TxStatus,
 };
+use crate::infrastructure::runtime::beacon::fetch_status;
+use crate::infrastructure::runtime::failover::{jitter, ProbePool, ProbeResult};
+use crate::infrastructure::runtime::multicall::{CallResult, ContractCall, Multicall};
+use crate::infrastructure::runtime::nodes::NodeComparer;
+use crate::infrastructure::runtime::optimism::fetch_l1_origin;
+use crate::infrastructure::runtime::prometheus::{scrape, NodeMetrics, PromSample};
+use crate::infrastructure::runtime::tokens::TokenCatalog;
+
+/// How often a configured node metrics endpoint is scraped
+const SCRAPE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How often a configured beacon node is polled; a slot is 12s
+const BEACON_INTERVAL: Duration = Duration::from_secs(6);
 
 /// Run the async worker loop
 pub async fn run_async_worker(
-    endpoints: Vec<ProviderConfig>,
+    endpoints: Vec<EndpointSpec>,
+    resolver: AbiResolver,
+    mut catalog: TokenCatalog,
+    mut alerts: AlertMonitor,
     cmd_rx: Receiver<RuntimeCommand>,
     evt_tx: Sender<RuntimeEvent>,
 ) -> Result<()> {
@@ -36,29 +64,124 @@ pub async fn run_async_worker(
 
     // ABI resolver for 4byte and Sourcify lookups
     // The resolver has internal caching, so we don't need to track pending selectors
-    let resolver = Arc::new(AbiResolver::new());
-
-    // Polling interval for HTTP endpoints
-    let mut poll_interval = interval(Duration::from_millis(500));
+    let resolver = Arc::new(resolver);
+    // Chain of the connected endpoint, for Sourcify lookups of delegate ABIs
+    let mut chain_id: Option<u64> = None;
+    // Whether the chain has the OP Stack L1Block predeploy; None until first read
+    let mut op_stack: Option<bool> = None;
+    // Whether the node answers "safe"/"finalized" block tags; None until first read
+    let mut finality_tags: Option<bool> = None;
+
+    // Batched contract reads; support is re-probed after every (re)connect
+    let mut multicall = Multicall::default();
+
+    // Rolling metrics and the RPC latencies feeding them
+    let rpc_timings = RpcTimings::default();
+    let mut metrics = MetricsStore::default();
+    let mut metrics_window = MetricsWindow::default();
+
+    // Per-endpoint rate limits; under pressure polling slows down and receipts are skipped
+    let budgets: Vec<SharedBudget> = endpoints
+        .iter()
+        .map(|endpoint| SharedBudget::new(endpoint.budget.clone()))
+        .collect();
+    let mut pressure = Pressure::Normal;
+
+    // Node Prometheus metrics, scraped off the main loop
+    let http = reqwest::Client::new();
+    let mut node_metrics = NodeMetrics::default();
+    let mut last_scrape = Instant::now() - SCRAPE_INTERVAL;
+    let mut scrape_in_flight = false;
+    let mut scrape_failing = false;
+    let (scrape_tx, mut scrape_rx) =
+        tokio::sync::mpsc::unbounded_channel::<(usize, Result<Vec<PromSample>>)>();
+
+    // Paired beacon node, polled the same way
+    let mut last_beacon = Instant::now() - BEACON_INTERVAL;
+    let mut beacon_in_flight = false;
+    let mut beacon_failing = false;
+    let (beacon_tx, mut beacon_rx) = tokio::sync::mpsc::unbounded_channel::<(usize, Result<BeaconStatus>)>();
+
+    // Per-endpoint health for failover; other endpoints are probed off the main loop
+    let mut health = HealthTracker::new(endpoints.len(), Instant::now());
+    let (probe_tx, mut probe_rx) = tokio::sync::mpsc::unbounded_channel::<(usize, Result<ProbeResult>)>();
+    let probes = ProbePool::default();
+
+    // Quorum reads: user-initiated reads go to every endpoint while enabled
+    let quorum_log = QuorumLog::default();
+    let mut quorum: Option<QuorumProvider> = None;
+    // Members connect off the main loop; results of an older toggle are dropped
+    let mut quorum_generation = 0u64;
+    let (quorum_tx, mut quorum_rx) = tokio::sync::mpsc::unbounded_channel::<(u64, Result<QuorumProvider>)>();
+
+    // Side-by-side polling of every endpoint, only while the comparison view is open
+    let mut comparison: Option<tokio::task::JoinHandle<()>> = None;
+
+    // Last head poll of an HTTP endpoint, and the highest head seen but not yet processed
+    let mut last_poll = Instant::now() - pressure.poll_interval();
+    let mut target_head: Option<u64> = None;
 
     loop {
         // Try to connect if not connected
         if provider.is_none() {
-            let config = endpoints[endpoint_index].clone();
-            match connect_to_endpoint(config.clone(), &evt_tx).await {
+            // A backed-off endpoint waits; try the healthiest one that may be tried instead
+            if !health.is_available(endpoint_index, Instant::now()) {
+                match health.best_available(endpoint_index, true, Instant::now()) {
+                    Some(index) => {
+                        send_failover(&endpoints, endpoint_index, index, "backing off", &evt_tx);
+                        endpoint_index = index;
+                    }
+                    None => {
+                        let wait = health
+                            .next_retry()
+                            .map(|at| at.saturating_duration_since(Instant::now()))
+                            .unwrap_or_default();
+                        tokio::time::sleep(wait.clamp(Duration::from_millis(10), Duration::from_secs(1)))
+                            .await;
+                        continue;
+                    }
+                }
+            }
+
+            let config = endpoints[endpoint_index].provider.clone();
+            match connect_to_endpoint(config.clone(), &rpc_timings, &budgets[endpoint_index], &evt_tx).await {
                 Ok((p, sub)) => {
+                    // The worker's own connection stands in for the probe one
+                    probes.forget(endpoint_index);
+                    multicall.reset();
+                    op_stack = None;
+                    finality_tags = None;
+                    let _ = evt_tx.send(RuntimeEvent::L1Origin { origin: None });
+                    alerts.reset_chain();
+                    metrics.reset();
+                    node_metrics.reset();
+                    scrape_failing = false;
+                    beacon_failing = false;
+                    last_beacon = Instant::now() - BEACON_INTERVAL;
+                    let _ = evt_tx.send(RuntimeEvent::Beacon { status: None });
+                    chain_id = p.chain_id().await.ok();
+                    if let Some(chain_id) = chain_id {
+                        catalog.set_chain(chain_id);
+                        health.set_chain(endpoint_index, chain_id);
+                    }
                     provider = Some(p);
                     block_subscription = sub;
                     last_block = None;
+                    target_head = None;
 
                     // Fetch initial snapshot
                     if let Some(ref p) = provider {
+                        let started = Instant::now();
                         if let Ok(head) = p.block_number().await {
+                            health.record_success(endpoint_index, elapsed_ms(started), head, Instant::now());
                             fetch_snapshot(
                                 p.as_ref(),
                                 head,
                                 &evt_tx,
                                 &resolver,
+                                &mut catalog,
+                                &mut metrics,
+                                pressure.fetch_receipts(),
                             )
                             .await;
                             last_block = Some(head);
@@ -69,13 +192,7 @@ pub async fn run_async_worker(
                     let _ = evt_tx.send(RuntimeEvent::Error {
                         message: format!("Connection failed ({}): {:#}", config.display(), err),
                     });
-
-                    // Try next endpoint if available
-                    if endpoints.len() > 1 {
-                        endpoint_index = (endpoint_index + 1) % endpoints.len();
-                    }
-
-                    tokio::time::sleep(Duration::from_millis(900)).await;
+                    fail_over(&mut health, &endpoints, &mut endpoint_index, "connection failed", &evt_tx);
                     continue;
                 }
             }
@@ -84,7 +201,12 @@ pub async fn run_async_worker(
         // Process commands (non-blocking)
         while let Ok(cmd) = cmd_rx.try_recv() {
             match cmd {
-                RuntimeCommand::Shutdown => return Ok(()),
+                RuntimeCommand::Shutdown => {
+                    if let Some(task) = comparison.take() {
+                        task.abort();
+                    }
+                    return Ok(());
+                }
 
                 RuntimeCommand::SwitchEndpoint { index } => {
                     if index >= endpoints.len() {
@@ -98,6 +220,7 @@ pub async fn run_async_worker(
                         continue;
                     }
                     endpoint_index = index;
+                    health.set_preferred(index);
                     provider = None;
                     block_subscription = None;
                     last_block = None;
@@ -111,6 +234,9 @@ pub async fn run_async_worker(
                                 head,
                                 &evt_tx,
                                 &resolver,
+                                &mut catalog,
+                                &mut metrics,
+                                pressure.fetch_receipts(),
                             )
                             .await;
                             last_block = Some(head);
@@ -137,14 +263,40 @@ pub async fn run_async_worker(
                     }
                 }
 
+                RuntimeCommand::FetchTransfers { tx_hash, tokens } => {
+                    if let Some(ref p) = provider {
+                        let p = reads(p.as_ref(), &quorum);
+                        if let Some(hash) = parse_b256(&tx_hash) {
+                            match fetch_transfer_summary(
+                                p,
+                                hash,
+                                &tokens,
+                                &mut catalog,
+                                &mut multicall,
+                            )
+                            .await {
+                                Ok(summary) => {
+                                    let _ = evt_tx.send(RuntimeEvent::TransfersReady { summary });
+                                }
+                                Err(err) => {
+                                    let _ = evt_tx.send(RuntimeEvent::Error {
+                                        message: format!("Transfer summary failed: {:#}", err),
+                                    });
+                                }
+                            }
+                        }
+                    }
+                }
+
                 RuntimeCommand::FetchBalance { address } => {
                     if let Some(ref p) = provider {
+                        let p = reads(p.as_ref(), &quorum);
                         if let Some(addr) = parse_address(&address) {
-                            match p.get_balance(addr).await {
+                            match p.get_balance(addr, None).await {
                                 Ok(balance) => {
                                     let balance_eth = wei_to_eth(balance);
                                     let _ = evt_tx.send(RuntimeEvent::BalanceReady {
-                                        address,
+                                        address: address.clone(),
                                         balance: balance_eth,
                                     });
                                 }
@@ -154,36 +306,72 @@ pub async fn run_async_worker(
                                     });
                                 }
                             }
+                            // A delegated EOA runs its delegate's code; decode calls to it with that ABI
+                            if let Ok(code) = p.get_code(addr).await {
+                                let delegate = parse_delegation(&code);
+                                if let (Some(delegate), Some(chain_id)) = (&delegate, chain_id) {
+                                    resolve_abi(&resolver, chain_id, delegate.clone(), &evt_tx);
+                                }
+                                let _ = evt_tx.send(RuntimeEvent::Delegation { address, delegate });
+                            }
                         }
                     }
                 }
 
-                RuntimeCommand::FetchTokenBalances { address, tokens } => {
+                RuntimeCommand::FetchTokenBalances { address, mut tokens } => {
                     if let Some(ref p) = provider {
+                        let p = reads(p.as_ref(), &quorum);
                         if let Some(owner) = parse_address(&address) {
-                            let balances = fetch_token_balances(p.as_ref(), owner, &tokens).await;
+                            // Configured tokens plus everything the address touched
+                            for token in catalog.touched_tokens(&address) {
+                                if !tokens.iter().any(|t| t.address.eq_ignore_ascii_case(&token)) {
+                                    tokens.push(TokenConfig {
+                                        address: token,
+                                        symbol: None,
+                                        decimals: None,
+                                    });
+                                }
+                            }
+                            let balances =
+                                fetch_token_balances(
+                                p,
+                                owner,
+                                &tokens,
+                                &mut catalog,
+                                &mut multicall,
+                            )
+                            .await;
                             let _ = evt_tx.send(RuntimeEvent::TokenBalancesReady { address, balances });
                         }
                     }
                 }
 
-                RuntimeCommand::FetchStorage { address, slot } => {
+                RuntimeCommand::FetchStorage { address, slots } => {
                     if let Some(ref p) = provider {
-                        if let (Some(addr), Some(slot_u256)) =
-                            (parse_address(&address), parse_u256(&slot))
-                        {
-                            match p.get_storage_at(addr, slot_u256).await {
-                                Ok(value) => {
-                                    let _ = evt_tx.send(RuntimeEvent::StorageReady {
-                                        address,
-                                        slot,
-                                        value: format!("{:?}", value),
-                                    });
-                                }
-                                Err(err) => {
-                                    let _ = evt_tx.send(RuntimeEvent::Error {
-                                        message: format!("Storage fetch failed: {:#}", err),
-                                    });
+                        let p = reads(p.as_ref(), &quorum);
+                        if let Some(addr) = parse_address(&address) {
+                            // eth_getStorageAt has no eth_call form, so Multicall3 can't
+                            // batch it; issue the reads concurrently instead
+                            let slots: Vec<(String, U256)> = slots
+                                .into_iter()
+                                .filter_map(|slot| parse_u256(&slot).map(|value| (slot, value)))
+                                .collect();
+                            let reads = slots.iter().map(|(_, slot)| p.get_storage_at(addr, *slot, None));
+                            let results = futures::future::join_all(reads).await;
+                            for ((slot, _), result) in slots.into_iter().zip(results) {
+                                match result {
+                                    Ok(value) => {
+                                        let _ = evt_tx.send(RuntimeEvent::StorageReady {
+                                            address: address.clone(),
+                                            slot,
+                                            value: format!("{:?}", value),
+                                        });
+                                    }
+                                    Err(err) => {
+                                        let _ = evt_tx.send(RuntimeEvent::Error {
+                                            message: format!("Storage fetch failed: {:#}", err),
+                                        });
+                                    }
                                 }
                             }
                         }
@@ -199,6 +387,56 @@ pub async fn run_async_worker(
                     });
                 }
 
+                RuntimeCommand::SetWatchedAddresses { addresses } => {
+                    alerts.set_watched(addresses);
+                    send_alerts(&mut alerts, &evt_tx);
+                }
+
+                RuntimeCommand::SetMetricsWindow { window } => {
+                    metrics_window = window;
+                    let _ = evt_tx.send(RuntimeEvent::Metrics {
+                        window,
+                        series: metrics.view(now_secs(), window),
+                    });
+                }
+
+                RuntimeCommand::SetQuorum { enabled } => {
+                    quorum = None;
+                    quorum_generation += 1;
+                    if enabled {
+                        let configs: Vec<_> = endpoints
+                            .iter()
+                            .zip(&budgets)
+                            .map(|(spec, budget)| (spec.provider.clone(), budget.clone()))
+                            .collect();
+                        let quorum_tx = quorum_tx.clone();
+                        let log = quorum_log.clone();
+                        let generation = quorum_generation;
+                        tokio::spawn(async move {
+                            let _ = quorum_tx.send((generation, QuorumProvider::connect(configs, log).await));
+                        });
+                    } else {
+                        let _ = evt_tx.send(RuntimeEvent::Quorum { endpoints: Vec::new() });
+                    }
+                }
+
+                RuntimeCommand::SetNodeComparison { enabled } => {
+                    if let Some(task) = comparison.take() {
+                        task.abort();
+                    }
+                    if enabled {
+                        let comparer = NodeComparer::new(
+                            endpoints
+                                .iter()
+                                .zip(&budgets)
+                                .map(|(spec, budget)| (spec.provider.clone(), budget.clone()))
+                                .collect(),
+                            rpc_timings.clone(),
+                        );
+                        comparison = Some(tokio::spawn(comparer.run(evt_tx.clone())));
+                    }
+                }
+
                 RuntimeCommand::ResolveSelector { selector } => {
                     // Resolve selector via 4byte API
                     // The resolver has internal caching to avoid duplicate API calls
@@ -220,228 +458,308 @@ pub async fn run_async_worker(
                 }
 
                 RuntimeCommand::ResolveAbi { chain_id, address } => {
-                    // Resolve ABI via Sourcify
-                    let resolver = Arc::clone(&resolver);
-                    let evt_tx = evt_tx.clone();
-                    tokio::spawn(async move {
-                        if let Ok(Some(abi)) = resolver.lookup_abi(chain_id, &address).await {
-                            let _ = evt_tx.send(RuntimeEvent::AbiResolved {
-                                chain_id,
-                                address: abi.address,
-                                abi_json: abi.abi_json,
-                                contract_name: abi.contract_name,
-                            });
-                        }
+                    resolve_abi(&resolver, chain_id, address, &evt_tx);
+                }
+            }
+        }
+
+        while let Ok((generation, result)) = quorum_rx.try_recv() {
+            if generation != quorum_generation {
+                continue;
+            }
+            match result {
+                Ok(provider) => quorum = Some(provider),
+                Err(err) => {
+                    let _ = evt_tx.send(RuntimeEvent::Error {
+                        message: format!("Quorum reads unavailable: {:#}", err),
                     });
                 }
             }
+            let _ = evt_tx.send(RuntimeEvent::Quorum {
+                endpoints: quorum.as_ref().map(QuorumProvider::endpoints).unwrap_or_default(),
+            });
+        }
+        for report in quorum_log.drain() {
+            let _ = evt_tx.send(RuntimeEvent::QuorumReport { report });
+        }
+
+        // Slow down or speed back up as the endpoint's budget allows
+        let budget = &budgets[endpoint_index];
+        if budget.pressure() != pressure {
+            pressure = budget.pressure();
+            send_budget(&endpoints, endpoint_index, budget, &evt_tx);
         }
 
         // Check for new blocks
         if let Some(ref p) = provider {
-            // Try WebSocket subscription first
+            // Subscribed endpoints announce heads; blocks are fetched below like polled ones
             if let Some(ref mut sub) = block_subscription {
                 while let Ok(block) = sub.try_recv() {
-                    let block_number = block.header.number;
-
-                    // Fetch full block with transactions
-                    if let Ok(Some(full_block)) = p.get_block(block_number).await {
-                        let (block_info, txs, selectors) =
-                            process_block(p.as_ref(), &full_block).await;
-                        let _ = evt_tx.send(RuntimeEvent::NewBlock { block: block_info, txs });
-
-                        // Auto-resolve any new selectors
-                        // Debug: Log collected selectors
-                        if !selectors.is_empty() {
-                            if let Ok(mut f) = std::fs::OpenOptions::new()
-                                .create(true)
-                                .append(true)
-                                .open("/tmp/poke-abi-debug.log")
-                            {
-                                use std::io::Write;
-                                let _ = writeln!(f, "[WORKER] Block {} has {} selectors: {:?}", block_number, selectors.len(), selectors);
-                            }
-                        }
-
-                        for selector in selectors {
-                            // The resolver has internal caching to avoid duplicate API calls
-                            let resolver = Arc::clone(&resolver);
-                            let evt_tx = evt_tx.clone();
-                            let sel_for_log = selector.clone();
-                            tokio::spawn(async move {
-                                match parse_selector(&selector) {
-                                    Ok(sel_bytes) => {
-                                        match resolver.lookup_selector(sel_bytes).await {
-                                            Ok(Some(sig)) => {
-                                                // Debug: Log event send
-                                                if let Ok(mut f) = std::fs::OpenOptions::new()
-                                                    .create(true)
-                                                    .append(true)
-                                                    .open("/tmp/poke-abi-debug.log")
-                                                {
-                                                    use std::io::Write;
-                                                    let _ = writeln!(f, "[WORKER-WS] Resolved {}: {}", sel_for_log, sig.signature);
-                                                }
-                                                let _ = evt_tx.send(RuntimeEvent::SignatureResolved {
-                                                    selector,
-                                                    name: sig.name,
-                                                    signature: sig.signature,
-                                                });
-                                            }
-                                            Ok(None) => {
-                                                // No signature found (common for custom contracts)
-                                            }
-                                            Err(e) => {
-                                                if let Ok(mut f) = std::fs::OpenOptions::new()
-                                                    .create(true)
-                                                    .append(true)
-                                                    .open("/tmp/poke-abi-debug.log")
-                                                {
-                                                    use std::io::Write;
-                                                    let _ = writeln!(f, "[WORKER-WS] Error resolving {}: {:?}", sel_for_log, e);
-                                                }
-                                            }
-                                        }
-                                    }
-                                    Err(e) => {
-                                        if let Ok(mut f) = std::fs::OpenOptions::new()
-                                            .create(true)
-                                            .append(true)
-                                            .open("/tmp/poke-abi-debug.log")
-                                        {
-                                            use std::io::Write;
-                                            let _ = writeln!(f, "[WORKER-WS] Parse error for {}: {:?}", sel_for_log, e);
-                                        }
-                                    }
-                                }
-                            });
-                        }
-                        last_block = Some(block_number);
+                    let number = block.header.number;
+                    // A head at or below the last processed height replaces reorged blocks
+                    if last_block.is_none_or(|last| number <= last) {
+                        last_block = Some(number.saturating_sub(1));
+                        target_head = Some(number);
+                    } else {
+                        target_head = target_head.max(Some(number));
                     }
                 }
             }
 
-            // Fall back to polling for HTTP endpoints
-            if !p.supports_subscriptions() {
-                poll_interval.tick().await;
-
+            // Fall back to polling for HTTP endpoints; checked rather than awaited so
+            // commands are still served while a throttled endpoint polls slowly
+            if !p.supports_subscriptions() && last_poll.elapsed() >= pressure.poll_interval() {
+                last_poll = Instant::now();
                 match p.block_number().await {
                     Ok(head) => {
-                        if let Some(last) = last_block {
-                            if head > last {
-                                // Fetch missing blocks
-                                for number in (last + 1)..=head {
-                                    if let Ok(Some(block)) = p.get_block(number).await {
-                                        let (block_info, txs, selectors) =
-                                            process_block(p.as_ref(), &block).await;
-                                        let _ = evt_tx.send(RuntimeEvent::NewBlock {
-                                            block: block_info,
-                                            txs,
-                                        });
-
-                                        // Auto-resolve any new selectors (HTTP polling path)
-                                        if !selectors.is_empty() {
-                                            if let Ok(mut f) = std::fs::OpenOptions::new()
-                                                .create(true)
-                                                .append(true)
-                                                .open("/tmp/poke-abi-debug.log")
-                                            {
-                                                use std::io::Write;
-                                                let _ = writeln!(f, "[WORKER-HTTP] Block {} has {} selectors: {:?}", number, selectors.len(), selectors);
-                                            }
-                                        }
-
-                                        for selector in selectors {
-                                            // The resolver has internal caching to avoid duplicate API calls
-                                            let resolver = Arc::clone(&resolver);
-                                            let evt_tx = evt_tx.clone();
-                                            let sel_for_log = selector.clone();
-                                            tokio::spawn(async move {
-                                                match parse_selector(&selector) {
-                                                    Ok(sel_bytes) => {
-                                                        match resolver.lookup_selector(sel_bytes).await {
-                                                            Ok(Some(sig)) => {
-                                                                // Debug log
-                                                                if let Ok(mut f) = std::fs::OpenOptions::new()
-                                                                    .create(true)
-                                                                    .append(true)
-                                                                    .open("/tmp/poke-abi-debug.log")
-                                                                {
-                                                                    use std::io::Write;
-                                                                    let _ = writeln!(f, "[WORKER-HTTP] Resolved {}: {}", sel_for_log, sig.signature);
-                                                                }
-                                                                let _ = evt_tx.send(
-                                                                    RuntimeEvent::SignatureResolved {
-                                                                        selector,
-                                                                        name: sig.name,
-                                                                        signature: sig.signature,
-                                                                    },
-                                                                );
-                                                            }
-                                                            Ok(None) => {}
-                                                            Err(e) => {
-                                                                if let Ok(mut f) = std::fs::OpenOptions::new()
-                                                                    .create(true)
-                                                                    .append(true)
-                                                                    .open("/tmp/poke-abi-debug.log")
-                                                                {
-                                                                    use std::io::Write;
-                                                                    let _ = writeln!(f, "[WORKER-HTTP] Error resolving {}: {:?}", sel_for_log, e);
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                    Err(e) => {
-                                                        if let Ok(mut f) = std::fs::OpenOptions::new()
-                                                            .create(true)
-                                                            .append(true)
-                                                            .open("/tmp/poke-abi-debug.log")
-                                                        {
-                                                            use std::io::Write;
-                                                            let _ = writeln!(f, "[WORKER-HTTP] Parse error for {}: {:?}", sel_for_log, e);
-                                                        }
-                                                    }
-                                                }
-                                            });
-                                        }
-                                    }
-                                }
-                                last_block = Some(head);
-                            }
-                        } else {
-                            last_block = Some(head);
-                        }
+                        last_block.get_or_insert(head);
+                        target_head = Some(head);
                     }
+                    // Throttled, not down: the budget already backs off
+                    Err(err) if rate_limited(&err).is_some() => {}
                     Err(err) => {
                         let _ = evt_tx.send(RuntimeEvent::Error {
                             message: format!("RPC error: {:#}", err),
                         });
                         provider = None;
                         block_subscription = None;
-
-                        // Try next endpoint
-                        if endpoints.len() > 1 {
-                            endpoint_index = (endpoint_index + 1) % endpoints.len();
-                        }
+                        fail_over(&mut health, &endpoints, &mut endpoint_index, "RPC error", &evt_tx);
                         continue;
                     }
                 }
             }
 
+            // Fetch missing blocks in order; a block that can't be fetched (throttled,
+            // not served yet) stops the run and is retried from there next time
+            if let (Some(last), Some(head)) = (last_block, target_head) {
+                for number in (last + 1)..=head {
+                    let Ok(Some(block)) = p.get_block(number).await else {
+                        break;
+                    };
+                    let (block_info, txs, selectors) =
+                        process_block(p.as_ref(), &block, &mut catalog, pressure.fetch_receipts()).await;
+                    observe_block_metrics(&mut metrics, &block, catalog.chain_id());
+                    alerts.observe_block(&block_info, &txs);
+                    let _ = evt_tx.send(RuntimeEvent::NewBlock { block: block_info, txs });
+                    send_alerts(&mut alerts, &evt_tx);
+                    resolve_selectors(&resolver, number, selectors, &evt_tx);
+                    last_block = Some(number);
+                }
+            }
+
             // Periodic status update
-            if last_status_check.elapsed() >= Duration::from_secs(2) {
-                if let Ok(block_number) = p.block_number().await {
+            if last_status_check.elapsed() >= pressure.status_interval() {
+                let started = Instant::now();
+                let status = p.block_number().await;
+                let rtt = started.elapsed();
+                if let Ok(block_number) = status {
+                    health.record_success(endpoint_index, rtt.as_secs_f64() * 1000.0, block_number, Instant::now());
                     let peer_count = p.peer_count().await.ok().map(|c| c as u32);
-                    let sync_progress = p.sync_progress().await.ok().flatten();
+                    let sync = p.sync_status().await.ok().flatten();
+                    let sync_progress = sync.map(|(current, highest)| {
+                        if highest == 0 {
+                            0.0
+                        } else {
+                            (current as f64 / highest as f64).clamp(0.0, 1.0)
+                        }
+                    });
+                    let head_lag = Some(sync.map_or(0, |(current, highest)| {
+                        highest.saturating_sub(current)
+                    }));
+                    let rtt_ms = Some(rtt.as_millis() as u64);
 
                     let _ = evt_tx.send(RuntimeEvent::Status {
-                        rtt_ms: Some(last_status_check.elapsed().as_millis() as u64),
+                        rtt_ms,
                         peer_count,
                         sync_progress,
                     });
-                    let _ = block_number; // suppress unused warning
+
+                    // Nodes without finality tags (pre-merge, some dev chains) reject
+                    // them; stop asking after the first refusal
+                    let mut heads = FinalityHeads::default();
+                    let mut finalized_block = None;
+                    if finality_tags != Some(false) {
+                        match p.get_tagged_block("finalized").await {
+                            Ok(block) => {
+                                finality_tags = Some(true);
+                                heads.safe = p.get_tagged_block("safe").await.ok().flatten().map(|b| b.number);
+                                heads.finalized = block.as_ref().map(|b| b.number);
+                                finalized_block = block;
+                            }
+                            // Only the node's refusal says the tags are unsupported; a timeout doesn't
+                            Err(err) if finality_tags.is_none() && rpc_error_response(&err) => {
+                                finality_tags = Some(false);
+                            }
+                            Err(_) => {}
+                        }
+                    }
+                    let _ = evt_tx.send(RuntimeEvent::Finality { heads });
+
+                    if op_stack != Some(false) {
+                        match fetch_l1_origin(p.as_ref(), &mut multicall).await {
+                            Ok(Some(origin)) => {
+                                op_stack = Some(true);
+                                let _ = evt_tx.send(RuntimeEvent::L1Origin { origin: Some(origin) });
+                            }
+                            // Not an OP Stack chain; stop asking
+                            Ok(None) => op_stack = Some(false),
+                            // Nothing known about the chain yet; try again next poll
+                            Err(_) if op_stack.is_none() => {}
+                            Err(err) => {
+                                let _ = evt_tx.send(RuntimeEvent::L1Origin { origin: None });
+                                let _ = evt_tx.send(RuntimeEvent::Error {
+                                    message: format!("L1Block read failed: {:#}", err),
+                                });
+                            }
+                        }
+                    }
+
+                    alerts.observe_status(peer_count, rtt_ms, head_lag);
+                    if let Some(finalized) = &finalized_block {
+                        alerts.observe_finalized(p.as_ref(), finalized).await;
+                    }
+                    send_alerts(&mut alerts, &evt_tx);
+
+                    let now = now_secs();
+                    if let Some(peers) = peer_count {
+                        metrics.observe_peers(now, peers);
+                    }
+                    for (method, ms) in rpc_timings.drain(&p.endpoint_name()) {
+                        metrics.observe_latency(now, method, ms);
+                    }
+                    let _ = evt_tx.send(RuntimeEvent::Metrics {
+                        window: metrics_window,
+                        series: metrics.view(now, metrics_window),
+                    });
+                    let _ = evt_tx.send(RuntimeEvent::RpcStats {
+                        rows: rpc_timings.rows(),
+                    });
+                    if !endpoints[endpoint_index].budget.is_unlimited() {
+                        send_budget(&endpoints, endpoint_index, &budgets[endpoint_index], &evt_tx);
+                    }
                 }
                 last_status_check = Instant::now();
+                match status {
+                    // Throttled, not down: the budget already backs off
+                    Err(err) if rate_limited(&err).is_none() => {
+                        let _ = evt_tx.send(RuntimeEvent::Error {
+                            message: format!("RPC error: {:#}", err),
+                        });
+                        provider = None;
+                        block_subscription = None;
+                        fail_over(&mut health, &endpoints, &mut endpoint_index, "RPC error", &evt_tx);
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Health probes of the other endpoints, and failover/failback on what they show
+        while let Ok((index, result)) = probe_rx.try_recv() {
+            match result {
+                Ok(probe) => {
+                    health.set_chain(index, probe.chain_id);
+                    health.record_success(index, probe.latency_ms, probe.head, Instant::now());
+                }
+                Err(_) => {
+                    health.record_failure(index, Instant::now(), jitter());
+                }
+            }
+        }
+        if endpoints.len() > 1 {
+            for index in health.due_probes(endpoint_index, Instant::now()) {
+                let config = endpoints[index].provider.clone();
+                let budget = budgets[index].clone();
+                let probe_tx = probe_tx.clone();
+                let probes = probes.clone();
+                tokio::spawn(async move {
+                    let _ = probe_tx.send((index, probes.probe(index, config, budget).await));
+                });
+            }
+            if provider.is_some() {
+                if let Some(switch) = health.decide(endpoint_index, Instant::now()) {
+                    send_failover(&endpoints, endpoint_index, switch.to, &switch.reason, &evt_tx);
+                    endpoint_index = switch.to;
+                    provider = None;
+                    block_subscription = None;
+                    last_block = None;
+                    continue;
+                }
+            }
+        }
+
+        // Node metrics endpoint, if the current endpoint has one
+        while let Ok((index, result)) = scrape_rx.try_recv() {
+            scrape_in_flight = false;
+            if index != endpoint_index {
+                continue;
+            }
+            match result {
+                Ok(samples) => {
+                    scrape_failing = false;
+                    let now = now_secs();
+                    for metric in node_metrics.extract(&samples, now) {
+                        metrics.observe_node(now, metric.key, metric.label, metric.unit, metric.value);
+                    }
+                }
+                Err(err) => {
+                    // Report once, not on every interval
+                    if !scrape_failing {
+                        let _ = evt_tx.send(RuntimeEvent::Error {
+                            message: format!("Metrics scrape failed: {:#}", err),
+                        });
+                    }
+                    scrape_failing = true;
+                }
+            }
+        }
+        if let Some(url) = endpoints[endpoint_index].metrics_url.clone() {
+            if provider.is_some() && !scrape_in_flight && last_scrape.elapsed() >= SCRAPE_INTERVAL {
+                scrape_in_flight = true;
+                last_scrape = Instant::now();
+                let http = http.clone();
+                let scrape_tx = scrape_tx.clone();
+                let index = endpoint_index;
+                tokio::spawn(async move {
+                    let _ = scrape_tx.send((index, scrape(&http, &url).await));
+                });
+            }
+        }
+
+        // Beacon node of the current endpoint, if it has one
+        while let Ok((index, result)) = beacon_rx.try_recv() {
+            beacon_in_flight = false;
+            if index != endpoint_index {
+                continue;
+            }
+            match result {
+                Ok(status) => {
+                    beacon_failing = false;
+                    let _ = evt_tx.send(RuntimeEvent::Beacon { status: Some(status) });
+                }
+                Err(err) => {
+                    if !beacon_failing {
+                        let _ = evt_tx.send(RuntimeEvent::Beacon { status: None });
+                        let _ = evt_tx.send(RuntimeEvent::Error {
+                            message: format!("Beacon node unreachable: {:#}", err),
+                        });
+                    }
+                    beacon_failing = true;
+                }
+            }
+        }
+        if let Some(url) = endpoints[endpoint_index].beacon_url.clone() {
+            if provider.is_some() && !beacon_in_flight && last_beacon.elapsed() >= BEACON_INTERVAL {
+                beacon_in_flight = true;
+                last_beacon = Instant::now();
+                let http = http.clone();
+                let auth = endpoints[endpoint_index].beacon_auth.clone();
+                let beacon_tx = beacon_tx.clone();
+                let index = endpoint_index;
+                tokio::spawn(async move {
+                    let _ = beacon_tx.send((index, fetch_status(&http, &url, &auth).await));
+                });
             }
         }
 
@@ -450,12 +768,183 @@ pub async fn run_async_worker(
     }
 }
 
+/// The quorum provider while quorum reads are on, else the connected one
+fn reads<'a>(
+    provider: &'a dyn EthereumProvider,
+    quorum: &'a Option<QuorumProvider>,
+) -> &'a dyn EthereumProvider {
+    match quorum {
+        Some(quorum) => quorum,
+        None => provider,
+    }
+}
+
+/// Record a failure of the current endpoint and move to the healthiest one that may be
+/// tried; with none available the connect path waits out the backoff
+fn fail_over(
+    health: &mut HealthTracker,
+    endpoints: &[EndpointSpec],
+    endpoint_index: &mut usize,
+    reason: &str,
+    evt_tx: &Sender<RuntimeEvent>,
+) {
+    health.record_failure(*endpoint_index, Instant::now(), jitter());
+    if let Some(next) = health.best_available(*endpoint_index, false, Instant::now()) {
+        send_failover(endpoints, *endpoint_index, next, reason, evt_tx);
+        *endpoint_index = next;
+    }
+}
+
+fn send_failover(
+    endpoints: &[EndpointSpec],
+    from: usize,
+    to: usize,
+    reason: &str,
+    evt_tx: &Sender<RuntimeEvent>,
+) {
+    let _ = evt_tx.send(RuntimeEvent::Failover {
+        from: endpoints[from].provider.display(),
+        to: endpoints[to].provider.display(),
+        reason: reason.to_string(),
+    });
+}
+
+fn send_budget(
+    endpoints: &[EndpointSpec],
+    index: usize,
+    budget: &SharedBudget,
+    evt_tx: &Sender<RuntimeEvent>,
+) {
+    if let Some(status) = budget.status() {
+        let _ = evt_tx.send(RuntimeEvent::Budget {
+            endpoint: endpoints[index].provider.display(),
+            status,
+        });
+    }
+}
+
+fn elapsed_ms(started: Instant) -> f64 {
+    started.elapsed().as_secs_f64() * 1000.0
+}
+
+fn observe_block_metrics(metrics: &mut MetricsStore, block: &RawBlock, chain_id: u64) {
+    let recorded = metrics.observe_block(
+        block.number,
+        block.timestamp,
+        block.gas_used,
+        block.gas_limit,
+        block.base_fee_per_gas,
+        block.transactions.len(),
+    );
+    if let (true, Some(used), Some(excess)) = (recorded, block.blob_gas_used, block.excess_blob_gas) {
+        let fee = blob_params(chain_id, block.timestamp).map(|params| blob_base_fee(excess, params));
+        metrics.observe_blobs(block.timestamp, blob_count(used), fee);
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Look up the signatures of selectors seen in a block; the resolver caches, so
+/// repeats cost nothing
+fn resolve_selectors(
+    resolver: &Arc<AbiResolver>,
+    block_number: u64,
+    selectors: Vec<String>,
+    evt_tx: &Sender<RuntimeEvent>,
+) {
+    // Debug: Log collected selectors
+    if !selectors.is_empty() {
+        if let Ok(mut f) = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open("/tmp/poke-abi-debug.log")
+        {
+            use std::io::Write;
+            let _ = writeln!(f, "[WORKER] Block {} has {} selectors: {:?}", block_number, selectors.len(), selectors);
+        }
+    }
+
+    for selector in selectors {
+        let resolver = Arc::clone(resolver);
+        let evt_tx = evt_tx.clone();
+        let sel_for_log = selector.clone();
+        tokio::spawn(async move {
+            match parse_selector(&selector) {
+                Ok(sel_bytes) => {
+                    match resolver.lookup_selector(sel_bytes).await {
+                        Ok(Some(sig)) => {
+                            // Debug: Log event send
+                            if let Ok(mut f) = std::fs::OpenOptions::new()
+                                .create(true)
+                                .append(true)
+                                .open("/tmp/poke-abi-debug.log")
+                            {
+                                use std::io::Write;
+                                let _ = writeln!(f, "[WORKER] Resolved {}: {}", sel_for_log, sig.signature);
+                            }
+                            let _ = evt_tx.send(RuntimeEvent::SignatureResolved {
+                                selector,
+                                name: sig.name,
+                                signature: sig.signature,
+                            });
+                        }
+                        Ok(None) => {
+                            // No signature found (common for custom contracts)
+                        }
+                        Err(e) => {
+                            if let Ok(mut f) = std::fs::OpenOptions::new()
+                                .create(true)
+                                .append(true)
+                                .open("/tmp/poke-abi-debug.log")
+                            {
+                                use std::io::Write;
+                                let _ = writeln!(f, "[WORKER] Error resolving {}: {:?}", sel_for_log, e);
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    if let Ok(mut f) = std::fs::OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open("/tmp/poke-abi-debug.log")
+                    {
+                        use std::io::Write;
+                        let _ = writeln!(f, "[WORKER] Parse error for {}: {:?}", sel_for_log, e);
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Evaluate alert rules and forward every fired/resolved transition
+fn send_alerts(alerts: &mut AlertMonitor, evt_tx: &Sender<RuntimeEvent>) {
+    for event in alerts.evaluate(evt_tx) {
+        let _ = evt_tx.send(RuntimeEvent::Alert { event });
+    }
+}
+
 /// Connect to an endpoint and return the provider and optional block subscription
 async fn connect_to_endpoint(
     config: ProviderConfig,
+    timings: &RpcTimings,
+    budget: &SharedBudget,
     evt_tx: &Sender<RuntimeEvent>,
 ) -> Result<(Box<dyn EthereumProvider>, Option<tokio::sync::mpsc::Receiver<Block>>)> {
-    let provider = create_provider(config.clone()).await?;
+    // Timings measure the node, not the time spent waiting on the budget
+    let provider: Box<dyn EthereumProvider> = Box::new(BudgetedProvider::new(
+        Box::new(InstrumentedProvider::new(
+            create_provider(config.clone()).await?,
+            timings.clone(),
+        )),
+        budget.clone(),
+    ));
 
     // Get node info
     let client_version = provider
@@ -463,6 +952,7 @@ async fn connect_to_endpoint(
         .await
         .context("Failed to get client version")?;
     let node_kind = detect_node_kind(&client_version);
+    let chain_id = provider.chain_id().await.ok();
     let accounts = provider.accounts().await.unwrap_or_default();
     let supports_subscriptions = provider.supports_subscriptions();
 
@@ -479,6 +969,7 @@ async fn connect_to_endpoint(
     let _ = evt_tx.send(RuntimeEvent::Connected {
         endpoint: provider.endpoint_name(),
         node_kind,
+        chain_id,
         accounts,
     });
 
@@ -505,11 +996,16 @@ async fn fetch_snapshot(
     head: u64,
     evt_tx: &Sender<RuntimeEvent>,
     resolver: &Arc<AbiResolver>,
+    catalog: &mut TokenCatalog,
+    metrics: &mut MetricsStore,
+    fetch_receipts: bool,
 ) {
     let start = head.saturating_sub(10);
     for number in start..=head {
         if let Ok(Some(block)) = provider.get_block(number).await {
-            let (block_info, txs, selectors) = process_block(provider, &block).await;
+            let (block_info, txs, selectors) =
+                process_block(provider, &block, catalog, fetch_receipts).await;
+            observe_block_metrics(metrics, &block, catalog.chain_id());
             let _ = evt_tx.send(RuntimeEvent::NewBlock { block: block_info, txs });
 
             // Auto-resolve any new selectors (fetch_snapshot path)
@@ -584,17 +1080,11 @@ async fn fetch_snapshot(
 async fn process_block(
     provider: &dyn EthereumProvider,
     block: &RawBlock,
+    catalog: &mut TokenCatalog,
+    fetch_receipts: bool,
 ) -> (BlockInfo, Vec<TxInfo>, Vec<String>) {
     let block_number = block.number;
-    let tx_count = block.transactions.len();
-
-    let block_info = BlockInfo {
-        number: block_number,
-        tx_count: tx_count as u32,
-        gas_used: block.gas_used,
-        base_fee: block.base_fee_per_gas.map(|f| f / 1_000_000_000).unwrap_or(0),
-        miner: block.miner.clone(),
-    };
+    let block_info = block_info(block, catalog.chain_id());
 
     let mut txs = Vec::new();
     let mut selectors = HashSet::new();
@@ -604,7 +1094,7 @@ async fn process_block(
         let tx_hash = parse_b256(&raw_tx.hash);
 
         // Only fetch receipts for first 20 txs to avoid overload
-        let receipt = if i < 20 {
+        let receipt = if fetch_receipts && i < 20 {
             if let Some(hash) = tx_hash {
                 provider.get_receipt(hash).await.ok().flatten()
             } else {
@@ -614,6 +1104,11 @@ async fn process_block(
             None
         };
 
+        // Discover tokens and their holders from Transfer logs
+        if let Some(receipt) = receipt.as_ref() {
+            catalog.observe(&receipt_transfers(catalog.chain_id(), receipt));
+        }
+
         let tx_info = convert_raw_tx(raw_tx, receipt.as_ref(), block_number);
 
         // Collect selector for resolution if it's a contract call
@@ -625,13 +1120,33 @@ async fn process_block(
         txs.push(tx_info);
     }
 
+    catalog.flush_holders();
+
     (block_info, txs, selectors.into_iter().collect())
 }
 
+/// Header summary of a raw block on `chain_id`
+pub fn block_info(block: &RawBlock, chain_id: u64) -> BlockInfo {
+    BlockInfo {
+        number: block.number,
+        hash: block.hash.clone(),
+        parent_hash: block.parent_hash.clone(),
+        tx_count: block.transactions.len() as u32,
+        gas_used: block.gas_used,
+        base_fee: block.base_fee_per_gas.map(|f| f / 1_000_000_000).unwrap_or(0),
+        miner: block.miner.clone(),
+        blob_count: block.blob_gas_used.map_or(0, blob_count) as u32,
+        blob_base_fee: block
+            .excess_blob_gas
+            .zip(blob_params(chain_id, block.timestamp))
+            .map(|(excess, params)| blob_base_fee(excess, params)),
+    }
+}
+
 /// Convert a raw transaction to TxInfo
-fn convert_raw_tx(
+pub fn convert_raw_tx(
     tx: &RawTransaction,
-    receipt: Option<&alloy::rpc::types::TransactionReceipt>,
+    receipt: Option<&AnyTransactionReceipt>,
     block_number: u64,
 ) -> TxInfo {
     let input = &tx.input;
@@ -678,55 +1193,216 @@ fn convert_raw_tx(
         decoded_args: None,
         decode_error: None,
         block_number,
+        blob_hashes: tx.blob_versioned_hashes.clone(),
+        max_fee_per_blob_gas: tx.max_fee_per_blob_gas,
+        blob_fee: receipt.and_then(|r| Some(r.blob_gas_used? as u128 * r.blob_gas_price?)),
+        authorizations: tx.authorization_list.clone(),
+        deposit: tx.deposit.clone(),
+        execution_fee: receipt.map(|r| r.gas_used as u128 * r.effective_gas_price),
+        l1_fee: receipt.and_then(receipt_l1_fee),
+        log_addresses: receipt.map(log_addresses).unwrap_or_default(),
     }
 }
 
-/// Fetch token balances for an address
+/// Emitter of every log, so contracts reached through internal calls count, plus its
+/// address-shaped indexed topics (12 zero bytes, then a non-zero address)
+fn log_addresses(receipt: &AnyTransactionReceipt) -> Vec<String> {
+    let mut addresses = BTreeSet::new();
+    for log in receipt.inner.logs() {
+        addresses.insert(format!("{:#x}", log.address()));
+        addresses.extend(
+            log.topics()
+                .iter()
+                .skip(1)
+                .filter(|topic| {
+                    topic[..12].iter().all(|byte| *byte == 0) && topic[12..].iter().any(|byte| *byte != 0)
+                })
+                .map(|topic| format!("{:#x}", Address::from_slice(&topic[12..]))),
+        );
+    }
+    addresses.into_iter().collect()
+}
+
+/// Resolve a contract ABI via Sourcify in the background
+fn resolve_abi(
+    resolver: &Arc<AbiResolver>,
+    chain_id: u64,
+    address: String,
+    evt_tx: &Sender<RuntimeEvent>,
+) {
+    let resolver = Arc::clone(resolver);
+    let evt_tx = evt_tx.clone();
+    tokio::spawn(async move {
+        if let Ok(Some(abi)) = resolver.lookup_abi(chain_id, &address).await {
+            let _ = evt_tx.send(RuntimeEvent::AbiResolved {
+                chain_id,
+                address: abi.address,
+                abi_json: abi.abi_json,
+                contract_name: abi.contract_name,
+            });
+        }
+    });
+}
+
+/// Fetch token balances for an address, batching reads through Multicall3
 async fn fetch_token_balances(
     provider: &dyn EthereumProvider,
     owner: Address,
     tokens: &[TokenConfig],
+    catalog: &mut TokenCatalog,
+    multicall: &mut Multicall,
 ) -> Vec<TokenBalance> {
-    let mut balances = Vec::new();
+    let tokens: Vec<(&TokenConfig, Address)> = tokens
+        .iter()
+        .filter_map(|token| parse_address(&token.address).map(|addr| (token, addr)))
+        .collect();
+
+    // Configured symbol/decimals win over what the contract reports
+    let unresolved: Vec<String> = tokens
+        .iter()
+        .filter(|(token, _)| token.symbol.is_none() || token.decimals.is_none())
+        .map(|(token, _)| token.address.clone())
+        .collect();
+    let metadata = catalog.metadata_many(provider, multicall, &unresolved).await;
+
+    // balanceOf(address) selector: 0x70a08231
+    let calls: Vec<ContractCall> = tokens
+        .iter()
+        .map(|(_, addr)| ContractCall::new(*addr, encode_balance_of(owner)))
+        .collect();
+    let results = multicall.call_all(provider, &calls).await;
+
+    tokens
+        .into_iter()
+        .zip(results)
+        .map(|((token, _), result)| {
+            let meta = metadata.get(&token.address.to_lowercase());
+            let symbol = token
+                .symbol
+                .clone()
+                .or_else(|| meta.and_then(|meta| meta.symbol.clone()));
+            let decimals = token.decimals.or_else(|| meta.and_then(|meta| meta.decimals));
+
+            let balance = match result {
+                CallResult::Returned(data) if data.len() >= 32 => {
+                    format_token_balance(U256::from_be_slice(&data[..32]), decimals)
+                }
+                CallResult::Returned(_) => "(decode err)".to_string(),
+                CallResult::Reverted => "(reverted)".to_string(),
+                CallResult::Failed => "(rpc err)".to_string(),
+            };
+
+            TokenBalance {
+                token: token.address.clone(),
+                symbol: symbol.unwrap_or_else(|| format!("0x{}…", &token.address[2..10])),
+                decimals,
+                balance,
+            }
+        })
+        .collect()
+}
 
-    for token in tokens {
-        let Some(token_addr) = parse_address(&token.address) else {
-            continue;
-        };
+/// Collect token transfers from receipt logs and native movements from the
+/// call trace, then resolve symbols/decimals for every token involved
+async fn fetch_transfer_summary(
+    provider: &dyn EthereumProvider,
+    hash: B256,
+    tokens: &[TokenConfig],
+    catalog: &mut TokenCatalog,
+    multicall: &mut Multicall,
+) -> Result<TransferSummary> {
+    let receipt = provider
+        .get_receipt(hash)
+        .await?
+        .context("receipt not found")?;
+
+    let mut summary = TransferSummary {
+        tx_hash: format!("{:#x}", hash),
+        ..Default::default()
+    };
 
-        // balanceOf(address) selector: 0x70a08231
-        let calldata = encode_balance_of(owner);
+    // Internal ETH movements need a call trace; without one only logs are summarized
+    if let Ok(frames) = provider.debug_trace_transaction(hash).await {
+        summary.transfers.extend(native_transfers(&frames));
+        summary.native_traced = true;
+    }
 
-        let request = TransactionRequest::default()
-            .to(token_addr)
-            .input(calldata.into());
+    let token_transfers = receipt_transfers(catalog.chain_id(), &receipt);
+    catalog.observe(&token_transfers);
+    catalog.flush_holders();
+    summary.transfers.extend(token_transfers);
+
+    let token_addresses: Vec<String> = summary
+        .transfers
+        .iter()
+        .filter_map(|transfer| transfer.asset.token_address().map(str::to_string))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let metadata = catalog
+        .metadata_many(provider, multicall, &token_addresses)
+        .await;
+    for (address, mut meta) in metadata {
+        if let Some(configured) = tokens
+            .iter()
+            .find(|token| token.address.eq_ignore_ascii_case(&address))
+        {
+            meta.symbol = configured.symbol.clone().or(meta.symbol);
+            meta.decimals = configured.decimals.or(meta.decimals);
+        }
+        summary.tokens.insert(address, meta);
+    }
 
-        let balance_str = match provider.call(request).await {
-            Ok(data) => {
-                if data.len() >= 32 {
-                    let value = U256::from_be_slice(&data[..32]);
-                    format_token_balance(value, token.decimals)
-                } else {
-                    "(decode err)".to_string()
-                }
-            }
-            Err(_) => "(rpc err)".to_string(),
-        };
+    Ok(summary)
+}
 
-        // Get symbol if not provided
-        let symbol = token.symbol.clone().unwrap_or_else(|| {
-            format!("0x{}…", &token.address[2..10])
-        });
+/// Value-carrying calls from a flattened call trace, skipping reverted subtrees
+fn native_transfers(frames: &[TraceFrame]) -> Vec<AssetTransfer> {
+    let mut transfers = Vec::new();
+    let mut reverted_depth: Option<usize> = None;
 
-        balances.push(TokenBalance {
-            token: token.address.clone(),
-            symbol,
-            decimals: token.decimals,
-            balance: balance_str,
+    for frame in frames {
+        if let Some(depth) = reverted_depth {
+            if frame.depth > depth {
+                continue;
+            }
+            reverted_depth = None;
+        }
+        if frame.status == CallStatus::Revert {
+            reverted_depth = Some(frame.depth);
+            continue;
+        }
+        // DELEGATECALL/STATICCALL report the caller's context value, nothing moves
+        if matches!(frame.call.as_str(), "DELEGATECALL" | "STATICCALL") || frame.value_wei.is_zero()
+        {
+            continue;
+        }
+        transfers.push(AssetTransfer {
+            asset: Asset::Native,
+            from: frame.from.to_lowercase(),
+            to: frame.to.to_lowercase(),
+            amount: frame.value_wei,
         });
     }
 
-    balances
+    transfers
+}
+
+/// Token transfers carried by a receipt's logs
+fn receipt_transfers(chain_id: u64, receipt: &AnyTransactionReceipt) -> Vec<AssetTransfer> {
+    receipt
+        .inner
+        .logs()
+        .iter()
+        .flat_map(|log| {
+            decode_transfer_log(
+                chain_id,
+                &format!("{:#x}", log.address()),
+                log.topics(),
+                &log.data().data,
+            )
+        })
+        .collect()
 }
 
 /// Encode balanceOf(address) call
@@ -2,6 +2,7 @@ mod app;
 mod config;
 mod core;
 mod domain;
+mod headless;
 mod infrastructure;
 mod modules;
 mod store;
@@ -14,7 +15,7 @@ use std::sync::mpsc::Sender;
 use std::thread;
 use std::time::{Duration, Instant};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 use crossterm::event::{
     self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
@@ -32,10 +33,15 @@ use crate::app::{
     App, DataMode, Focus, InputMode, ListKind, PromptKind, RpcEndpointOption, Section, StatusLevel,
     View,
 };
-use crate::domain::abi::AbiRegistry;
-use crate::infrastructure::ethereum::ProviderConfig;
-use crate::infrastructure::runtime::{RuntimeBridge, RuntimeCommand, RuntimeEvent, TokenConfig};
-use crate::store::LabelStore;
+use crate::domain::abi::AbiLayout;
+use crate::domain::alerts::{AlertCondition, AlertRule};
+use crate::domain::budget::BudgetConfig;
+use crate::infrastructure::abi::{AbiResolver, AbiScanner};
+use crate::infrastructure::ethereum::{parse_jwt_secret, EndpointAuth, ProviderConfig};
+use crate::infrastructure::runtime::{
+    AlertMonitor, EndpointSpec, RuntimeBridge, RuntimeCommand, RuntimeEvent, TokenCatalog, TokenConfig,
+};
+use crate::store::{ImportStats, LabelStore, SignatureStore, TokenStore};
 
 #[derive(Debug, Parser)]
 #[command(
@@ -44,28 +50,100 @@ use crate::store::LabelStore;
     about = "Poke: a local-first Ethereum node TUI tool"
 )]
 struct Args {
+    #[command(subcommand)]
+    command: Option<headless::Command>,
+
     /// HTTP JSON-RPC endpoint (e.g. http://localhost:8545)
-    #[arg(long)]
+    #[arg(long, global = true)]
     rpc: Option<String>,
 
     /// WebSocket endpoint (e.g. ws://localhost:8546)
-    #[arg(long)]
+    #[arg(long, global = true)]
     ws: Option<String>,
 
     /// IPC path (e.g. ~/.ethereum/geth.ipc). Unix only.
-    #[arg(long)]
+    #[arg(long, global = true)]
     ipc: Option<PathBuf>,
+
+    /// Import signature dumps (text, 4byte JSON or CSV) into the local signature DB and exit
+    #[arg(long, value_name = "FILE", num_args = 1..)]
+    import_signatures: Vec<PathBuf>,
+
+    /// Disable remote signature and ABI lookups
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Run without the TUI and serve Prometheus metrics on ADDR (e.g. 0.0.0.0:9898)
+    #[arg(long, value_name = "ADDR")]
+    serve_metrics: Option<String>,
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let mut args = Args::parse();
     let config = config::load();
+
+    if !args.import_signatures.is_empty() {
+        return import_signatures_cli(&args.import_signatures);
+    }
+
     let (rpc_endpoints, rpc_endpoint_options) = endpoints_from_args_and_config(&args, &config)?;
+
+    let watch = match args.command.take() {
+        Some(headless::Command::Watch(watch)) => Some(watch),
+        Some(headless::Command::Inspect(command)) => {
+            return run_command(command, &args, &config, rpc_endpoints)
+        }
+        None => None,
+    };
     let initial_endpoint_display = rpc_endpoints
         .first()
-        .map(|endpoint| endpoint.display())
+        .map(|endpoint| endpoint.provider.display())
         .unwrap_or_else(|| "localhost:8545".to_string());
 
+    // Local signature DB (plus configured dumps) backs selector resolution
+    let offline = args.offline || config.offline;
+    let (signature_store, signature_status) = open_signature_store(&config);
+    let mut resolver = AbiResolver::new().offline(offline);
+    if let Some(store) = signature_store {
+        resolver = resolver.with_signature_store(store);
+    }
+
+    // Token metadata and holder cache, shared across sessions per chain
+    let token_store = config::tokens_db_path().and_then(|db_path| {
+        if let Some(parent) = db_path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        TokenStore::open(&db_path).ok()
+    });
+
+    // Alert rules from config.toml, evaluated continuously by the worker
+    let (alert_rules, alert_errors) = alert_rules(&config.alerts);
+
+    // Create the runtime bridge
+    let runtime = RuntimeBridge::with_services(
+        rpc_endpoints,
+        resolver,
+        TokenCatalog::new(token_store),
+        AlertMonitor::new(alert_rules.clone()),
+    )?;
+
+    if watch.is_some() || args.serve_metrics.is_some() {
+        for err in &alert_errors {
+            eprintln!("poke: alert rule skipped: {err}");
+        }
+        let mut watched = config::labels_db_path()
+            .and_then(|db_path| LabelStore::open(&db_path).ok())
+            .and_then(|store| store.load_watched().ok())
+            .unwrap_or_default();
+        if let Some(watch) = watch {
+            watched.extend(watch.addresses.iter().map(|address| address.trim().to_string()));
+            return headless::watch(watch, runtime, watched);
+        }
+        if let Some(addr) = args.serve_metrics.as_deref() {
+            return headless::serve_metrics(addr, runtime, &alert_rules, watched);
+        }
+    }
+
     let mut stdout = io::stdout();
     enable_raw_mode()?;
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
@@ -73,12 +151,10 @@ fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create the runtime bridge
-    let runtime = RuntimeBridge::new(rpc_endpoints)?;
-
     // Spawn ABI scanner thread
     let abi_scan_roots = abi_scan_roots_from_config(&config);
-    let (abi_scan_tx, abi_evt_rx) = spawn_abi_scanner(abi_scan_roots.clone());
+    let (abi_layouts, unknown_layouts) = abi_layouts_from_config(&config);
+    let (abi_scan_tx, abi_evt_rx) = spawn_abi_scanner(abi_scan_roots.clone(), abi_layouts.clone());
 
     let mut app = App::new();
     app.data_mode = DataMode::Rpc;
@@ -92,7 +168,20 @@ fn main() -> Result<()> {
     app.node_kind = "connecting".to_string();
     app.abi_reload_sender = Some(abi_scan_tx);
     app.abi_scan_roots = abi_scan_roots;
+    app.abi_layouts = abi_layouts;
+    app.alert_rules = alert_rules;
     app.set_status("Connecting…", StatusLevel::Info);
+    if let Some((message, level)) = signature_status {
+        app.set_status(message, level);
+    } else if offline {
+        app.set_status("Offline mode: remote lookups disabled", StatusLevel::Info);
+    }
+    if let Some(err) = alert_errors.first() {
+        app.set_status(format!("Alert rule skipped: {err}"), StatusLevel::Warn);
+    }
+    if !unknown_layouts.is_empty() {
+        app.set_status(unknown_layouts_message(&unknown_layouts), StatusLevel::Warn);
+    }
 
     if let Some(db_path) = config::labels_db_path() {
         if let Some(parent) = db_path.parent() {
@@ -135,7 +224,7 @@ fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     mut app: App,
     runtime: RuntimeBridge,
-    abi_evt_rx: std::sync::mpsc::Receiver<AbiRegistry>,
+    abi_evt_rx: std::sync::mpsc::Receiver<RuntimeEvent>,
 ) -> Result<()> {
     let tick_rate = Duration::from_millis(200);
     let mut last_tick = Instant::now();
@@ -174,7 +263,7 @@ fn run_app<B: ratatui::backend::Backend>(
 fn pump_background(
     app: &mut App,
     runtime: &RuntimeBridge,
-    abi_evt_rx: &std::sync::mpsc::Receiver<AbiRegistry>,
+    abi_evt_rx: &std::sync::mpsc::Receiver<RuntimeEvent>,
 ) {
     // Process runtime events
     for event in runtime.poll_events() {
@@ -182,8 +271,9 @@ fn pump_background(
             RuntimeEvent::Connected {
                 endpoint,
                 node_kind,
+                chain_id,
                 accounts,
-            } => app.apply_rpc_connected(endpoint, node_kind, accounts),
+            } => app.apply_rpc_connected(endpoint, node_kind, chain_id, accounts),
             RuntimeEvent::Status {
                 rtt_ms,
                 peer_count,
@@ -192,80 +282,16 @@ fn pump_background(
             RuntimeEvent::NewBlock { block, txs } => {
                 if !app.paused {
                     // Convert infrastructure types to app types
-                    let block = app::BlockInfo {
-                        number: block.number,
-                        tx_count: block.tx_count,
-                        gas_used: block.gas_used,
-                        base_fee: block.base_fee,
-                        miner: block.miner,
-                    };
-                    let txs: Vec<app::TxInfo> = txs
-                        .into_iter()
-                        .map(|tx| app::TxInfo {
-                            hash: tx.hash,
-                            from: tx.from,
-                            to: tx.to,
-                            value: tx.value,
-                            gas_used: tx.gas_used,
-                            status: match tx.status {
-                                infrastructure::TxStatus::Success => app::TxStatus::Success,
-                                infrastructure::TxStatus::Revert => app::TxStatus::Revert,
-                                infrastructure::TxStatus::Unknown => app::TxStatus::Unknown,
-                            },
-                            input: tx.input,
-                            selector: tx.selector,
-                            method: tx.method,
-                            signature: tx.signature,
-                            decoded_args: tx.decoded_args.map(|args| {
-                                args.into_iter()
-                                    .map(|a| app::DecodedArg {
-                                        name: a.name,
-                                        kind: a.kind,
-                                        value: a.value,
-                                    })
-                                    .collect()
-                            }),
-                            decode_error: tx.decode_error,
-                            block_number: tx.block_number,
-                        })
-                        .collect();
-                    app.ingest_block(block, txs);
+                    let txs: Vec<app::TxInfo> = txs.into_iter().map(app::TxInfo::from).collect();
+                    app.ingest_block(block.into(), txs);
                 }
             }
             RuntimeEvent::TraceReady { frames, .. } => {
-                let frames: Vec<app::TraceFrame> = frames
-                    .into_iter()
-                    .map(|f| app::TraceFrame {
-                        depth: f.depth,
-                        call: f.call,
-                        from: f.from,
-                        to: f.to,
-                        value: f.value,
-                        gas_used: f.gas_used,
-                        status: match f.status {
-                            infrastructure::CallStatus::Ok => app::CallStatus::Ok,
-                            infrastructure::CallStatus::Revert => app::CallStatus::Revert,
-                        },
-                        note: f.note,
-                        collapsed: f.collapsed,
-                        input: f.input,
-                        selector: f.selector,
-                        method: f.method,
-                        signature: f.signature,
-                        decoded_args: f.decoded_args.map(|args| {
-                            args.into_iter()
-                                .map(|a| app::DecodedArg {
-                                    name: a.name,
-                                    kind: a.kind,
-                                    value: a.value,
-                                })
-                                .collect()
-                        }),
-                        decode_error: f.decode_error,
-                    })
-                    .collect();
+                let frames: Vec<app::TraceFrame> =
+                    frames.into_iter().map(app::TraceFrame::from).collect();
                 app.ingest_trace(frames);
             }
+            RuntimeEvent::TransfersReady { summary } => app.apply_transfer_summary(summary),
             RuntimeEvent::BalanceReady { address, balance } => app.apply_balance(address, balance),
             RuntimeEvent::TokenBalancesReady { address, balances } => {
                 let balances: Vec<app::TokenBalance> = balances
@@ -301,9 +327,33 @@ fn pump_background(
                 }
                 app.apply_signature(selector, name, signature);
             }
-            RuntimeEvent::AbiResolved { .. } => {
-                // TODO: integrate full ABI into registry for better decoding
+            RuntimeEvent::AbiResolved {
+                address,
+                abi_json,
+                contract_name,
+                ..
+            } => {
+                let source = PathBuf::from(format!("sourcify:{address}"));
+                match AbiScanner::parse_abi_json(&abi_json, &source) {
+                    Ok(functions) => app.apply_resolved_abi(address, contract_name, functions),
+                    Err(err) => app.set_status(
+                        format!("Sourcify ABI for {address} unreadable: {err}"),
+                        StatusLevel::Warn,
+                    ),
+                }
             }
+            RuntimeEvent::Delegation { address, delegate } => app.apply_delegation(address, delegate),
+            RuntimeEvent::Alert { event } => app.apply_alert(event),
+            RuntimeEvent::Metrics { window, series } => app.apply_metrics(window, series),
+            RuntimeEvent::NodeComparison { comparisons } => app.apply_node_comparison(comparisons),
+            RuntimeEvent::Failover { from, to, reason } => app.apply_failover(from, to, reason),
+            RuntimeEvent::Quorum { endpoints } => app.apply_quorum(endpoints),
+            RuntimeEvent::QuorumReport { report } => app.apply_quorum_report(report),
+            RuntimeEvent::RpcStats { rows } => app.apply_rpc_stats(rows),
+            RuntimeEvent::Budget { endpoint, status } => app.apply_budget(endpoint, status),
+            RuntimeEvent::Beacon { status } => app.apply_beacon(status),
+            RuntimeEvent::L1Origin { origin } => app.apply_l1_origin(origin),
+            RuntimeEvent::Finality { heads } => app.apply_finality(heads),
             RuntimeEvent::Error { message } => app.apply_rpc_error(message),
         }
     }
@@ -315,53 +365,133 @@ fn pump_background(
     if let Some(tx_hash) = app.take_trace_request() {
         let _ = runtime.send(RuntimeCommand::FetchTrace { tx_hash });
     }
+    if let Some(tx_hash) = app.take_transfers_request() {
+        let _ = runtime.send(RuntimeCommand::FetchTransfers {
+            tx_hash,
+            tokens: token_configs(&app.tokens),
+        });
+    }
     if app.take_refresh_request() {
         let _ = runtime.send(RuntimeCommand::Refresh);
     }
+    if let Some(window) = app.take_metrics_window_request() {
+        let _ = runtime.send(RuntimeCommand::SetMetricsWindow { window });
+    }
+    if let Some(enabled) = app.take_quorum_request() {
+        let _ = runtime.send(RuntimeCommand::SetQuorum { enabled });
+    }
+    if let Some(enabled) = app.take_node_comparison_update() {
+        let _ = runtime.send(RuntimeCommand::SetNodeComparison { enabled });
+    }
+    if let Some(addresses) = app.take_watchlist_update() {
+        let _ = runtime.send(RuntimeCommand::SetWatchedAddresses { addresses });
+    }
     if let Some(address) = app.take_balance_request() {
         let _ = runtime.send(RuntimeCommand::FetchBalance {
             address: address.clone(),
         });
-        if !app.tokens.is_empty() {
-            let tokens: Vec<TokenConfig> = app
-                .tokens
-                .iter()
-                .map(|t| TokenConfig {
-                    address: t.address.clone(),
-                    symbol: t.symbol.clone(),
-                    decimals: t.decimals,
-                })
-                .collect();
-            let _ = runtime.send(RuntimeCommand::FetchTokenBalances { address, tokens });
-        }
+        // The worker adds every token the address touched on top of the configured ones
+        let tokens = token_configs(&app.tokens);
+        let _ = runtime.send(RuntimeCommand::FetchTokenBalances { address, tokens });
     }
     if let Some(request) = app.take_storage_request() {
         let _ = runtime.send(RuntimeCommand::FetchStorage {
             address: request.address,
-            slot: request.slot,
+            slots: request.slots,
         });
     }
 
     // Process ABI registry updates
-    while let Ok(registry) = abi_evt_rx.try_recv() {
-        app.apply_abi_registry(registry);
+    while let Ok(event) = abi_evt_rx.try_recv() {
+        if let RuntimeEvent::AbiRegistryReady { registry } = event {
+            app.apply_abi_registry(registry);
+        }
+    }
+}
+
+/// Build alert rules from config, falling back to the built-in defaults.
+/// Invalid entries are skipped and reported.
+fn alert_rules(configs: &[config::AlertRuleConfig]) -> (Vec<AlertRule>, Vec<String>) {
+    if configs.is_empty() {
+        return (AlertRule::defaults(), Vec::new());
+    }
+    let mut rules = Vec::new();
+    let mut errors = Vec::new();
+    for entry in configs {
+        match AlertCondition::from_kind(&entry.kind, entry.threshold, entry.window, &entry.addresses)
+        {
+            Ok(condition)
+                if entry.finalized && !matches!(condition, AlertCondition::WatchedActivity(_)) =>
+            {
+                errors.push(format!("{}: finalized only applies to watched_activity", entry.name));
+            }
+            Ok(condition) => {
+                let mut rule = AlertRule::new(entry.name.clone(), condition);
+                if let Some(message) = &entry.message {
+                    rule = rule.message(message.clone());
+                }
+                rule.command = entry.command.clone();
+                rule.webhook = entry.webhook.clone();
+                rule.finalized = entry.finalized;
+                rules.push(rule);
+            }
+            Err(err) => errors.push(format!("{}: {err}", entry.name)),
+        }
+    }
+    (rules, errors)
+}
+
+fn token_configs(tokens: &[config::TokenSpec]) -> Vec<TokenConfig> {
+    tokens
+        .iter()
+        .map(|t| TokenConfig {
+            address: t.address.clone(),
+            symbol: t.symbol.clone(),
+            decimals: t.decimals,
+        })
+        .collect()
+}
+
+/// One-shot headless commands (trace, tx, block, decode)
+fn run_command(
+    command: headless::InspectCommand,
+    args: &Args,
+    config: &config::Config,
+    endpoints: Vec<EndpointSpec>,
+) -> Result<()> {
+    let offline = args.offline || config.offline;
+    let (signature_store, _) = open_signature_store(config);
+    let mut resolver = AbiResolver::new().offline(offline);
+    if let Some(store) = signature_store {
+        resolver = resolver.with_signature_store(store);
+    }
+    let (abi_layouts, unknown_layouts) = abi_layouts_from_config(config);
+    if !unknown_layouts.is_empty() {
+        eprintln!("poke: {}", unknown_layouts_message(&unknown_layouts));
     }
+    let registry = AbiScanner::scan_roots_with_layouts(&abi_scan_roots_from_config(config), &abi_layouts);
+    let endpoint = endpoints
+        .into_iter()
+        .next()
+        .map(|endpoint| endpoint.provider)
+        .ok_or_else(|| anyhow::anyhow!("no RPC endpoint configured"))?;
+    headless::run(command, endpoint, resolver, registry)
 }
 
 fn endpoints_from_args_and_config(
     args: &Args,
     config: &config::Config,
-) -> Result<(Vec<ProviderConfig>, Vec<RpcEndpointOption>)> {
+) -> Result<(Vec<EndpointSpec>, Vec<RpcEndpointOption>)> {
     use std::collections::BTreeSet;
 
     fn push_endpoint(
-        endpoints: &mut Vec<ProviderConfig>,
+        endpoints: &mut Vec<EndpointSpec>,
         options: &mut Vec<RpcEndpointOption>,
         seen: &mut BTreeSet<String>,
-        endpoint: ProviderConfig,
+        endpoint: EndpointSpec,
         name: Option<String>,
     ) {
-        let display = endpoint.display();
+        let display = endpoint.provider.display();
         let key = display.to_lowercase();
         if !seen.insert(key) {
             return;
@@ -387,7 +517,7 @@ fn endpoints_from_args_and_config(
                 &mut endpoints,
                 &mut options,
                 &mut seen,
-                ProviderConfig::Ipc(ipc),
+                ProviderConfig::Ipc(ipc).into(),
                 Some("cli".to_string()),
             );
         }
@@ -400,7 +530,7 @@ fn endpoints_from_args_and_config(
             &mut endpoints,
             &mut options,
             &mut seen,
-            ProviderConfig::WebSocket(ws.to_string()),
+            ProviderConfig::WebSocket(ws.to_string(), EndpointAuth::default()).into(),
             Some("cli".to_string()),
         );
     } else if let Some(rpc) = args.rpc.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
@@ -408,7 +538,7 @@ fn endpoints_from_args_and_config(
             &mut endpoints,
             &mut options,
             &mut seen,
-            ProviderConfig::Http(normalize_http_endpoint(rpc)),
+            ProviderConfig::Http(normalize_http_endpoint(rpc), EndpointAuth::default()).into(),
             Some("cli".to_string()),
         );
     }
@@ -416,12 +546,63 @@ fn endpoints_from_args_and_config(
     // Config file endpoints
     for (idx, entry) in config.endpoints.iter().enumerate() {
         let name = entry.name.clone().filter(|value| !value.trim().is_empty());
+        let metrics_url = entry
+            .metrics_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(normalize_http_endpoint);
+        let beacon_url = entry
+            .beacon_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(normalize_http_endpoint);
+        let budget = BudgetConfig {
+            max_rps: entry.max_rps,
+            cu_per_second: entry.cu_per_second,
+            daily_cu: entry.daily_cu,
+            costs: entry.cu_costs.clone(),
+        };
+        let label = || format!("endpoint {}", name.clone().unwrap_or_else(|| format!("#{}", idx + 1)));
+        // Connecting with a literal ${NAME} would send a placeholder as the secret
+        if !entry.unresolved_vars.is_empty() {
+            return Err(anyhow::anyhow!(
+                "environment variables not set: {}",
+                entry.unresolved_vars.join(", ")
+            ))
+            .with_context(label);
+        }
+        let beacon_auth = beacon_auth(entry);
+        let auth = endpoint_auth(entry).with_context(label)?;
         if let Some(rpc) = entry.rpc.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
             push_endpoint(
                 &mut endpoints,
                 &mut options,
                 &mut seen,
-                ProviderConfig::Http(normalize_http_endpoint(rpc)),
+                EndpointSpec {
+                    provider: ProviderConfig::Http(normalize_http_endpoint(rpc), auth),
+                    metrics_url,
+                    beacon_url,
+                    beacon_auth,
+                    budget,
+                },
+                name,
+            );
+            continue;
+        }
+        if let Some(ws) = entry.ws.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+            push_endpoint(
+                &mut endpoints,
+                &mut options,
+                &mut seen,
+                EndpointSpec {
+                    provider: ProviderConfig::WebSocket(ws.to_string(), auth),
+                    metrics_url,
+                    beacon_url,
+                    beacon_auth,
+                    budget,
+                },
                 name,
             );
             continue;
@@ -436,13 +617,19 @@ fn endpoints_from_args_and_config(
                     &mut endpoints,
                     &mut options,
                     &mut seen,
-                    ProviderConfig::Ipc(ipc_path),
+                    EndpointSpec {
+                        provider: ProviderConfig::Ipc(ipc_path),
+                        metrics_url,
+                        beacon_url,
+                        beacon_auth,
+                        budget,
+                    },
                     name.or_else(|| Some(format!("ipc-{idx}"))),
                 );
             }
             #[cfg(not(unix))]
             {
-                let _ = idx;
+                let _ = (idx, budget, beacon_url, beacon_auth);
             }
         }
     }
@@ -453,7 +640,8 @@ fn endpoints_from_args_and_config(
             &mut endpoints,
             &mut options,
             &mut seen,
-            ProviderConfig::Http(normalize_http_endpoint("localhost:8545")),
+            ProviderConfig::Http(normalize_http_endpoint("localhost:8545"), EndpointAuth::default())
+                .into(),
             Some("local".to_string()),
         );
     }
@@ -461,6 +649,55 @@ fn endpoints_from_args_and_config(
     Ok((endpoints, options))
 }
 
+/// Headers, basic auth and JWT secret configured for an endpoint
+fn endpoint_auth(entry: &config::EndpointConfig) -> Result<EndpointAuth> {
+    let jwt_secret = match (&entry.jwt_secret, &entry.jwt_secret_file) {
+        (Some(secret), _) => Some(parse_jwt_secret(secret)?),
+        (None, Some(path)) => {
+            let path = expand_path(path).context("jwt_secret_file is empty")?;
+            let secret = std::fs::read_to_string(&path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            Some(parse_jwt_secret(&secret).with_context(|| path.display().to_string())?)
+        }
+        (None, None) => None,
+    };
+    if jwt_secret.is_some() && entry.basic_auth.is_some() {
+        anyhow::bail!("set either basic_auth or a JWT secret, not both");
+    }
+    // The WebSocket handshake is signed once and replayed on reconnect, and
+    // carries no headers besides Authorization
+    let set = |value: &Option<String>| value.as_deref().is_some_and(|value| !value.trim().is_empty());
+    if !set(&entry.rpc) && set(&entry.ws) {
+        if jwt_secret.is_some() {
+            anyhow::bail!("JWT secrets are only supported on rpc endpoints, not ws");
+        }
+        if !entry.headers.is_empty() {
+            anyhow::bail!("headers are only sent to rpc endpoints; use basic_auth with ws");
+        }
+    }
+    Ok(EndpointAuth {
+        headers: entry.headers.clone().into_iter().collect(),
+        basic: entry
+            .basic_auth
+            .as_ref()
+            .map(|auth| (auth.username.clone(), auth.password.clone())),
+        jwt_secret,
+    })
+}
+
+/// Credentials for an endpoint's beacon API. Configured apart from the RPC ones,
+/// which may belong to another origin; a JWT is engine-API only and never applies.
+fn beacon_auth(entry: &config::EndpointConfig) -> EndpointAuth {
+    EndpointAuth {
+        headers: entry.beacon_headers.clone().into_iter().collect(),
+        basic: entry
+            .beacon_basic_auth
+            .as_ref()
+            .map(|auth| (auth.username.clone(), auth.password.clone())),
+        jwt_secret: None,
+    }
+}
+
 fn normalize_http_endpoint(endpoint: &str) -> String {
     let trimmed = endpoint.trim();
     if trimmed.starts_with("http://") || trimmed.starts_with("https://") {
@@ -483,6 +720,118 @@ fn abi_scan_roots_from_config(config: &config::Config) -> Vec<PathBuf> {
     roots
 }
 
+/// Configured ABI layouts, plus the names that match no known layout
+fn abi_layouts_from_config(config: &config::Config) -> (Vec<AbiLayout>, Vec<String>) {
+    let mut layouts = Vec::new();
+    let mut unknown = Vec::new();
+    for name in &config.abi_layouts {
+        match AbiLayout::parse(name) {
+            Some(layout) => layouts.push(layout),
+            None => unknown.push(name.clone()),
+        }
+    }
+    if layouts.is_empty() {
+        layouts = AbiLayout::ALL.to_vec();
+    }
+    (layouts, unknown)
+}
+
+fn unknown_layouts_message(unknown: &[String]) -> String {
+    let known: Vec<&str> = AbiLayout::ALL.iter().map(|layout| layout.name()).collect();
+    format!("Unknown ABI layouts ignored: {} (known: {})", unknown.join(", "), known.join(", "))
+}
+
+fn open_signature_store(
+    config: &config::Config,
+) -> (Option<SignatureStore>, Option<(String, StatusLevel)>) {
+    let Some(db_path) = config::signatures_db_path() else {
+        return (None, None);
+    };
+    if let Some(parent) = db_path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let mut store = match SignatureStore::open(&db_path) {
+        Ok(store) => store,
+        Err(err) => {
+            return (
+                None,
+                Some((format!("Signature DB disabled: {err}"), StatusLevel::Warn)),
+            )
+        }
+    };
+
+    // One bad file doesn't stop the rest from importing
+    let mut imported = 0;
+    let mut failures = Vec::new();
+    for raw in &config.signature_files {
+        let Some(path) = expand_path(raw) else {
+            continue;
+        };
+        match store.import_file(&path) {
+            Ok(stats) => imported += stats.inserted,
+            Err(err) => failures.push(format!("{}: {err:#}", path.display())),
+        }
+    }
+    if !failures.is_empty() {
+        return (
+            Some(store),
+            Some((
+                format!(
+                    "Signature import failed for {} of {} files ({imported} imported): {}",
+                    failures.len(),
+                    config.signature_files.len(),
+                    failures.join("; ")
+                ),
+                StatusLevel::Warn,
+            )),
+        );
+    }
+
+    let status = (imported > 0).then(|| {
+        (
+            format!("Imported {imported} signatures"),
+            StatusLevel::Info,
+        )
+    });
+    (Some(store), status)
+}
+
+fn import_signatures_cli(files: &[PathBuf]) -> Result<()> {
+    let db_path = config::signatures_db_path()
+        .ok_or_else(|| anyhow::anyhow!("No data directory for signature DB"))?;
+    if let Some(parent) = db_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut store = SignatureStore::open(&db_path)?;
+    let mut failed = 0;
+    for file in files {
+        let stats = match store.import_file(file) {
+            Ok(stats) => stats,
+            Err(err) => {
+                eprintln!("{}: {err:#}", file.display());
+                failed += 1;
+                continue;
+            }
+        };
+        if stats == ImportStats::default() {
+            println!("{}: unchanged since last import", file.display());
+            continue;
+        }
+        println!(
+            "{}: {} parsed, {} new, {} skipped",
+            file.display(),
+            stats.parsed,
+            stats.inserted,
+            stats.skipped
+        );
+    }
+    println!("{} signatures in {}", store.count()?, db_path.display());
+    if failed > 0 {
+        anyhow::bail!("{failed} of {} signature files failed to import", files.len());
+    }
+    Ok(())
+}
+
 fn expand_path(path: &str) -> Option<PathBuf> {
     let trimmed = path.trim();
     if trimmed.is_empty() {
@@ -508,37 +857,57 @@ fn expand_path(path: &str) -> Option<PathBuf> {
 #[derive(Debug, Clone)]
 pub struct AbiScanRequest {
     pub roots: Vec<PathBuf>,
+    pub layouts: Vec<AbiLayout>,
 }
 
+/// How often the ABI roots are checked for changed artifacts
+const ABI_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
 fn spawn_abi_scanner(
     initial_roots: Vec<PathBuf>,
-) -> (Sender<AbiScanRequest>, std::sync::mpsc::Receiver<AbiRegistry>) {
+    initial_layouts: Vec<AbiLayout>,
+) -> (Sender<AbiScanRequest>, std::sync::mpsc::Receiver<RuntimeEvent>) {
     let (req_tx, req_rx) = std::sync::mpsc::channel::<AbiScanRequest>();
-    let (evt_tx, evt_rx) = std::sync::mpsc::channel::<AbiRegistry>();
+    let (evt_tx, evt_rx) = std::sync::mpsc::channel::<RuntimeEvent>();
 
     thread::spawn(move || {
-        while let Ok(mut request) = req_rx.recv() {
-            while let Ok(next) = req_rx.try_recv() {
-                request = next;
+        let mut watcher = infrastructure::AbiWatcher::new(initial_roots, initial_layouts);
+        loop {
+            // Artifacts are rescanned incrementally; a reload request forces a full rescan
+            if let Some(registry) = watcher.poll() {
+                if evt_tx
+                    .send(RuntimeEvent::AbiRegistryReady { registry })
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            match req_rx.recv_timeout(ABI_WATCH_INTERVAL) {
+                Ok(mut request) => {
+                    while let Ok(next) = req_rx.try_recv() {
+                        request = next;
+                    }
+                    watcher.set_roots(request.roots, request.layouts);
+                }
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
             }
-            let registry = infrastructure::AbiScanner::scan_roots(&request.roots);
-            let _ = evt_tx.send(registry);
         }
     });
 
-    let _ = req_tx.send(AbiScanRequest {
-        roots: initial_roots,
-    });
-
     (req_tx, evt_rx)
 }
 
 fn reload_config(app: &mut App) {
     let config = config::load();
     let abi_scan_roots = abi_scan_roots_from_config(&config);
+    let (abi_layouts, unknown_layouts) = abi_layouts_from_config(&config);
+    app.abi_layouts = abi_layouts;
     app.tokens = config.tokens;
     app.token_balances.clear();
     app.abi_scan_roots = abi_scan_roots;
+    // Point the ABI watcher at the new roots
+    app.request_abi_reload();
     app.set_status(
         format!(
             "Reloaded config: {} tokens, {} ABI roots",
@@ -547,6 +916,9 @@ fn reload_config(app: &mut App) {
         ),
         StatusLevel::Info,
     );
+    if !unknown_layouts.is_empty() {
+        app.set_status(unknown_layouts_message(&unknown_layouts), StatusLevel::Warn);
+    }
 }
 
 fn handle_key(app: &mut App, key: KeyEvent, _runtime: &RuntimeBridge) {
@@ -1348,13 +1720,17 @@ fn handle_tab_nav_down(app: &mut App) {
         Tab::Ops => {
             // Cycle through Ops sections
             app.ops_section = match app.ops_section {
-                OpsSection::Health => OpsSection::Peers,
+                OpsSection::Health => OpsSection::Nodes,
+                OpsSection::Nodes => OpsSection::Beacon,
+                OpsSection::Beacon => OpsSection::L1Origin,
+                OpsSection::L1Origin => OpsSection::Peers,
                 OpsSection::Peers => OpsSection::Mempool,
                 OpsSection::Mempool => OpsSection::Logs,
                 OpsSection::Logs => OpsSection::Metrics,
                 OpsSection::Metrics => OpsSection::Alerts,
                 OpsSection::Alerts => OpsSection::RpcStats,
-                OpsSection::RpcStats => OpsSection::Health,
+                OpsSection::RpcStats => OpsSection::Quorum,
+                OpsSection::Quorum => OpsSection::Health,
             };
         }
         Tab::Home => {
@@ -1404,8 +1780,12 @@ fn handle_tab_nav_up(app: &mut App) {
         }
         Tab::Ops => {
             app.ops_section = match app.ops_section {
-                OpsSection::Health => OpsSection::RpcStats,
-                OpsSection::Peers => OpsSection::Health,
+                OpsSection::Health => OpsSection::Quorum,
+                OpsSection::Quorum => OpsSection::RpcStats,
+                OpsSection::Nodes => OpsSection::Health,
+                OpsSection::Beacon => OpsSection::Nodes,
+                OpsSection::L1Origin => OpsSection::Beacon,
+                OpsSection::Peers => OpsSection::L1Origin,
                 OpsSection::Mempool => OpsSection::Peers,
                 OpsSection::Logs => OpsSection::Mempool,
                 OpsSection::Metrics => OpsSection::Logs,
@@ -1490,6 +1870,87 @@ fn handle_tab_enter(app: &mut App) {
         Tab::Toolkit => {
             app.set_status("Press / to enter command for this tool", StatusLevel::Info);
         }
+        Tab::Ops if app.ops_section == crate::app::OpsSection::Metrics => {
+            app.cycle_metrics_window();
+        }
+        Tab::Ops if app.ops_section == crate::app::OpsSection::RpcStats => {
+            app.cycle_rpc_stats_sort();
+        }
         _ => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(toml: &str) -> config::EndpointConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_endpoint_auth() {
+        let secret = "11".repeat(32);
+        let auth = endpoint_auth(&entry(&format!(
+            "rpc = \"http://node:8551\"\njwt_secret = \"0x{secret}\"\n[headers]\nX-Api-Key = \"key\""
+        )))
+        .unwrap();
+        assert_eq!(auth.jwt_secret, Some(vec![0x11; 32]));
+        assert_eq!(auth.headers, vec![("X-Api-Key".to_string(), "key".to_string())]);
+
+        let auth = endpoint_auth(&entry(
+            "ws = \"ws://node:8546\"\nbasic_auth = { username = \"poke\", password = \"pw\" }",
+        ))
+        .unwrap();
+        assert_eq!(auth.basic, Some(("poke".to_string(), "pw".to_string())));
+
+        let err = endpoint_auth(&entry(&format!(
+            "rpc = \"http://node:8551\"\njwt_secret = \"{secret}\"\nbasic_auth = {{ username = \"poke\", password = \"pw\" }}"
+        )))
+        .unwrap_err();
+        assert!(err.to_string().contains("not both"));
+
+        let err = endpoint_auth(&entry(&format!("ws = \"ws://node:8546\"\njwt_secret = \"{secret}\""))).unwrap_err();
+        assert!(err.to_string().contains("not ws"));
+
+        let err =
+            endpoint_auth(&entry("ws = \"ws://node:8546\"\n[headers]\nX-Api-Key = \"key\"")).unwrap_err();
+        assert!(err.to_string().contains("headers"));
+
+        assert!(endpoint_auth(&entry("jwt_secret = \"00\"")).is_err());
+    }
+
+    #[test]
+    fn test_beacon_auth_kept_apart_from_rpc() {
+        let secret = "11".repeat(32);
+        let rpc_only = entry(&format!(
+            "rpc = \"http://node:8551\"\nbeacon_url = \"http://beacon:5052\"\njwt_secret = \"{secret}\"\n\
+             [headers]\nX-Api-Key = \"key\""
+        ));
+        assert_eq!(beacon_auth(&rpc_only), EndpointAuth::default());
+
+        let auth = beacon_auth(&entry(
+            "rpc = \"http://node:8545\"\nbeacon_basic_auth = { username = \"poke\", password = \"pw\" }\n\
+             [beacon_headers]\nX-Beacon-Key = \"beacon\"",
+        ));
+        assert_eq!(auth.headers, vec![("X-Beacon-Key".to_string(), "beacon".to_string())]);
+        assert_eq!(auth.basic, Some(("poke".to_string(), "pw".to_string())));
+        assert_eq!(auth.jwt_secret, None);
+    }
+
+    #[test]
+    fn test_unknown_abi_layouts_reported() {
+        let mut config = config::Config {
+            abi_layouts: vec!["foundry".to_string(), "truffel".to_string()],
+            ..Default::default()
+        };
+        let (layouts, unknown) = abi_layouts_from_config(&config);
+        assert_eq!(layouts, vec![AbiLayout::Foundry]);
+        assert_eq!(unknown, vec!["truffel".to_string()]);
+
+        config.abi_layouts = vec!["truffel".to_string()];
+        let (layouts, unknown) = abi_layouts_from_config(&config);
+        assert_eq!(layouts, AbiLayout::ALL.to_vec());
+        assert_eq!(unknown.len(), 1);
+    }
+}
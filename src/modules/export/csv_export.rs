@@ -2,7 +2,9 @@
 //!
 //! Writes blocks, transactions, and addresses to CSV files.
 
-use crate::app::{AddressInfo, BlockInfo, TxInfo, TxStatus};
+use crate::app::{AddressInfo, BlockInfo, CallStatus, TraceFrame, TxInfo, TxStatus};
+use std::fs::File;
+use std::io::Write;
 use std::path::Path;
 
 /// Write blocks to CSV file
@@ -29,7 +31,15 @@ pub fn write_blocks(path: &Path, blocks: &[BlockInfo]) -> Result<usize, Box<dyn
 
 /// Write transactions to CSV file
 pub fn write_transactions(path: &Path, txs: &[TxInfo]) -> Result<usize, Box<dyn std::error::Error>> {
-    let mut wtr = csv::Writer::from_path(path)?;
+    write_transactions_to(File::create(path)?, txs)
+}
+
+/// Write transactions as CSV to any writer (e.g. stdout)
+pub fn write_transactions_to<W: Write>(
+    writer: W,
+    txs: &[TxInfo],
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut wtr = csv::Writer::from_writer(writer);
 
     // Write header
     wtr.write_record([
@@ -90,3 +100,40 @@ pub fn write_addresses(path: &Path, addresses: &[AddressInfo]) -> Result<usize,
     wtr.flush()?;
     Ok(addresses.len())
 }
+
+/// Write trace frames as CSV to any writer (e.g. stdout)
+pub fn write_trace_to<W: Write>(
+    writer: W,
+    traces: &[TraceFrame],
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut wtr = csv::Writer::from_writer(writer);
+
+    // Write header
+    wtr.write_record([
+        "depth", "call", "from", "to", "value", "gas_used", "status", "method", "selector", "note",
+    ])?;
+
+    // Write data rows
+    for frame in traces {
+        let status = match frame.status {
+            CallStatus::Ok => "ok",
+            CallStatus::Revert => "revert",
+        };
+
+        wtr.write_record([
+            frame.depth.to_string(),
+            frame.call.clone(),
+            frame.from.clone(),
+            frame.to.clone(),
+            frame.value.to_string(),
+            frame.gas_used.to_string(),
+            status.to_string(),
+            frame.method.clone().unwrap_or_default(),
+            frame.selector.clone().unwrap_or_default(),
+            frame.note.clone(),
+        ])?;
+    }
+
+    wtr.flush()?;
+    Ok(traces.len())
+}
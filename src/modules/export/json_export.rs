@@ -1,8 +1,9 @@
 //! JSON Export
 //!
-//! Writes trace data to JSON files.
+//! Writes trace, transaction and block data as JSON.
 
-use crate::app::{CallStatus, TraceFrame};
+use crate::app::{BlockInfo, CallStatus, DecodedArg, TraceFrame, TxInfo, TxStatus};
+use crate::domain::abi::NestedCall;
 use serde::Serialize;
 use std::fs::File;
 use std::io::Write;
@@ -28,8 +29,12 @@ struct ExportableTrace {
     #[serde(skip_serializing_if = "Option::is_none")]
     input: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     decoded_args: Option<Vec<ExportableArg>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    decoded_output: Option<Vec<ExportableArg>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     decode_error: Option<String>,
 }
 
@@ -38,6 +43,64 @@ struct ExportableArg {
     name: String,
     kind: String,
     value: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    calls: Vec<NestedCall>,
+}
+
+#[derive(Serialize)]
+struct ExportableTx {
+    hash: String,
+    block_number: u64,
+    from: String,
+    to: String,
+    value: f64,
+    gas_used: u64,
+    status: String,
+    selector: String,
+    method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+    input: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    decoded_args: Option<Vec<ExportableArg>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    decode_error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ExportableBlock {
+    number: u64,
+    tx_count: u32,
+    gas_used: u64,
+    base_fee: u64,
+    miner: String,
+    transactions: Vec<ExportableTx>,
+}
+
+impl From<&TxInfo> for ExportableTx {
+    fn from(tx: &TxInfo) -> Self {
+        let status = match tx.status {
+            TxStatus::Success => "success",
+            TxStatus::Revert => "revert",
+            TxStatus::Unknown => "unknown",
+        };
+
+        Self {
+            hash: tx.hash.clone(),
+            block_number: tx.block_number,
+            from: tx.from.clone(),
+            to: tx.to.clone(),
+            value: tx.value,
+            gas_used: tx.gas_used,
+            status: status.to_string(),
+            selector: tx.selector.clone(),
+            method: tx.method.clone(),
+            signature: tx.signature.clone(),
+            input: tx.input.clone(),
+            decoded_args: tx.decoded_args.as_deref().map(exportable_args),
+            decode_error: tx.decode_error.clone(),
+        }
+    }
 }
 
 impl From<&TraceFrame> for ExportableTrace {
@@ -60,28 +123,70 @@ impl From<&TraceFrame> for ExportableTrace {
             selector: frame.selector.clone(),
             signature: frame.signature.clone(),
             input: frame.input.clone(),
-            decoded_args: frame.decoded_args.as_ref().map(|args| {
-                args.iter()
-                    .map(|arg| ExportableArg {
-                        name: arg.name.clone(),
-                        kind: arg.kind.clone(),
-                        value: arg.value.clone(),
-                    })
-                    .collect()
-            }),
+            output: frame.output.clone(),
+            decoded_args: frame.decoded_args.as_deref().map(exportable_args),
+            decoded_output: frame.decoded_output.as_deref().map(exportable_args),
             decode_error: frame.decode_error.clone(),
         }
     }
 }
 
+fn exportable_args(args: &[DecodedArg]) -> Vec<ExportableArg> {
+    args.iter()
+        .map(|arg| ExportableArg {
+            name: arg.name.clone(),
+            kind: arg.kind.clone(),
+            value: arg.value.clone(),
+            calls: arg.nested.clone(),
+        })
+        .collect()
+}
+
 /// Write trace frames to JSON file
 pub fn write_trace(path: &Path, traces: &[TraceFrame]) -> Result<usize, Box<dyn std::error::Error>> {
+    write_trace_to(File::create(path)?, traces)
+}
+
+/// Write trace frames as JSON to any writer (e.g. stdout)
+pub fn write_trace_to<W: Write>(
+    mut writer: W,
+    traces: &[TraceFrame],
+) -> Result<usize, Box<dyn std::error::Error>> {
     let exportable: Vec<ExportableTrace> = traces.iter().map(ExportableTrace::from).collect();
 
     let json = serde_json::to_string_pretty(&exportable)?;
-
-    let mut file = File::create(path)?;
-    file.write_all(json.as_bytes())?;
+    writer.write_all(json.as_bytes())?;
 
     Ok(traces.len())
 }
+
+/// Write one transaction as a JSON object
+pub fn write_transaction_to<W: Write>(
+    mut writer: W,
+    tx: &TxInfo,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(&ExportableTx::from(tx))?;
+    writer.write_all(json.as_bytes())?;
+    Ok(())
+}
+
+/// Write a block and its transactions as a JSON object
+pub fn write_block_to<W: Write>(
+    mut writer: W,
+    block: &BlockInfo,
+    txs: &[TxInfo],
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let exportable = ExportableBlock {
+        number: block.number,
+        tx_count: block.tx_count,
+        gas_used: block.gas_used,
+        base_fee: block.base_fee,
+        miner: block.miner.clone(),
+        transactions: txs.iter().map(ExportableTx::from).collect(),
+    };
+
+    let json = serde_json::to_string_pretty(&exportable)?;
+    writer.write_all(json.as_bytes())?;
+
+    Ok(txs.len())
+}
@@ -7,8 +7,8 @@
 //! - Traces → JSON
 //! - Files saved to ~/.poke/exports/
 
-mod csv_export;
-mod json_export;
+pub mod csv_export;
+pub mod json_export;
 
 use crate::app::{App, Section, View};
 use crate::core::{Action, NotifyLevel};
@@ -3,99 +3,60 @@
 
 use super::{OpsResult, OpsStatus};
 use crate::core::Action;
+use crate::domain::alerts::{AlertEngine, AlertEvent, AlertRule};
 
-/// Alert rule definition
-#[derive(Clone, Debug)]
-pub struct AlertRule {
-    pub name: String,
-    pub condition: AlertCondition,
-    pub message: String,
-}
-
-/// Alert condition types
-#[derive(Clone, Debug)]
-pub enum AlertCondition {
-    PeerCountBelow(u32),
-    RpcLatencyAbove(u64),
-    SyncStalled,
-}
-
-/// Alert checker
+/// One-shot alert checker over a status sample
 pub struct AlertChecker {
     rules: Vec<AlertRule>,
 }
 
 impl AlertChecker {
     pub fn new() -> Self {
-        Self {
-            rules: Self::default_rules(),
-        }
+        Self::with_rules(Self::default_rules())
     }
 
-    fn default_rules() -> Vec<AlertRule> {
-        vec![
-            AlertRule {
-                name: "peer_count_low".to_string(),
-                condition: AlertCondition::PeerCountBelow(3),
-                message: "Peer count critically low".to_string(),
-            },
-            AlertRule {
-                name: "rpc_latency_high".to_string(),
-                condition: AlertCondition::RpcLatencyAbove(500),
-                message: "RPC latency spike detected".to_string(),
-            },
-        ]
+    pub fn with_rules(rules: Vec<AlertRule>) -> Self {
+        Self { rules }
+    }
+
+    pub fn default_rules() -> Vec<AlertRule> {
+        AlertRule::defaults()
     }
 
     pub fn check_alerts(
         &self,
         peer_count: u32,
         rpc_latency_ms: Option<u64>,
-        syncing: bool,
+        head_lag: Option<u64>,
     ) -> Vec<String> {
-        let mut alerts = Vec::new();
-
-        for rule in &self.rules {
-            match rule.condition {
-                AlertCondition::PeerCountBelow(threshold) => {
-                    if peer_count < threshold {
-                        alerts.push(format!("⚠ {}: {}", rule.name, rule.message));
-                    }
-                }
-                AlertCondition::RpcLatencyAbove(threshold) => {
-                    if let Some(latency) = rpc_latency_ms {
-                        if latency > threshold {
-                            alerts.push(format!("⚠ {}: {}", rule.name, rule.message));
-                        }
-                    }
-                }
-                AlertCondition::SyncStalled => {
-                    // Would need additional state to detect stall
-                    // For now, just check if syncing
-                    if syncing {
-                        // Placeholder logic
-                    }
-                }
-            }
-        }
-
-        alerts
+        let mut engine = AlertEngine::new(self.rules.clone());
+        engine.observe_status(Some(peer_count), rpc_latency_ms, head_lag);
+        engine
+            .evaluate(0)
+            .into_iter()
+            .map(|event| format!("⚠ {}: {}", event.rule, event.message))
+            .collect()
     }
 }
 
 /// Display active alerts
-pub fn alerts(peer_count: u32, rpc_latency_ms: Option<u64>, syncing: bool) -> Action {
-    let checker = AlertChecker::new();
-    let active_alerts = checker.check_alerts(peer_count, rpc_latency_ms, syncing);
-
+pub fn alerts(active: &[&AlertEvent], rule_count: usize) -> Action {
     let mut result = OpsResult::new("Alerts");
 
-    if active_alerts.is_empty() {
-        result = result.add("status", "All clear", OpsStatus::Ok);
+    if active.is_empty() {
+        result = result.add(
+            "status",
+            format!("All clear ({} rules)", rule_count),
+            OpsStatus::Ok,
+        );
     } else {
-        result = result.add("count", active_alerts.len().to_string(), OpsStatus::Warning);
-        for (i, alert) in active_alerts.iter().take(3).enumerate() {
-            result = result.add(format!("alert{}", i + 1), alert, OpsStatus::Warning);
+        result = result.add("count", active.len().to_string(), OpsStatus::Warning);
+        for (i, alert) in active.iter().take(3).enumerate() {
+            result = result.add(
+                format!("alert{}", i + 1),
+                format!("⚠ {}: {}", alert.rule, alert.message),
+                OpsStatus::Warning,
+            );
         }
     }
 
@@ -109,14 +70,14 @@ mod tests {
     #[test]
     fn test_alert_checker() {
         let checker = AlertChecker::new();
-        let alerts = checker.check_alerts(2, Some(600), false);
+        let alerts = checker.check_alerts(2, Some(600), None);
         assert_eq!(alerts.len(), 2); // Both peer count and latency alerts
     }
 
     #[test]
     fn test_no_alerts() {
         let checker = AlertChecker::new();
-        let alerts = checker.check_alerts(10, Some(50), false);
+        let alerts = checker.check_alerts(10, Some(50), None);
         assert_eq!(alerts.len(), 0);
     }
 }
@@ -1,11 +1,61 @@
-//! Metrics tracking (placeholder)
+//! Metrics tracking
 
-use crate::core::{Action, NotifyLevel};
+use super::{OpsResult, OpsStatus};
+use crate::core::Action;
+use crate::domain::metrics::{MetricsWindow, SeriesView};
 
-/// Placeholder for metrics command
-pub fn metrics_unavailable() -> Action {
-    Action::Notify(
-        "Metrics: collection not yet implemented".into(),
-        NotifyLevel::Warn,
-    )
+/// Latest value and window average of every collected series
+pub fn metrics_summary(series: &[SeriesView], window: MetricsWindow) -> Action {
+    let mut result = OpsResult::new(format!("Metrics ({})", window.label()));
+
+    if series.is_empty() {
+        result = result.add("status", "No samples yet", OpsStatus::Unknown);
+    }
+    for view in series.iter().take(6) {
+        result = result.add(
+            view.label.clone(),
+            format!(
+                "{} (avg {})",
+                format_value(view.last, view.unit),
+                format_value(view.avg, view.unit)
+            ),
+            OpsStatus::Ok,
+        );
+    }
+
+    result.into_action()
+}
+
+/// Render a sample with its unit, e.g. "12.1s" or "31.5 gwei"
+pub fn format_value(value: Option<f64>, unit: &str) -> String {
+    let Some(value) = value else {
+        return "--".to_string();
+    };
+    let number = if value.fract() == 0.0 || value.abs() >= 100.0 {
+        format!("{:.0}", value)
+    } else if value.abs() < 1.0 {
+        format!("{:.2}", value)
+    } else {
+        format!("{:.1}", value)
+    };
+    match unit {
+        "" => number,
+        "s" | "%" | "ms" => format!("{}{}", number, unit),
+        _ => format!("{} {}", number, unit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value(Some(12.0), "s"), "12s");
+        assert_eq!(format_value(Some(31.46), "gwei"), "31.5 gwei");
+        assert_eq!(format_value(Some(250.4), "ms"), "250ms");
+        assert_eq!(format_value(Some(25.0), ""), "25");
+        assert_eq!(format_value(Some(0.042), "gwei"), "0.04 gwei");
+        assert_eq!(format_value(None, "%"), "--");
+    }
 }
@@ -2,9 +2,10 @@
 
 use super::{OpsResult, OpsStatus};
 use crate::core::Action;
+use crate::domain::rpc_stats::RpcStatRow;
 
-/// Simple RPC stats from current state
-pub fn rpc_stats_simple(latency_ms: Option<u64>, endpoint: &str) -> Action {
+/// RPC stats from current state: head latency plus p95 per recorded method
+pub fn rpc_stats_simple(latency_ms: Option<u64>, endpoint: &str, rows: &[RpcStatRow]) -> Action {
     let latency_status = match latency_ms {
         Some(ms) if ms < 100 => OpsStatus::Ok,
         Some(ms) if ms < 500 => OpsStatus::Warning,
@@ -22,8 +23,24 @@ pub fn rpc_stats_simple(latency_ms: Option<u64>, endpoint: &str) -> Action {
         endpoint.to_string()
     };
 
-    OpsResult::new("RPC Stats")
+    let mut result = OpsResult::new("RPC Stats")
         .add("endpoint", short_endpoint, OpsStatus::Ok)
-        .add("latency", latency_text, latency_status)
-        .into_action()
+        .add("latency", latency_text, latency_status);
+    for row in rows.iter().filter(|row| row.endpoint == endpoint) {
+        let status = if row.errors > 0 {
+            OpsStatus::Warning
+        } else {
+            OpsStatus::Ok
+        };
+        let p95 = row
+            .p95
+            .map(|ms| format!("p95 {:.1}ms", ms))
+            .unwrap_or_else(|| "p95 N/A".to_string());
+        result = result.add(
+            &row.method,
+            format!("{} calls, {} errors, {}", row.calls, row.errors, p95),
+            status,
+        );
+    }
+    result.into_action()
 }
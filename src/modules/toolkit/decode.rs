@@ -3,6 +3,9 @@
 use alloy_dyn_abi::{DynSolType, DynSolValue};
 use super::ToolResult;
 use crate::core::{Action, NotifyLevel};
+use crate::infrastructure::abi::nested::{
+    decode_nested_call, function_from_signature, render_call_tree, SelectorLookup,
+};
 use std::collections::BTreeMap;
 
 /// Decode function calldata back to human-readable format
@@ -40,6 +43,41 @@ pub fn decode(input: Option<String>, signature_cache: &BTreeMap<String, (String,
     }
 }
 
+/// Render calldata as a call tree, unwrapping multicall/Safe/router payloads.
+/// Returns no lines when the calldata can't be decoded.
+pub fn decode_tree(input: Option<&str>, lookup: &SelectorLookup) -> Vec<String> {
+    let Some(input) = input else {
+        return Vec::new();
+    };
+    let mut parts = input.split_whitespace();
+    let Some(calldata) = parts.next() else {
+        return Vec::new();
+    };
+    let manual_signature = parts.collect::<Vec<_>>().join(" ");
+    let manual = (!manual_signature.is_empty())
+        .then(|| function_from_signature(&manual_signature))
+        .flatten();
+
+    let calldata = calldata.strip_prefix("0x").unwrap_or(calldata);
+    let Ok(data) = hex::decode(calldata) else {
+        return Vec::new();
+    };
+
+    // A manual signature overrides the lookup for the outer call only
+    let outer_selector = data.get(..4).map(|sel| format!("0x{}", hex::encode(sel)));
+    let lookup_with_manual = |selector: &str| match manual.as_ref() {
+        Some(function) if outer_selector.as_deref() == Some(selector) => Some(function.clone()),
+        _ => lookup(selector),
+    };
+
+    let Some(call) = decode_nested_call(&data, &lookup_with_manual) else {
+        return Vec::new();
+    };
+    let mut lines = Vec::new();
+    render_call_tree(std::slice::from_ref(&call), 0, &mut lines);
+    lines
+}
+
 /// Decode calldata using signature from cache or manual override
 fn decode_calldata(
     calldata: &str,
@@ -144,9 +182,9 @@ fn decode_params(types: &[DynSolType], data: &[u8]) -> Result<Vec<DynSolValue>,
     // Wrap types in a tuple for decoding
     let tuple_type = DynSolType::Tuple(types.to_vec());
 
-    // Decode the data
+    // Decode the data (calldata is a parameter list, not a single tuple)
     let decoded = tuple_type
-        .abi_decode(data)
+        .abi_decode_params(data)
         .map_err(|e| format!("Failed to decode parameters: {}", e))?;
 
     // Extract values from the tuple
@@ -301,6 +339,29 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_decode_tree_multicall() {
+        let cache = create_test_cache();
+        let lookup = |selector: &str| {
+            cache
+                .get(selector)
+                .and_then(|(_, sig)| function_from_signature(sig))
+        };
+        let transfer = "a9059cbb000000000000000000000000742d35cc6634c0532925a3b844bc9e7595f0beb000000000000000000000000000000000000000000000000000000000000f4240";
+        let inner = hex::decode(transfer).unwrap();
+        let mut calldata = hex::decode("ac9650d8").unwrap();
+        calldata.extend(
+            DynSolValue::Tuple(vec![DynSolValue::Array(vec![DynSolValue::Bytes(inner)])])
+                .abi_encode_params(),
+        );
+        let input = format!("0x{} multicall(bytes[])", hex::encode(calldata));
+
+        let lines = decode_tree(Some(&input), &lookup);
+        assert!(lines[0].contains("multicall"));
+        assert!(lines.iter().any(|line| line.contains("transfer")));
+        assert!(lines.iter().any(|line| line.contains("1000000")));
+    }
+
     #[test]
     fn test_format_value() {
         use alloy::primitives::{Address, U256};
@@ -1,3 +1,7 @@
 mod labels;
+mod signatures;
+mod tokens;
 
 pub use labels::LabelStore;
+pub use signatures::{ImportStats, SignatureStore};
+pub use tokens::TokenStore;
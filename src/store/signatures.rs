@@ -0,0 +1,329 @@
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use alloy::primitives::keccak256;
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+
+/// Local function signature database, fed from bulk dumps so selector
+/// resolution keeps working without network access.
+#[derive(Debug)]
+pub struct SignatureStore {
+    conn: Connection,
+}
+
+/// Outcome of importing a single dump file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportStats {
+    pub parsed: usize,
+    pub inserted: usize,
+    pub skipped: usize,
+}
+
+impl SignatureStore {
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path).with_context(|| format!("open db {}", path.display()))?;
+        let store = Self { conn };
+        store.init()?;
+        Ok(store)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self> {
+        let store = Self {
+            conn: Connection::open_in_memory()?,
+        };
+        store.init()?;
+        Ok(store)
+    }
+
+    // === Signatures ===
+
+    /// All known signatures for a selector (`0x` + 8 hex chars), oldest first
+    pub fn lookup(&self, selector: &str) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT signature FROM signatures WHERE selector = ?1 ORDER BY rowid")?;
+        let mut rows = stmt.query(params![selector.to_lowercase()])?;
+        let mut out = Vec::new();
+        while let Some(row) = rows.next()? {
+            out.push(row.get(0)?);
+        }
+        Ok(out)
+    }
+
+    pub fn count(&self) -> Result<usize> {
+        let count: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM signatures", [], |row| row.get(0))?;
+        Ok(count as usize)
+    }
+
+    /// Insert signatures, deriving each selector from the text signature.
+    /// Returns how many rows were new.
+    pub fn insert_signatures<I, S>(&mut self, signatures: I) -> Result<usize>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let tx = self.conn.transaction()?;
+        let mut inserted = 0;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR IGNORE INTO signatures(selector, signature) VALUES (?1, ?2)",
+            )?;
+            for signature in signatures {
+                let signature = signature.as_ref();
+                inserted += stmt.execute(params![selector_of(signature), signature])?;
+            }
+        }
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    // === Imports ===
+
+    /// Import a dump file. Plain text lists, 4byte-style JSON and CSV are
+    /// accepted. Files already imported with the same size and mtime are skipped.
+    pub fn import_file(&mut self, path: &Path) -> Result<ImportStats> {
+        let meta = std::fs::metadata(path).with_context(|| format!("stat {}", path.display()))?;
+        let size = meta.len() as i64;
+        let mtime = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+        let key = path.to_string_lossy().to_string();
+
+        let previous: Option<(i64, i64)> = self
+            .conn
+            .query_row(
+                "SELECT size, mtime FROM imports WHERE path = ?1",
+                params![key],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        if previous == Some((size, mtime)) {
+            return Ok(ImportStats::default());
+        }
+
+        let content =
+            std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+        let format = SignatureFormat::detect(path, &content);
+        let (signatures, skipped) = parse_signature_dump(&content, format)?;
+        let parsed = signatures.len();
+        let inserted = self.insert_signatures(&signatures)?;
+
+        self.conn.execute(
+            "INSERT INTO imports(path, size, mtime, count) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(path) DO UPDATE SET size=excluded.size, mtime=excluded.mtime, count=excluded.count",
+            params![key, size, mtime, parsed as i64],
+        )?;
+
+        Ok(ImportStats {
+            parsed,
+            inserted,
+            skipped,
+        })
+    }
+
+    fn init(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS signatures (
+                selector  TEXT NOT NULL,
+                signature TEXT NOT NULL,
+                PRIMARY KEY (selector, signature)
+            );
+            CREATE TABLE IF NOT EXISTS imports (
+                path  TEXT PRIMARY KEY,
+                size  INTEGER NOT NULL,
+                mtime INTEGER NOT NULL,
+                count INTEGER NOT NULL
+            );",
+        )?;
+        Ok(())
+    }
+}
+
+/// Supported dump layouts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureFormat {
+    /// One `name(types)` per line, optionally prefixed with its selector
+    Text,
+    /// 4byte API pages (`results[].text_signature`), arrays of signatures,
+    /// or `{ "0xselector": ["sig", ...] }` maps
+    Json,
+    /// 4byte database export with a `text_signature` column
+    Csv,
+}
+
+impl SignatureFormat {
+    pub fn detect(path: &Path, content: &str) -> Self {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .as_deref()
+        {
+            Some("json") => return SignatureFormat::Json,
+            Some("csv") => return SignatureFormat::Csv,
+            _ => {}
+        }
+        let head = content.trim_start();
+        if head.starts_with('{') || head.starts_with('[') {
+            SignatureFormat::Json
+        } else {
+            SignatureFormat::Text
+        }
+    }
+}
+
+/// Parse a dump into canonical text signatures.
+/// Returns (signatures, skipped_entries).
+pub fn parse_signature_dump(content: &str, format: SignatureFormat) -> Result<(Vec<String>, usize)> {
+    let raw: Vec<String> = match format {
+        SignatureFormat::Text => content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                // Accept "0xa9059cbb transfer(...)" and "0xa9059cbb,transfer(...)"
+                match line.split_once(|c: char| c == ',' || c.is_whitespace()) {
+                    Some((first, rest)) if is_selector(first) => rest.trim().to_string(),
+                    _ => line.to_string(),
+                }
+            })
+            .collect(),
+        SignatureFormat::Json => {
+            let value: Value = serde_json::from_str(content).context("parse signature JSON")?;
+            let mut out = Vec::new();
+            collect_json_signatures(&value, &mut out);
+            out
+        }
+        SignatureFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .flexible(true)
+                .from_reader(content.as_bytes());
+            let headers = reader.headers().context("read CSV header")?.clone();
+            let column = headers
+                .iter()
+                .position(|h| matches!(h.trim(), "text_signature" | "signature" | "name"))
+                .context("CSV needs a text_signature column")?;
+            let mut out = Vec::new();
+            for record in reader.records() {
+                let record = record.context("read CSV record")?;
+                if let Some(field) = record.get(column) {
+                    out.push(field.to_string());
+                }
+            }
+            out
+        }
+    };
+
+    let mut signatures = Vec::with_capacity(raw.len());
+    let mut skipped = 0;
+    for entry in raw {
+        match canonical_signature(&entry) {
+            Some(sig) => signatures.push(sig),
+            None => skipped += 1,
+        }
+    }
+    Ok((signatures, skipped))
+}
+
+fn collect_json_signatures(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::String(s) => out.push(s.clone()),
+        Value::Array(items) => items.iter().for_each(|item| collect_json_signatures(item, out)),
+        Value::Object(map) => {
+            if let Some(sig) = map
+                .get("text_signature")
+                .or_else(|| map.get("signature"))
+                .and_then(Value::as_str)
+            {
+                out.push(sig.to_string());
+            } else if let Some(results) = map.get("results") {
+                collect_json_signatures(results, out);
+            } else {
+                // selector -> signature(s) map
+                map.iter()
+                    .filter(|(key, _)| is_selector(key))
+                    .for_each(|(_, v)| collect_json_signatures(v, out));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Strip whitespace and validate `name(types)` shape
+fn canonical_signature(raw: &str) -> Option<String> {
+    let sig: String = raw.chars().filter(|c| !c.is_whitespace()).collect();
+    let open = sig.find('(')?;
+    if open == 0 || !sig.ends_with(')') {
+        return None;
+    }
+    let name = &sig[..open];
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$') {
+        return None;
+    }
+    Some(sig)
+}
+
+fn is_selector(value: &str) -> bool {
+    let value = value.trim();
+    let payload = value.strip_prefix("0x").unwrap_or(value);
+    payload.len() == 8 && payload.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn selector_of(signature: &str) -> String {
+    format!("0x{}", hex::encode(&keccak256(signature.as_bytes())[..4]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_text_list() {
+        let content = "# comment\ntransfer(address,uint256)\n0x095ea7b3 approve(address, uint256)\nnot a signature\n";
+        let (sigs, skipped) = parse_signature_dump(content, SignatureFormat::Text).unwrap();
+        assert_eq!(sigs, vec!["transfer(address,uint256)", "approve(address,uint256)"]);
+        assert_eq!(skipped, 1);
+    }
+
+    #[test]
+    fn test_parse_4byte_json() {
+        let page = r#"{"count":1,"results":[{"id":1,"text_signature":"transfer(address,uint256)","hex_signature":"0xa9059cbb"}]}"#;
+        let (sigs, _) = parse_signature_dump(page, SignatureFormat::Json).unwrap();
+        assert_eq!(sigs, vec!["transfer(address,uint256)"]);
+
+        let map = r#"{"0xd0e30db0":["deposit()"],"0x2e1a7d4d":"withdraw(uint256)"}"#;
+        let (mut sigs, _) = parse_signature_dump(map, SignatureFormat::Json).unwrap();
+        sigs.sort();
+        assert_eq!(sigs, vec!["deposit()", "withdraw(uint256)"]);
+    }
+
+    #[test]
+    fn test_parse_4byte_csv() {
+        let content = "id,created_at,text_signature,hex_signature,bytes_signature\n\
+                       1,2016-07-09,\"transfer(address,uint256)\",0xa9059cbb,x\n";
+        let (sigs, _) = parse_signature_dump(content, SignatureFormat::Csv).unwrap();
+        assert_eq!(sigs, vec!["transfer(address,uint256)"]);
+    }
+
+    #[test]
+    fn test_insert_and_lookup() {
+        let mut store = SignatureStore::open_in_memory().unwrap();
+        let inserted = store
+            .insert_signatures(["transfer(address,uint256)", "transfer(address,uint256)"])
+            .unwrap();
+        assert_eq!(inserted, 1);
+        assert_eq!(
+            store.lookup("0xA9059CBB").unwrap(),
+            vec!["transfer(address,uint256)".to_string()]
+        );
+        assert!(store.lookup("0xdeadbeef").unwrap().is_empty());
+    }
+}
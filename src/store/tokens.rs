@@ -0,0 +1,180 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use alloy::primitives::U256;
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::domain::transfers::TokenMeta;
+
+/// Per-chain token metadata cache and the holder -> token relations seen in
+/// Transfer logs, so balance snapshots can cover every touched token.
+#[derive(Debug)]
+pub struct TokenStore {
+    conn: Connection,
+}
+
+impl TokenStore {
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path).with_context(|| format!("open db {}", path.display()))?;
+        let store = Self { conn };
+        store.init()?;
+        Ok(store)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self> {
+        let store = Self {
+            conn: Connection::open_in_memory()?,
+        };
+        store.init()?;
+        Ok(store)
+    }
+
+    // === Metadata ===
+
+    pub fn get(&self, chain_id: u64, token: &str) -> Result<Option<TokenMeta>> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT name, symbol, decimals, total_supply FROM tokens
+                 WHERE chain_id = ?1 AND address = ?2",
+                params![chain_id as i64, token.to_lowercase()],
+                |row| {
+                    Ok((
+                        row.get::<_, Option<String>>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                        row.get::<_, Option<i64>>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                    ))
+                },
+            )
+            .optional()?;
+        Ok(row.map(|(name, symbol, decimals, total_supply)| TokenMeta {
+            name,
+            symbol,
+            decimals: decimals.and_then(|d| u8::try_from(d).ok()),
+            total_supply: total_supply.and_then(|s| s.parse::<U256>().ok()),
+        }))
+    }
+
+    pub fn upsert(&self, chain_id: u64, token: &str, meta: &TokenMeta) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO tokens(chain_id, address, name, symbol, decimals, total_supply, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(chain_id, address) DO UPDATE SET
+                name=excluded.name, symbol=excluded.symbol, decimals=excluded.decimals,
+                total_supply=excluded.total_supply, updated_at=excluded.updated_at",
+            params![
+                chain_id as i64,
+                token.to_lowercase(),
+                meta.name,
+                meta.symbol,
+                meta.decimals.map(i64::from),
+                meta.total_supply.map(|s| s.to_string()),
+                now_secs(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    // === Holders ===
+
+    /// Remember that each `(holder, token)` pair sent or received the token,
+    /// in a single transaction
+    pub fn record_holders(&mut self, chain_id: u64, pairs: &[(String, String)]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT OR IGNORE INTO token_holders(chain_id, holder, token) VALUES (?1, ?2, ?3)",
+            )?;
+            for (holder, token) in pairs {
+                stmt.execute(params![
+                    chain_id as i64,
+                    holder.to_lowercase(),
+                    token.to_lowercase()
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Tokens a holder has touched on a chain
+    pub fn tokens_for_holder(&self, chain_id: u64, holder: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT token FROM token_holders WHERE chain_id = ?1 AND holder = ?2 ORDER BY token",
+        )?;
+        let mut rows = stmt.query(params![chain_id as i64, holder.to_lowercase()])?;
+        let mut out = Vec::new();
+        while let Some(row) = rows.next()? {
+            out.push(row.get(0)?);
+        }
+        Ok(out)
+    }
+
+    fn init(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS tokens (
+                chain_id     INTEGER NOT NULL,
+                address      TEXT NOT NULL,
+                name         TEXT,
+                symbol       TEXT,
+                decimals     INTEGER,
+                total_supply TEXT,
+                updated_at   INTEGER NOT NULL,
+                PRIMARY KEY (chain_id, address)
+            );
+            CREATE TABLE IF NOT EXISTS token_holders (
+                chain_id INTEGER NOT NULL,
+                holder   TEXT NOT NULL,
+                token    TEXT NOT NULL,
+                PRIMARY KEY (chain_id, holder, token)
+            );",
+        )?;
+        Ok(())
+    }
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    const HOLDER: &str = "0x1111111111111111111111111111111111111111";
+
+    #[test]
+    fn test_metadata_is_per_chain() {
+        let store = TokenStore::open_in_memory().unwrap();
+        let meta = TokenMeta {
+            name: Some("USD Coin".to_string()),
+            symbol: Some("USDC".to_string()),
+            decimals: Some(6),
+            total_supply: Some(U256::from(1_000_000u64)),
+        };
+        store.upsert(1, TOKEN, &meta).unwrap();
+
+        assert_eq!(store.get(1, &TOKEN.to_lowercase()).unwrap(), Some(meta));
+        assert_eq!(store.get(10, TOKEN).unwrap(), None);
+    }
+
+    #[test]
+    fn test_holder_tokens() {
+        let mut store = TokenStore::open_in_memory().unwrap();
+        let pair = (HOLDER.to_string(), TOKEN.to_string());
+        store.record_holders(1, &[pair.clone(), pair]).unwrap();
+
+        assert_eq!(
+            store.tokens_for_holder(1, HOLDER).unwrap(),
+            vec![TOKEN.to_lowercase()]
+        );
+        assert!(store.tokens_for_holder(31337, HOLDER).unwrap().is_empty());
+    }
+}
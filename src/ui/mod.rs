@@ -9,10 +9,12 @@ pub mod tabs;
 pub mod widgets;
 
 use crate::app::{
-    AddressKind, App, CallStatus, Focus, InputMode, ListKind, PromptKind, Section, StatusLevel,
-    Tab, TxStatus, View,
+    AddressKind, App, CallStatus, DecodedArg, Focus, InputMode, ListKind, PromptKind, Section,
+    StatusLevel, Tab, TxStatus, View,
 };
 use crate::config;
+use crate::domain::finality::Finality;
+use crate::infrastructure::abi::nested::render_call_tree;
 
 pub fn draw(f: &mut Frame, app: &mut App) {
     let size = f.size();
@@ -395,7 +397,7 @@ fn draw_command_line(f: &mut Frame, area: Rect, app: &App) {
                 Span::styled("> slot ", Style::default().fg(Color::LightCyan)),
                 Span::raw(&app.command.input),
                 Span::styled(
-                    format!("  (contract: {target}, Enter=ok Esc=cancel)"),
+                    format!("  (contract: {target}, e.g. 0, 3..6; Enter=ok Esc=cancel)"),
                     Style::default().fg(Color::DarkGray),
                 ),
             ])
@@ -783,15 +785,30 @@ fn block_items(app: &App) -> Vec<ListItem> {
             } else {
                 format!("{}", block.gas_used)
             };
-            let line = Line::from(vec![
+            let finality = match app.finality.classify(block.number) {
+                Some(Finality::Finalized) => Span::styled(" F", Style::default().fg(Color::LightGreen)),
+                Some(Finality::Safe) => Span::styled(" S", Style::default().fg(Color::LightYellow)),
+                Some(Finality::Unsafe) => Span::styled(" U", Style::default().fg(Color::DarkGray)),
+                None => Span::raw("  "),
+            };
+            let mut line = Line::from(vec![
                 pinned,
                 Span::styled(format!("{:>7}", block.number), Style::default().fg(Color::White)),
+                finality,
                 Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
                 Span::styled(format!("{:>3}", block.tx_count), Style::default().fg(Color::LightCyan)),
                 Span::styled(" txs │ ", Style::default().fg(Color::DarkGray)),
                 Span::styled(format!("{:>6}", gas_str), Style::default().fg(Color::LightGreen)),
                 Span::styled(" gas", Style::default().fg(Color::DarkGray)),
             ]);
+            if block.blob_count > 0 {
+                line.spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+                line.spans.push(Span::styled(
+                    format!("{:>2}", block.blob_count),
+                    Style::default().fg(Color::LightMagenta),
+                ));
+                line.spans.push(Span::styled(" blobs", Style::default().fg(Color::DarkGray)));
+            }
             ListItem::new(line)
         })
         .collect()
@@ -852,6 +869,7 @@ fn address_items(app: &App) -> Vec<ListItem> {
             let kind = match addr.kind {
                 AddressKind::Eoa => "EOA",
                 AddressKind::Contract => "CON",
+                AddressKind::Delegated => "DEL",
             };
             // Show "--" for unfetched balance
             let (balance_str, delta_str) = if addr.balance == 0.0 && addr.delta == 0.0 {
@@ -1005,7 +1023,18 @@ fn block_summary_lines(app: &App) -> Vec<Line<'static>> {
             Line::from(format!("Tx count: {}", block.tx_count)),
             Line::from(format!("Gas used: {}", block.gas_used)),
             Line::from(format!("Base fee: {} gwei", block.base_fee)),
+            Line::from(format!(
+                "Blobs: {} (blob base fee {})",
+                block.blob_count,
+                block.blob_base_fee.map_or("--".to_string(), wei_label)
+            )),
             Line::from(format!("Miner: {}", &block.miner)),
+            Line::from(format!(
+                "Finality: {}",
+                app.finality
+                    .classify(block.number)
+                    .map_or("unknown", |finality| finality.label())
+            )),
             Line::from(format!("Pinned: {}", pinned)),
         ];
     }
@@ -1054,6 +1083,72 @@ fn tx_inspector_lines(app: &App) -> Vec<Line<'static>> {
             Line::from(format!("Block: #{}", tx.block_number)),
             Line::from(format!("Input: {}", input_preview)),
         ];
+        if !tx.blob_hashes.is_empty() {
+            lines.push(Line::from(format!(
+                "Blobs: {}  max fee {}/blob gas  paid {}",
+                tx.blob_hashes.len(),
+                tx.max_fee_per_blob_gas.map_or("--".to_string(), wei_label),
+                tx.blob_fee.map_or("--".to_string(), wei_label)
+            )));
+            for hash in tx.blob_hashes.iter().take(6) {
+                lines.push(Line::from(Span::styled(
+                    format!("  {hash}"),
+                    Style::default().fg(Color::Gray),
+                )));
+            }
+        }
+        if let Some(deposit) = tx.deposit.as_ref() {
+            lines.push(Line::from(format!(
+                "Deposit: mint {}  system tx {}",
+                wei_label(deposit.mint),
+                if deposit.is_system_tx { "yes" } else { "no" }
+            )));
+            lines.push(Line::from(Span::styled(
+                format!("  source {}", deposit.source_hash),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+        match (tx.execution_fee, tx.l1_fee) {
+            (Some(execution), Some(l1)) => {
+                lines.push(Line::from(format!(
+                    "Fee: L2 execution {} + L1 data {} = {} ({:.0}% L1)",
+                    wei_label(execution),
+                    wei_label(l1.fee),
+                    wei_label(execution.saturating_add(l1.fee)),
+                    l1.share(execution) * 100.0
+                )));
+                let field = |value: Option<u128>| value.map_or("--".to_string(), |v| v.to_string());
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "  L1 gas used {}  base fee scalar {}  blob base fee scalar {}",
+                        field(l1.gas_used),
+                        field(l1.base_fee_scalar),
+                        field(l1.blob_base_fee_scalar)
+                    ),
+                    Style::default().fg(Color::Gray),
+                )));
+            }
+            (Some(execution), None) => lines.push(Line::from(format!("Fee: {}", wei_label(execution)))),
+            _ => {}
+        }
+        if let Some(delegate) = app.delegate_of(&tx.to) {
+            lines.push(Line::from(format!("To delegates to: {delegate}")));
+        }
+        if !tx.authorizations.is_empty() {
+            lines.push(Line::from(format!("Authorizations: {}", tx.authorizations.len())));
+            for auth in tx.authorizations.iter().take(6) {
+                let authority = auth.authority.as_deref().unwrap_or("(bad signature)");
+                let delegate = if auth.clears() {
+                    "(cleared)"
+                } else {
+                    auth.delegate.as_str()
+                };
+                lines.push(Line::from(Span::styled(
+                    format!("  {authority} -> {delegate}  chain {} nonce {}", auth.chain_id, auth.nonce),
+                    Style::default().fg(Color::Gray),
+                )));
+            }
+        }
 
         if let Some(args) = tx.decoded_args.as_ref() {
             if !args.is_empty() {
@@ -1065,10 +1160,7 @@ fn tx_inspector_lines(app: &App) -> Vec<Line<'static>> {
                         .add_modifier(Modifier::BOLD),
                 )));
                 for arg in args.iter().take(10) {
-                    let name = &arg.name;
-                    let kind = &arg.kind;
-                    let value = truncate_str(&arg.value, 72);
-                    lines.push(Line::from(format!("{name} ({kind}) = {value}")));
+                    lines.extend(decoded_arg_lines(arg));
                 }
                 if args.len() > 10 {
                     lines.push(Line::from(format!("… ({} args)", args.len())));
@@ -1083,6 +1175,8 @@ fn tx_inspector_lines(app: &App) -> Vec<Line<'static>> {
             lines.push(Line::from(truncate_str(err, 86)));
         }
 
+        lines.extend(asset_change_lines(app, &tx.hash));
+
         lines.push(Line::from(""));
         lines.push(Line::from("Enter/t: open trace"));
         lines
@@ -1096,6 +1190,7 @@ fn address_summary_lines(app: &App) -> Vec<Line<'static>> {
         let kind = match addr.kind {
             AddressKind::Eoa => "EOA",
             AddressKind::Contract => "Contract",
+            AddressKind::Delegated => "Delegated EOA (EIP-7702)",
         };
         let label = addr.label.clone().unwrap_or_else(|| "(no tag)".to_string());
         let watched = if app.watched_addresses.contains(&addr.address) {
@@ -1125,6 +1220,9 @@ fn address_summary_lines(app: &App) -> Vec<Line<'static>> {
             Line::from(format!("Delta: {}", delta_str)),
             Line::from(format!("Watched: {}", watched)),
         ];
+        if let Some(delegate) = app.delegate_of(&addr.address) {
+            lines.insert(2, Line::from(format!("Delegate: {delegate}")));
+        }
         lines.extend(token_balance_lines(app, &addr.address));
         return lines;
     }
@@ -1251,7 +1349,7 @@ fn contract_browser_lines(app: &App) -> Vec<Line<'static>> {
             .storage_cache
             .iter()
             .filter(|((addr, _), _)| addr.eq_ignore_ascii_case(&contract.address))
-            .take(6)
+            .take(12)
             .map(|((_, slot), value)| {
                 let preview = if value.len() > 18 {
                     format!("{}…", &value[..18])
@@ -1321,17 +1419,18 @@ fn trace_detail_lines(app: &App) -> Vec<Line<'static>> {
             .as_deref()
             .or(frame.selector.as_deref())
             .unwrap_or("(unknown)");
-        let input_preview = frame
-            .input
-            .as_ref()
-            .map(|input| {
-                if input.len() > 18 {
-                    format!("{}… ({} chars)", &input[..18], input.len())
+        let preview = |data: Option<&String>| {
+            data.map(|data| {
+                if data.len() > 18 {
+                    format!("{}… ({} chars)", &data[..18], data.len())
                 } else {
-                    input.clone()
+                    data.clone()
                 }
             })
-            .unwrap_or_else(|| "--".to_string());
+            .unwrap_or_else(|| "--".to_string())
+        };
+        let input_preview = preview(frame.input.as_ref());
+        let output_preview = preview(frame.output.as_ref());
 
         let mut lines = vec![
             Line::from(vec![
@@ -1347,6 +1446,7 @@ fn trace_detail_lines(app: &App) -> Vec<Line<'static>> {
             Line::from(format!("Status: {}", status)),
             Line::from(format!("Note: {}", frame.note)),
             Line::from(format!("Input: {}", input_preview)),
+            Line::from(format!("Output: {}", output_preview)),
             Line::from(format!("Collapsed: {}", collapsed)),
         ];
 
@@ -1360,10 +1460,7 @@ fn trace_detail_lines(app: &App) -> Vec<Line<'static>> {
                         .add_modifier(Modifier::BOLD),
                 )));
                 for arg in args.iter().take(10) {
-                    let name = &arg.name;
-                    let kind = &arg.kind;
-                    let value = truncate_str(&arg.value, 72);
-                    lines.push(Line::from(format!("{name} ({kind}) = {value}")));
+                    lines.extend(decoded_arg_lines(arg));
                 }
                 if args.len() > 10 {
                     lines.push(Line::from(format!("… ({} args)", args.len())));
@@ -1378,6 +1475,27 @@ fn trace_detail_lines(app: &App) -> Vec<Line<'static>> {
             lines.push(Line::from(truncate_str(err, 86)));
         }
 
+        if let Some(outputs) = frame.decoded_output.as_ref() {
+            if !outputs.is_empty() {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    "Returns",
+                    Style::default()
+                        .fg(Color::LightCyan)
+                        .add_modifier(Modifier::BOLD),
+                )));
+                for ret in outputs.iter().take(10) {
+                    let name = &ret.name;
+                    let kind = &ret.kind;
+                    let value = truncate_str(&ret.value, 72);
+                    lines.push(Line::from(format!("{name} ({kind}) = {value}")));
+                }
+                if outputs.len() > 10 {
+                    lines.push(Line::from(format!("… ({} values)", outputs.len())));
+                }
+            }
+        }
+
         lines.push(Line::from(""));
         lines.push(Line::from(
             "Enter/e toggles collapse, Esc closes trace view.",
@@ -1388,11 +1506,107 @@ fn trace_detail_lines(app: &App) -> Vec<Line<'static>> {
     }
 }
 
-fn token_balance_lines(app: &App, owner: &str) -> Vec<Line<'static>> {
-    if app.tokens.is_empty() {
+/// One decoded argument, followed by the tree of any calls nested inside it.
+fn decoded_arg_lines(arg: &DecodedArg) -> Vec<Line<'static>> {
+    const MAX_TREE_ROWS: usize = 40;
+
+    let value = truncate_str(&arg.value, 72);
+    let mut lines = vec![Line::from(format!("{} ({}) = {value}", arg.name, arg.kind))];
+    if arg.nested.is_empty() {
+        return lines;
+    }
+
+    let mut tree = Vec::new();
+    render_call_tree(&arg.nested, 1, &mut tree);
+    for row in tree.iter().take(MAX_TREE_ROWS) {
+        lines.push(Line::from(Span::styled(
+            truncate_str(row, 86),
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    if tree.len() > MAX_TREE_ROWS {
+        lines.push(Line::from(format!(
+            "  … ({} more lines)",
+            tree.len() - MAX_TREE_ROWS
+        )));
+    }
+    lines
+}
+
+fn asset_change_lines(app: &App, tx_hash: &str) -> Vec<Line<'static>> {
+    let Some(summary) = app
+        .transfer_summary
+        .as_ref()
+        .filter(|summary| summary.tx_hash.eq_ignore_ascii_case(tx_hash))
+    else {
         return Vec::new();
+    };
+    let changes = summary.net_changes();
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Asset changes ({} transfers)", summary.transfers.len()),
+            Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+        )),
+    ];
+    if changes.is_empty() {
+        lines.push(Line::from("(no net asset movement)"));
+    }
+    let mut last_address = None;
+    for change in changes.iter().take(24) {
+        let who = if last_address == Some(&change.address) {
+            String::new()
+        } else {
+            match app.label_for(&change.address) {
+                Some(label) => truncate_str(label, 13),
+                None => short_addr(&change.address),
+            }
+        };
+        last_address = Some(&change.address);
+        let (amount, increase) = change.net();
+        let (sign, color) = if increase {
+            ("+", Color::LightGreen)
+        } else {
+            ("-", Color::LightRed)
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!("{who:<14} ")),
+            Span::styled(
+                format!("{sign}{}", summary.format_amount(&change.asset, amount)),
+                Style::default().fg(color),
+            ),
+            Span::raw(format!(" {}", summary.symbol(&change.asset))),
+        ]));
+    }
+    if changes.len() > 24 {
+        lines.push(Line::from(format!("… ({} changes)", changes.len())));
+    }
+    if !summary.native_traced {
+        lines.push(Line::from(Span::styled(
+            "internal ETH transfers unavailable (no debug_traceTransaction)",
+            Style::default().fg(Color::Gray),
+        )));
     }
+    lines
+}
+
+fn token_balance_lines(app: &App, owner: &str) -> Vec<Line<'static>> {
     let owner_key = normalize_hex(owner);
+    // Configured tokens always, discovered ones only where this owner has a balance
+    let tokens: Vec<_> = app
+        .tokens
+        .iter()
+        .chain(app.discovered_tokens.iter().filter(|token| {
+            app.token_balances
+                .contains_key(&(owner_key.clone(), token.normalized_address()))
+        }))
+        .collect();
+    if tokens.is_empty() {
+        return Vec::new();
+    }
     let mut lines = Vec::new();
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
@@ -1401,7 +1615,7 @@ fn token_balance_lines(app: &App, owner: &str) -> Vec<Line<'static>> {
             .fg(Color::LightCyan)
             .add_modifier(Modifier::BOLD),
     )));
-    for token in app.tokens.iter().take(8) {
+    for token in tokens.iter().take(8) {
         let key = (owner_key.clone(), token.normalized_address());
         let balance = app
             .token_balances
@@ -1414,8 +1628,8 @@ fn token_balance_lines(app: &App, owner: &str) -> Vec<Line<'static>> {
             balance
         )));
     }
-    if app.tokens.len() > 8 {
-        lines.push(Line::from(format!("… ({} tokens)", app.tokens.len())));
+    if tokens.len() > 8 {
+        lines.push(Line::from(format!("… ({} tokens)", tokens.len())));
     }
     lines
 }
@@ -1436,6 +1650,17 @@ fn short_addr(value: &str) -> String {
     format!("{}..{}", start, end)
 }
 
+/// Wei in the largest unit that keeps it readable
+fn wei_label(wei: u128) -> String {
+    if wei < 1_000_000 {
+        format!("{wei} wei")
+    } else if wei < 1_000_000_000_000_000 {
+        format!("{:.3} gwei", wei as f64 / 1e9)
+    } else {
+        format!("{:.6} eth", wei as f64 / 1e18)
+    }
+}
+
 fn short_hash(value: &str, len: usize) -> String {
     if value.len() <= len {
         return value.to_string();
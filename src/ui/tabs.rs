@@ -6,7 +6,14 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Tabs as RataTabs};
 use ratatui::Frame;
 
-use crate::app::{App, OpsSection, Tab, ToolkitTool};
+use crate::app::{App, OpsSection, StatusLevel, Tab, ToolkitTool};
+use crate::domain::alerts::AlertState;
+use crate::domain::budget::Pressure;
+use crate::domain::metrics::MetricsWindow;
+use crate::domain::nodes::{NodeComparison, NodeVerdict};
+use crate::modules::ops::metrics::format_value;
+use crate::ui::widgets::sparkline::{sparkline_series, sparkline_text};
+use crate::ui::{short_addr, truncate_str};
 
 /// Draw the tab bar at the top
 pub fn draw_tab_bar(f: &mut Frame, area: Rect, app: &App) {
@@ -75,6 +82,12 @@ fn draw_status_bar(f: &mut Frame, area: Rect, app: &App) {
         .map(|ms| format!("{}ms", ms))
         .unwrap_or_else(|| "--".to_string());
 
+    let head = app.head_number();
+    let finalized_lag = head
+        .and_then(|head| app.finality.finalized_lag(head))
+        .map(|lag| format!("-{lag}"))
+        .unwrap_or_else(|| "--".to_string());
+
     let content = Line::from(vec![
         Span::styled("●", Style::default().fg(status_color)),
         Span::raw(" "),
@@ -84,10 +97,13 @@ fn draw_status_bar(f: &mut Frame, area: Rect, app: &App) {
         Span::raw("   "),
         Span::styled("Block ", Style::default().fg(Color::DarkGray)),
         Span::styled(
-            format!("#{}", app.blocks.first().map(|b| b.number).unwrap_or(0)),
+            format!("#{}", head.unwrap_or(0)),
             Style::default().fg(Color::White),
         ),
         Span::raw("   "),
+        Span::styled("Finalized ", Style::default().fg(Color::DarkGray)),
+        Span::raw(finalized_lag),
+        Span::raw("   "),
         Span::styled("Gas ", Style::default().fg(Color::DarkGray)),
         Span::raw("-- gwei"),
         Span::raw("   "),
@@ -177,6 +193,28 @@ fn draw_quick_access(f: &mut Frame, area: Rect, _app: &App) {
 fn draw_live_feed(f: &mut Frame, area: Rect, app: &App) {
     let mut lines = vec![Line::from("")];
 
+    // Blob usage per block, oldest to newest
+    if app.blocks.iter().any(|block| block.blob_base_fee.is_some()) {
+        let counts: Vec<u64> = app.blocks.iter().map(|block| block.blob_count as u64).collect();
+        let fee = app
+            .blocks
+            .last()
+            .and_then(|block| block.blob_base_fee)
+            .map_or("--".to_string(), super::wei_label);
+        lines.push(Line::from(vec![
+            Span::styled(" Blobs ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                sparkline_text(&counts, 30),
+                Style::default().fg(Color::LightMagenta),
+            ),
+            Span::styled(
+                format!(" {} last  fee {}", counts.last().copied().unwrap_or(0), fee),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        lines.push(Line::from(""));
+    }
+
     // Add recent blocks with their transactions
     for block in app.blocks.iter().rev().take(6) {
         lines.push(Line::from(vec![
@@ -327,13 +365,18 @@ fn draw_toolkit_content(f: &mut Frame, area: Rect, app: &App) {
         .border_style(Style::default().fg(Color::DarkGray))
         .title("Result");
 
-    let output_text = if app.toolkit_state.output.is_empty() {
-        Span::styled(" (no output)", Style::default().fg(Color::DarkGray))
+    // Output may be multi-line (e.g. a decoded call tree)
+    let output_lines: Vec<Line> = if app.toolkit_state.output.is_empty() {
+        vec![Line::from(Span::styled(" (no output)", Style::default().fg(Color::DarkGray)))]
     } else {
-        Span::styled(&app.toolkit_state.output, Style::default().fg(Color::Green))
+        app.toolkit_state
+            .output
+            .lines()
+            .map(|line| Line::from(Span::styled(format!(" {}", line), Style::default().fg(Color::Green))))
+            .collect()
     };
 
-    let output_para = Paragraph::new(Line::from(output_text)).block(output_block);
+    let output_para = Paragraph::new(output_lines).block(output_block);
     f.render_widget(output_para, chunks[1]);
 
     // History area
@@ -464,20 +507,14 @@ fn draw_ops_content(f: &mut Frame, area: Rect, app: &App) {
                 )),
             ]
         }
-        OpsSection::RpcStats => {
-            vec![
-                Line::from(""),
-                Line::from(Span::styled(
-                    " RPC STATS",
-                    Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
-                )),
-                Line::from(""),
-                Line::from(Span::styled(
-                    " (RPC call statistics will appear here)",
-                    Style::default().fg(Color::DarkGray),
-                )),
-            ]
-        }
+        OpsSection::RpcStats => rpc_stats_lines(app),
+        OpsSection::Alerts => alert_lines(app),
+        OpsSection::Metrics => metrics_lines(app),
+        OpsSection::Nodes => node_lines(app),
+        OpsSection::Beacon => beacon_lines(app),
+        OpsSection::L1Origin => l1_origin_lines(app),
+        OpsSection::Logs => log_lines(app),
+        OpsSection::Quorum => quorum_lines(app),
         _ => {
             vec![
                 Line::from(""),
@@ -503,6 +540,600 @@ fn draw_ops_content(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(paragraph, area);
 }
 
+/// One chart per collected series over the selected window
+fn metrics_lines(app: &App) -> Vec<Line<'static>> {
+    let label = Style::default().fg(Color::DarkGray);
+    let mut window_spans = vec![Span::styled(" Window: ", label)];
+    for window in MetricsWindow::ALL {
+        let style = if window == app.metrics_window {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            label
+        };
+        window_spans.push(Span::styled(format!("[{}] ", window.label()), style));
+    }
+    window_spans.push(Span::styled(" Enter to switch", label));
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            " METRICS",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(window_spans),
+        Line::from(""),
+    ];
+
+    if app.metrics.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " (Waiting for samples from the node)",
+            label,
+        )));
+        return lines;
+    }
+
+    for series in &app.metrics {
+        lines.push(Line::from(vec![
+            Span::raw(format!(" {:<26}", series.label)),
+            Span::styled(
+                format!("{:<12}", format_value(series.last, series.unit)),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "avg {}  min {}  max {}",
+                    format_value(series.avg, series.unit),
+                    format_value(series.min, series.unit),
+                    format_value(series.max, series.unit)
+                ),
+                label,
+            ),
+        ]));
+        lines.push(Line::from(Span::styled(
+            format!("   {}", sparkline_series(&series.buckets)),
+            Style::default().fg(Color::Cyan),
+        )));
+    }
+    lines
+}
+
+/// Connections, failovers and errors reported by the worker (newest first)
+fn log_lines(app: &App) -> Vec<Line<'static>> {
+    let label = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            " EVENT LOG",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    if app.event_log.is_empty() {
+        lines.push(Line::from(Span::styled(" (no events yet)", label)));
+    }
+    for entry in app.event_log.iter().rev() {
+        let time = chrono::DateTime::from_timestamp(entry.at, 0)
+            .map(|at| {
+                at.with_timezone(&chrono::Local)
+                    .format("%H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        let color = match entry.level {
+            StatusLevel::Info => Color::White,
+            StatusLevel::Warn => Color::Yellow,
+            StatusLevel::Error => Color::Red,
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {} ", time), label),
+            Span::styled(entry.message.clone(), Style::default().fg(color)),
+        ]));
+    }
+    lines
+}
+
+/// Paired beacon node: sync state, finality and how far the execution head trails it
+fn beacon_lines(app: &App) -> Vec<Line<'static>> {
+    let label = Style::default().fg(Color::DarkGray);
+    let value = Style::default().fg(Color::White);
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            " BEACON NODE",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    let Some(beacon) = &app.beacon else {
+        lines.push(Line::from(Span::styled(
+            " (No beacon node data - set beacon_url on this endpoint)",
+            label,
+        )));
+        return lines;
+    };
+    let head = app.head_number();
+    let epoch = |epoch: Option<u64>| epoch.map_or("--".to_string(), |e| e.to_string());
+    let (sync, sync_color) = if beacon.is_syncing {
+        (format!("syncing, {} slots behind", beacon.sync_distance), Color::Yellow)
+    } else {
+        ("synced".to_string(), Color::Green)
+    };
+    lines.push(Line::from(vec![
+        Span::styled(" Head slot:       ", label),
+        Span::styled(beacon.head_slot.to_string(), value),
+        Span::styled(format!("  epoch {}", beacon.head_epoch()), label),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(" Sync:            ", label),
+        Span::styled(sync, Style::default().fg(sync_color)),
+        Span::styled(format!("  distance {}", beacon.sync_distance), label),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(" Finalized epoch: ", label),
+        Span::styled(epoch(beacon.finalized_epoch), value),
+        Span::styled(
+            beacon
+                .finality_distance()
+                .map_or(String::new(), |distance| format!("  {distance} epochs ago")),
+            label,
+        ),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(" Justified epoch: ", label),
+        Span::styled(epoch(beacon.justified_epoch), value),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(" Peers:           ", label),
+        Span::styled(
+            beacon.peers.map_or("--".to_string(), |peers| peers.to_string()),
+            value,
+        ),
+    ]));
+    let lag = match beacon.el_lag(head) {
+        Some(lag) if lag > 0 => Span::styled(format!("{lag} blocks behind CL"), Style::default().fg(Color::Yellow)),
+        Some(lag) => Span::styled(format!("in step ({lag:+})"), Style::default().fg(Color::Green)),
+        None => Span::styled("--", label),
+    };
+    lines.push(Line::from(vec![
+        Span::styled(" EL head:         ", label),
+        Span::styled(head.map_or("--".to_string(), |head| head.to_string()), value),
+        Span::styled(
+            format!(
+                "  CL payload {}  ",
+                beacon.execution_head.map_or("--".to_string(), |n| n.to_string())
+            ),
+            label,
+        ),
+        lag,
+    ]));
+
+    let warnings = beacon.warnings(head);
+    lines.push(Line::from(""));
+    if warnings.is_empty() {
+        lines.push(Line::from(Span::styled(" No consensus issues", Style::default().fg(Color::Green))));
+    }
+    for warning in warnings {
+        lines.push(Line::from(Span::styled(
+            format!(" ! {warning}"),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    lines
+}
+
+/// L1 block the OP Stack chain last derived from, as read from the L1Block predeploy
+fn l1_origin_lines(app: &App) -> Vec<Line<'static>> {
+    let label = Style::default().fg(Color::DarkGray);
+    let value = Style::default().fg(Color::White);
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            " L1 ORIGIN",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    let Some(origin) = &app.l1_origin else {
+        lines.push(Line::from(Span::styled(
+            " (No L1Block predeploy - not an OP Stack chain)",
+            label,
+        )));
+        return lines;
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let scalar = |scalar: Option<u32>| scalar.map_or("--".to_string(), |s| s.to_string());
+    lines.push(Line::from(vec![
+        Span::styled(" L1 block:        ", label),
+        Span::styled(format!("#{}", origin.number), value),
+        Span::styled(format!("  {}s ago", origin.age(now)), label),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(" Hash:            ", label),
+        Span::styled(origin.hash.clone(), value),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(" Sequence number: ", label),
+        Span::styled(origin.sequence_number.to_string(), value),
+        Span::styled("  L2 blocks on this origin", label),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(" L1 base fee:     ", label),
+        Span::styled(super::wei_label(origin.base_fee), value),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(" L1 blob fee:     ", label),
+        Span::styled(origin.blob_base_fee.map_or("--".to_string(), super::wei_label), value),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(" Fee scalars:     ", label),
+        Span::styled(
+            format!(
+                "base {}  blob {}",
+                scalar(origin.base_fee_scalar),
+                scalar(origin.blob_base_fee_scalar)
+            ),
+            value,
+        ),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(" Batcher:         ", label),
+        Span::styled(origin.batcher_hash.clone(), value),
+    ]));
+    lines
+}
+
+/// Calls, errors and latency percentiles per endpoint and method
+fn rpc_stats_lines(app: &App) -> Vec<Line<'static>> {
+    let label = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            " RPC STATS",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" Sorted by: ", label),
+            Span::styled(
+                app.rpc_stats_sort.label(),
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  Enter to change", label),
+        ]),
+    ];
+    if let Some(budget) = &app.rpc_budget {
+        let color = match budget.pressure {
+            Pressure::Normal => Color::Green,
+            Pressure::Constrained => Color::Yellow,
+            Pressure::Exhausted => Color::Red,
+        };
+        let used = match budget.daily_cu {
+            Some(limit) => format!("{} / {} CU today", budget.used_cu, limit),
+            None => format!("{} CU today", budget.used_cu),
+        };
+        let mut spans = vec![
+            Span::styled(" Budget:    ", label),
+            Span::styled(budget.pressure.label(), Style::default().fg(color)),
+            Span::styled(format!("  {}  {} throttled", used, budget.throttles), label),
+        ];
+        if let Some(retry_in) = budget.retry_in {
+            spans.push(Span::styled(
+                format!("  retry in {:.0}s", retry_in.as_secs_f64().ceil()),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    if app.rpc_stats.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " (No RPC calls recorded yet)",
+            label,
+        )));
+        return lines;
+    }
+
+    lines.push(Line::from(Span::styled(
+        format!(
+            " {:<30} {:<28} {:>8} {:>7} {:>9} {:>9} {:>9}",
+            "ENDPOINT", "METHOD", "CALLS", "ERRORS", "P50", "P95", "P99"
+        ),
+        label.add_modifier(Modifier::BOLD),
+    )));
+    let ms = |value: Option<f64>| {
+        value
+            .map(|ms| format!("{:.1}ms", ms))
+            .unwrap_or_else(|| "--".to_string())
+    };
+    for row in &app.rpc_stats {
+        let errors_style = if row.errors > 0 {
+            Style::default().fg(Color::Red)
+        } else {
+            label
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {:<30} ", truncate_str(&row.endpoint, 29)), label),
+            Span::raw(format!("{:<28} {:>8} ", truncate_str(&row.method, 27), row.calls)),
+            Span::styled(format!("{:>7} ", row.errors), errors_style),
+            Span::raw(format!(
+                "{:>9} {:>9} {:>9}",
+                ms(row.p50),
+                ms(row.p95),
+                ms(row.p99)
+            )),
+        ]));
+    }
+    lines
+}
+
+/// Recent quorum reads (newest first), each member's answer under divergent ones
+fn quorum_lines(app: &App) -> Vec<Line<'static>> {
+    let label = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            " QUORUM READS",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    if app.quorum_endpoints.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " Off. Run :quorum to send balance, storage, call and receipt reads to every endpoint",
+            label,
+        )));
+    } else {
+        lines.push(Line::from(vec![
+            Span::styled(" Members: ", label),
+            Span::raw(app.quorum_endpoints.join(", ")),
+        ]));
+    }
+    lines.push(Line::from(""));
+    if app.quorum_reports.is_empty() {
+        lines.push(Line::from(Span::styled(" (no quorum reads yet)", label)));
+    }
+
+    for report in app.quorum_reports.iter().rev() {
+        let (icon, color) = if report.agreed() {
+            ("✓", Color::Green)
+        } else {
+            ("✗", Color::Red)
+        };
+        let agreeing = report.responses.len() - report.divergent().len();
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {} ", icon), Style::default().fg(color)),
+            Span::raw(format!("{:<26}", report.method)),
+            Span::styled(format!("{:<56}", truncate_str(&report.params, 55)), label),
+            Span::styled(
+                format!("{}/{} agree", agreeing, report.responses.len()),
+                Style::default().fg(color),
+            ),
+        ]));
+        if report.agreed() {
+            continue;
+        }
+        for response in &report.responses {
+            let matches = response.value.as_ref().ok() == report.majority.as_ref();
+            let (value, style) = match &response.value {
+                Ok(value) if matches => (value.clone(), label),
+                Ok(value) => (value.clone(), Style::default().fg(Color::Red)),
+                Err(err) => (format!("error: {}", err), Style::default().fg(Color::Red)),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("     {:<30} ", truncate_str(&response.endpoint, 29)), label),
+                Span::styled(truncate_str(&value, 80), style),
+            ]));
+        }
+    }
+    lines
+}
+
+/// Every configured endpoint side by side, sick nodes flagged
+fn node_lines(app: &App) -> Vec<Line<'static>> {
+    let label = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            " NODE COMPARISON",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    let Some(comparisons) = app.node_comparison.as_ref() else {
+        lines.push(Line::from(Span::styled(
+            format!(" (Polling {} endpoint(s)…)", app.rpc_endpoints.len().max(1)),
+            label,
+        )));
+        return lines;
+    };
+
+    for (index, comparison) in comparisons.iter().enumerate() {
+        if comparisons.len() > 1 {
+            if index > 0 {
+                lines.push(Line::from(""));
+            }
+            let chain = comparison
+                .chain_id
+                .map(|id| format!(" CHAIN {}", id))
+                .unwrap_or_else(|| " CHAIN UNKNOWN".to_string());
+            lines.push(Line::from(Span::styled(chain, Style::default().fg(Color::Cyan))));
+        }
+        lines.extend(comparison_lines(comparison));
+    }
+    lines
+}
+
+/// Summary and one row per node for the endpoints of one chain
+fn comparison_lines(comparison: &NodeComparison) -> Vec<Line<'static>> {
+    let label = Style::default().fg(Color::DarkGray);
+    let mut lines = Vec::new();
+    lines.push(Line::from(vec![
+        Span::styled(" Nodes: ", label),
+        Span::raw(comparison.nodes.len().to_string()),
+        Span::styled("   Unhealthy: ", label),
+        Span::styled(
+            comparison.unhealthy().to_string(),
+            Style::default().fg(if comparison.unhealthy() == 0 {
+                Color::Green
+            } else {
+                Color::Red
+            }),
+        ),
+        Span::styled("   Best head: ", label),
+        Span::raw(
+            comparison
+                .best_head
+                .map(|head| head.to_string())
+                .unwrap_or_else(|| "--".to_string()),
+        ),
+    ]));
+    if let (Some(height), Some(canonical)) = (comparison.height, comparison.canonical.as_ref()) {
+        lines.push(Line::from(vec![
+            Span::styled(format!(" Hash at #{}: ", height), label),
+            Span::raw(short_addr(canonical)),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "   {:<30} {:<12} {:>10} {:<15} {:>7} {:>6} {:<9} {}",
+            "ENDPOINT", "CLIENT", "HEAD", "HEAD HASH", "RTT", "PEERS", "SYNC", "STATUS"
+        ),
+        label,
+    )));
+
+    for node in &comparison.nodes {
+        let probe = &node.probe;
+        let color = match node.verdict {
+            NodeVerdict::Healthy => Color::Green,
+            NodeVerdict::Lagging(_) | NodeVerdict::Syncing => Color::Yellow,
+            NodeVerdict::Forked | NodeVerdict::Down => Color::Red,
+        };
+        let dash = || "--".to_string();
+        let sync = match probe.sync {
+            Some((current, highest)) if highest > 0 => {
+                format!("{:.1}%", current as f64 * 100.0 / highest as f64)
+            }
+            Some(_) => "syncing".to_string(),
+            None if probe.head.is_some() => "synced".to_string(),
+            None => dash(),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(" ● ", Style::default().fg(color)),
+            Span::raw(format!(
+                "{:<30} {:<12} {:>10} {:<15} {:>7} {:>6} {:<9} ",
+                truncate_str(&probe.endpoint, 29),
+                truncate_str(
+                    &probe.client.as_deref().map(client_name).unwrap_or_default(),
+                    11
+                ),
+                probe.head.map(|head| head.to_string()).unwrap_or_else(dash),
+                probe.head_hash.as_deref().map(short_addr).unwrap_or_else(dash),
+                probe.rtt_ms.map(|ms| format!("{}ms", ms)).unwrap_or_else(dash),
+                probe.peers.map(|peers| peers.to_string()).unwrap_or_else(dash),
+                sync,
+            )),
+            Span::styled(node.verdict.label(), Style::default().fg(color)),
+        ]));
+        if let Some(error) = &probe.error {
+            lines.push(Line::from(Span::styled(
+                format!("     {}", error),
+                Style::default().fg(Color::Red),
+            )));
+        }
+    }
+    lines
+}
+
+/// First word of a web3_clientVersion string, e.g. "Geth/v1.14.0" from "Geth/v1.14.0-stable/linux-amd64/go1.22"
+fn client_name(version: &str) -> String {
+    let mut parts = version.split('/');
+    match (parts.next(), parts.next()) {
+        (Some(name), Some(release)) => format!("{}/{}", name, release.split('-').next().unwrap_or(release)),
+        _ => version.to_string(),
+    }
+}
+
+/// Alert rules, what's firing and the fired/resolved history (newest first)
+fn alert_lines(app: &App) -> Vec<Line<'static>> {
+    let label = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            " ALERTS",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" Rules:  ", label),
+            Span::raw(app.alert_rules.len().to_string()),
+            Span::styled("   Firing: ", label),
+            Span::styled(
+                app.active_alerts.len().to_string(),
+                Style::default().fg(if app.active_alerts.is_empty() {
+                    Color::Green
+                } else {
+                    Color::Red
+                }),
+            ),
+        ]),
+    ];
+
+    for rule in &app.alert_rules {
+        let firing = app.active_alerts.get(&rule.name);
+        let (icon, color) = if firing.is_some() {
+            ("●", Color::Red)
+        } else {
+            ("○", Color::Green)
+        };
+        let mut spans = vec![
+            Span::styled(format!(" {} ", icon), Style::default().fg(color)),
+            Span::raw(format!("{:<20}", rule.name)),
+            Span::styled(rule.message.clone(), label),
+        ];
+        if let Some(event) = firing {
+            spans.push(Span::styled(
+                format!("  {}", event.detail),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(" History", label)));
+    if app.alert_history.is_empty() {
+        lines.push(Line::from(Span::styled(" (no alerts yet)", label)));
+    }
+    for event in app.alert_history.iter().rev() {
+        let time = chrono::DateTime::from_timestamp(event.at as i64, 0)
+            .map(|at| {
+                at.with_timezone(&chrono::Local)
+                    .format("%H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        let color = match event.state {
+            AlertState::Fired => Color::Red,
+            AlertState::Resolved => Color::Green,
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {} ", time), label),
+            Span::styled(
+                format!("{:<9}", event.state.as_str()),
+                Style::default().fg(color),
+            ),
+            Span::raw(format!("{:<20}", event.rule)),
+            Span::styled(event.detail.clone(), label),
+        ]));
+    }
+    lines
+}
+
 /// Draw the Anvil tab content
 pub fn draw_anvil_tab(f: &mut Frame, area: Rect, app: &App) {
     let is_anvil = app.node_kind.to_lowercase().contains("anvil");
@@ -96,6 +96,23 @@ pub fn sparkline_text(data: &[u64], width: usize) -> String {
         .collect()
 }
 
+/// Sparkline for sampled series; buckets without samples are left blank
+pub fn sparkline_series(data: &[Option<f64>]) -> String {
+    let bar_chars = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = data.iter().flatten().copied().fold(0.0, f64::max);
+
+    data.iter()
+        .map(|value| match value {
+            None => ' ',
+            Some(_) if max <= 0.0 => bar_chars[0],
+            Some(value) => {
+                let scaled = ((value / max) * 7.0).round().max(0.0) as usize;
+                bar_chars[scaled.min(7)]
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,6 +124,12 @@ mod tests {
         assert_eq!(text.chars().count(), 8);
     }
 
+    #[test]
+    fn test_sparkline_series_gaps() {
+        let text = sparkline_series(&[Some(0.0), None, Some(4.0), Some(8.0)]);
+        assert_eq!(text, "▁ ▅█");
+    }
+
     #[test]
     fn test_sparkline_text_empty() {
         let data: [u64; 0] = [];